@@ -134,7 +134,7 @@ const_ordinary! { CAL: u32;
 	UMALQURA 23
 }
 
-const_values! { CB;
+const_values! { CB in COMCTL_NAMES;
 	FIRST 0x1700
 	=>
 	SETMINVISIBLE Self::FIRST.raw() + 1
@@ -310,7 +310,7 @@ const_ws! { DTS: u32;
 	RIGHTALIGN 0x0020
 }
 
-const_values! { EM;
+const_values! { EM in COMCTL_NAMES;
 	FIRST 0x1500
 	=>
 	SETCUEBANNER Self::FIRST.raw() + 1
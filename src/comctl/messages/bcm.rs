@@ -70,7 +70,7 @@ unsafe impl<'a> MsgSend for GetNote<'a> {
 	fn as_generic_wm(&mut self) -> WndMsg {
 		WndMsg {
 			msg_id: co::BCM::GETNOTE.into(),
-			wparam: self.text.buf_len(),
+			wparam: self.text.as_ref().as_wchars_with_nul().len(),
 			lparam: unsafe { self.text.as_mut_ptr() } as _,
 		}
 	}
@@ -198,11 +198,13 @@ unsafe impl<'a> MsgSend for SetImageList<'a> {
 /// message parameters.
 ///
 /// Return type: `SysResult<()>`.
-pub struct SetNote {
-	pub text: WString,
+pub struct SetNote<S = WString> {
+	pub text: S,
 }
 
-unsafe impl MsgSend for SetNote {
+unsafe impl<S> MsgSend for SetNote<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<()>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -212,8 +214,8 @@ unsafe impl MsgSend for SetNote {
 	fn as_generic_wm(&mut self) -> WndMsg {
 		WndMsg {
 			msg_id: co::BCM::SETNOTE.into(),
-			wparam: self.text.buf_len(),
-			lparam: self.text.as_ptr() as _,
+			wparam: self.text.as_ref().as_wchars_with_nul().len(),
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
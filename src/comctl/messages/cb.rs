@@ -57,11 +57,13 @@ unsafe impl MsgSend for GetMinVisible {
 /// message parameters.
 ///
 /// Return type: `SysResult<()>`.
-pub struct SetCueBanner {
-	pub text: WString,
+pub struct SetCueBanner<S = WString> {
+	pub text: S,
 }
 
-unsafe impl MsgSend for SetCueBanner {
+unsafe impl<S> MsgSend for SetCueBanner<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<()>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -75,7 +77,7 @@ unsafe impl MsgSend for SetCueBanner {
 		WndMsg {
 			msg_id: co::CB::SETCUEBANNER.into(),
 			wparam: 0,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
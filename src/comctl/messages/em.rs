@@ -57,12 +57,14 @@ unsafe impl MsgSend for HideBalloonTip {
 /// message parameters..
 ///
 /// Return type: `SysResult<()>`.
-pub struct SetCueBanner {
+pub struct SetCueBanner<S = WString> {
 	pub show_even_with_focus: bool,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for SetCueBanner {
+unsafe impl<S> MsgSend for SetCueBanner<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<()>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -73,7 +75,7 @@ unsafe impl MsgSend for SetCueBanner {
 		WndMsg {
 			msg_id: co::EM::SETCUEBANNER.into(),
 			wparam: self.show_even_with_focus as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
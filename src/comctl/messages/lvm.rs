@@ -1356,11 +1356,13 @@ unsafe impl MsgSend for GetSelectionMark {
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct GetStringWidth {
-	pub text: WString,
+pub struct GetStringWidth<S = WString> {
+	pub text: S,
 }
 
-unsafe impl MsgSend for GetStringWidth {
+unsafe impl<S> MsgSend for GetStringWidth<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -1371,7 +1373,7 @@ unsafe impl MsgSend for GetStringWidth {
 		WndMsg {
 			msg_id: co::LVM::GETSTRINGWIDTH.into(),
 			wparam: 0,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -325,13 +325,15 @@ unsafe impl<'a> MsgSend for SetParts<'a> {
 /// message parameters.
 ///
 /// Return type: `SysResult<()>`.
-pub struct SetText {
+pub struct SetText<S = WString> {
 	pub part_index: u8,
 	pub draw_operation: co::SBT,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for SetText {
+unsafe impl<S> MsgSend for SetText<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<()>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -342,19 +344,21 @@ unsafe impl MsgSend for SetText {
 		WndMsg {
 			msg_id: co::SB::SETTEXT.into(),
 			wparam: MAKEDWORD(MAKEWORD(self.part_index, 0), self.draw_operation.raw()) as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
 
 /// [`SB_SETTIPTEXT`](https://learn.microsoft.com/en-us/windows/win32/controls/sb-settiptext)
 /// message parameters.
-pub struct SetTipText {
+pub struct SetTipText<S = WString> {
 	pub part_index: u8,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for SetTipText {
+unsafe impl<S> MsgSend for SetTipText<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = ();
 
 	fn convert_ret(&self, _: isize) -> Self::RetType {
@@ -365,7 +369,7 @@ unsafe impl MsgSend for SetTipText {
 		WndMsg {
 			msg_id: co::SB::SETTIPTEXT.into(),
 			wparam: self.part_index as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -2137,11 +2137,13 @@ unsafe impl MsgSend for SetUnicodeFormat {
 /// message parameters.
 ///
 /// Return type: `()`.
-pub struct SetWindowTheme {
-	pub visual_style: WString,
+pub struct SetWindowTheme<S = WString> {
+	pub visual_style: S,
 }
 
-unsafe impl MsgSend for SetWindowTheme {
+unsafe impl<S> MsgSend for SetWindowTheme<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = ();
 
 	fn convert_ret(&self, _: isize) -> Self::RetType {
@@ -2152,7 +2154,7 @@ unsafe impl MsgSend for SetWindowTheme {
 		WndMsg {
 			msg_id: co::TBM::SETWINDOWTHEME.into(),
 			wparam: 0,
-			lparam: self.visual_style.as_ptr() as _,
+			lparam: self.visual_style.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -1,6 +1,6 @@
 use crate::co::*;
 
-const_values! { HRESULT;
+const_values! { HRESULT in DSHOW_NAMES;
 	=>
 	VFW_E_INVALIDMEDIATYPE 0x8004_0200
 	VFW_E_INVALIDSUBTYPE 0x8004_0201
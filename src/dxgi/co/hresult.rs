@@ -1,6 +1,6 @@
 use crate::co::*;
 
-const_values! { HRESULT;
+const_values! { HRESULT in DXGI_NAMES;
 	=>
 	DXGI_STATUS_OCCLUDED 0x087a_0001
 	DXGI_STATUS_CLIPPED 0x087a_0002
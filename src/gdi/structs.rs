@@ -257,6 +257,14 @@ pub struct RGBQUAD {
 	rgbReserved: u8,
 }
 
+impl RGBQUAD {
+	/// Creates a new `RGBQUAD` object with the given color intensities.
+	#[must_use]
+	pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+		Self { rgbBlue: blue, rgbGreen: green, rgbRed: red, rgbReserved: 0 }
+	}
+}
+
 /// [`TEXTMETRIC`](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-textmetricw)
 /// struct.
 #[repr(C)]
@@ -0,0 +1,625 @@
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+
+/// A device-independent bitmap, which owns its pixel data along with the
+/// header metadata.
+///
+/// Supports uncompressed 1, 4, 8, 16, 24 and 32 bits per pixel, with color
+/// palettes and `BI_BITFIELDS` masks, stored either bottom-up or top-down.
+///
+/// Reading and writing `.bmp` bytes is a pure Rust implementation, no system
+/// calls are made. Malformed data fails with
+/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA), and compressed
+/// formats fail with
+/// [`co::ERROR::NOT_SUPPORTED`](crate::co::ERROR::NOT_SUPPORTED).
+///
+/// # Examples
+///
+/// Taking a screenshot and saving it to a `.bmp` file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let cx_screen = w::GetSystemMetrics(co::SM::CXSCREEN);
+/// let cy_screen = w::GetSystemMetrics(co::SM::CYSCREEN);
+///
+/// let hdc_screen = w::HWND::DESKTOP.GetDC()?;
+/// let hbmp = hdc_screen.CreateCompatibleBitmap(cx_screen, cy_screen)?;
+/// {
+///     let hdc_mem = hdc_screen.CreateCompatibleDC()?;
+///     let _hbmp_guard = hdc_mem.SelectObject(&*hbmp)?;
+///     hdc_mem.BitBlt(w::POINT::new(0, 0), w::SIZE::new(cx_screen, cy_screen),
+///         &hdc_screen, w::POINT::new(0, 0), co::ROP::SRCCOPY)?;
+/// } // bitmap is deselected here
+///
+/// let dib = w::Dib::from_hbitmap(&hdc_screen, &hbmp, 24)?;
+/// dib.serialize_to_file("C:\\Temp\\screen.bmp")?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+///
+/// Creating a bitmap from scratch:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut dib = w::Dib::new(64, 64, 24)?;
+/// for y in 0..64 {
+///     for x in 0..64 {
+///         dib.set_color(x, y, w::COLORREF::new(x as u8 * 4, y as u8 * 4, 0x80))?;
+///     }
+/// }
+/// let bmp_bytes = dib.serialize_to_bytes();
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Dib {
+	width: u32,
+	height: u32,
+	bit_count: u16,
+	top_down: bool,
+	masks: Option<[u32; 3]>,
+	palette: Vec<RGBQUAD>,
+	resolution: (i32, i32),
+	bits: Vec<u8>,
+}
+
+impl Dib {
+	/// Creates a new bottom-up bitmap with all pixels zeroed.
+	///
+	/// Formats with up to 8 bits per pixel receive a full grayscale palette.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER)
+	/// if the dimensions are zero or too large, or if the bit count is not 1,
+	/// 4, 8, 16, 24 or 32.
+	#[must_use]
+	pub fn new(width: u32, height: u32, bit_count: u16) -> SysResult<Self> {
+		let stride = Self::calc_stride(width, height, bit_count)
+			.ok_or(co::ERROR::INVALID_PARAMETER)?;
+
+		let palette = if bit_count <= 8 {
+			let num = 1u32 << bit_count;
+			(0..num)
+				.map(|idx| {
+					let gray = (idx * 255 / (num - 1)) as u8;
+					RGBQUAD::new(gray, gray, gray)
+				})
+				.collect()
+		} else {
+			Vec::new()
+		};
+
+		Ok(Self {
+			width,
+			height,
+			bit_count,
+			top_down: false,
+			masks: None,
+			palette,
+			resolution: (0, 0),
+			bits: vec![0; stride * height as usize],
+		})
+	}
+
+	/// Parses the contents of a `.bmp` file.
+	///
+	/// Accepts `BITMAPCOREHEADER`, `BITMAPINFOHEADER`, `BITMAPV4HEADER` and
+	/// `BITMAPV5HEADER` headers; the extra V4 and V5 fields are ignored.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		if !data.starts_with(b"BM") {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let off_bits = read_u32(data, 10)? as usize; // BITMAPFILEHEADER
+		let hdr_sz = read_u32(data, 14)? as usize;
+		let hdr_off = 14;
+
+		let (width, height, bit_count, top_down, compression, clr_used, resolution) =
+			if hdr_sz == 12 { // BITMAPCOREHEADER
+				(
+					read_u16(data, hdr_off + 4)? as i32,
+					read_u16(data, hdr_off + 6)? as i32,
+					read_u16(data, hdr_off + 10)?,
+					false,
+					co::BI::RGB,
+					0,
+					(0, 0),
+				)
+			} else if hdr_sz >= 40 { // BITMAPINFOHEADER and later versions
+				let height = read_u32(data, hdr_off + 8)? as i32;
+				(
+					read_u32(data, hdr_off + 4)? as i32,
+					height.checked_abs().ok_or(co::ERROR::INVALID_DATA)?,
+					read_u16(data, hdr_off + 14)?,
+					height < 0,
+					unsafe { co::BI::from_raw(read_u32(data, hdr_off + 16)?) },
+					read_u32(data, hdr_off + 32)?,
+					(read_u32(data, hdr_off + 24)? as i32, read_u32(data, hdr_off + 28)? as i32),
+				)
+			} else {
+				return Err(co::ERROR::INVALID_DATA);
+			};
+
+		if width <= 0 || height <= 0 {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let stride = Self::calc_stride(width as _, height as _, bit_count)
+			.ok_or(co::ERROR::INVALID_DATA)?;
+
+		let mut pal_off = hdr_off + hdr_sz;
+		let masks = match compression {
+			co::BI::RGB => None,
+			co::BI::BITFIELDS if bit_count == 16 || bit_count == 32 => {
+				let masks_off = if hdr_sz >= 52 {
+					hdr_off + 40 // masks are part of V2 and later headers
+				} else {
+					pal_off += 12;
+					hdr_off + hdr_sz
+				};
+				Some([
+					read_u32(data, masks_off)?,
+					read_u32(data, masks_off + 4)?,
+					read_u32(data, masks_off + 8)?,
+				])
+			},
+			co::BI::BITFIELDS => return Err(co::ERROR::INVALID_DATA),
+			_ => return Err(co::ERROR::NOT_SUPPORTED),
+		};
+
+		let num_colors = match clr_used {
+			0 if bit_count <= 8 => 1 << bit_count,
+			n if bit_count <= 8 && n > 1 << bit_count => return Err(co::ERROR::INVALID_DATA),
+			n => n as usize,
+		};
+		let entry_sz = if hdr_sz == 12 { 3 } else { 4 }; // RGBTRIPLE or RGBQUAD
+		let palette = (0..num_colors)
+			.map(|idx| {
+				let off = pal_off + idx * entry_sz;
+				data.get(off..off + 3)
+					.map(|c| RGBQUAD::new(c[2], c[1], c[0]))
+					.ok_or(co::ERROR::INVALID_DATA)
+			})
+			.collect::<SysResult<Vec<_>>>()?;
+
+		let bits = data.get(off_bits..)
+			.and_then(|rest| rest.get(..stride * height as usize))
+			.ok_or(co::ERROR::INVALID_DATA)?
+			.to_vec();
+
+		Ok(Self {
+			width: width as _,
+			height: height as _,
+			bit_count,
+			top_down,
+			masks,
+			palette,
+			resolution,
+			bits,
+		})
+	}
+
+	/// Parses a `.bmp` file directly from the disk. The file will be
+	/// [mapped in memory](crate::FileMapped) during reading.
+	#[must_use]
+	pub fn parse_from_file(file_path: &str) -> SysResult<Self> {
+		let fin = FileMapped::open(file_path, FileAccess::ExistingReadOnly)?;
+		Self::parse(fin.as_slice())
+	}
+
+	/// Reads the pixels of a bitmap with
+	/// [`HDC::GetDIBits`](crate::prelude::gdi_Hdc::GetDIBits), converting them
+	/// to the given bit count.
+	///
+	/// The bitmap must not be selected into a device context.
+	#[must_use]
+	pub fn from_hbitmap(hdc: &HDC, hbmp: &HBITMAP, bit_count: u16) -> SysResult<Self> {
+		let mut bm = BITMAP::default();
+		hbmp.GetObject(&mut bm)?;
+		let mut dib = Self::new(bm.bmWidth as _, bm.bmHeight.unsigned_abs(), bit_count)?;
+
+		let mut bmi_buf = dib.info_buf();
+		unsafe {
+			hdc.GetDIBits(hbmp, 0, dib.height, Some(&mut dib.bits),
+				&mut *(bmi_buf.as_mut_ptr() as *mut BITMAPINFO), co::DIB::RGB_COLORS)?;
+		}
+
+		let pal_off = 10; // the palette comes right after the 40-byte header
+		for (idx, color) in dib.palette.iter_mut().enumerate() {
+			let [blue, green, red, _] = bmi_buf[pal_off + idx].to_le_bytes();
+			*color = RGBQUAD::new(red, green, blue);
+		}
+		Ok(dib)
+	}
+
+	/// Creates a bitmap compatible with the given device context with
+	/// [`HDC::CreateCompatibleBitmap`](crate::prelude::gdi_Hdc::CreateCompatibleBitmap),
+	/// then copies the pixels into it with
+	/// [`HDC::SetDIBits`](crate::prelude::gdi_Hdc::SetDIBits).
+	///
+	/// Note that a memory device context creates monochrome bitmaps; usually
+	/// you'll want to pass the screen device context.
+	#[must_use]
+	pub fn to_hbitmap(&self, hdc: &HDC) -> SysResult<DeleteObjectGuard<HBITMAP>> {
+		let hbmp = hdc.CreateCompatibleBitmap(self.width as _, self.height as _)?;
+		let bmi_buf = self.info_buf();
+		hdc.SetDIBits(&hbmp, 0, self.height, &self.bits,
+			unsafe { &*(bmi_buf.as_ptr() as *const BITMAPINFO) }, co::DIB::RGB_COLORS)?;
+		Ok(hbmp)
+	}
+
+	/// Serializes the bitmap to the contents of a `.bmp` file, with a
+	/// `BITMAPINFOHEADER` header.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> Vec<u8> {
+		let info = self.info_bytes();
+		let off_bits = 14 + info.len();
+
+		let mut buf = Vec::with_capacity(off_bits + self.bits.len());
+		buf.extend_from_slice(b"BM");
+		buf.extend_from_slice(&((off_bits + self.bits.len()) as u32).to_le_bytes());
+		buf.extend_from_slice(&[0; 4]); // bfReserved1, bfReserved2
+		buf.extend_from_slice(&(off_bits as u32).to_le_bytes());
+		buf.extend_from_slice(&info);
+		buf.extend_from_slice(&self.bits);
+		buf
+	}
+
+	/// Serializes the bitmap directly to a `.bmp` file.
+	pub fn serialize_to_file(&self, file_path: &str) -> SysResult<()> {
+		let fout = File::open(file_path, FileAccess::OpenOrCreateRW)?;
+		fout.erase_and_write(&self.serialize_to_bytes())?;
+		Ok(())
+	}
+
+	/// Returns the width in pixels.
+	#[must_use]
+	pub const fn width(&self) -> u32 {
+		self.width
+	}
+
+	/// Returns the height in pixels.
+	#[must_use]
+	pub const fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Returns the number of bits per pixel.
+	#[must_use]
+	pub const fn bit_count(&self) -> u16 {
+		self.bit_count
+	}
+
+	/// Returns the number of bytes of each row, which is padded to a multiple
+	/// of 4.
+	#[must_use]
+	pub const fn stride(&self) -> usize {
+		((self.width as usize * self.bit_count as usize + 31) & !31) / 8
+	}
+
+	/// Tells whether the rows are stored from top to bottom. By default,
+	/// bitmaps are stored bottom-up.
+	#[must_use]
+	pub const fn is_top_down(&self) -> bool {
+		self.top_down
+	}
+
+	/// Changes the order in which the rows are stored, reordering the pixel
+	/// data accordingly. The image itself is not changed.
+	pub fn set_top_down(&mut self, top_down: bool) {
+		if self.top_down != top_down {
+			let stride = self.stride();
+			let num_rows = self.height as usize;
+			for y in 0..num_rows / 2 {
+				let (head, tail) = self.bits.split_at_mut((num_rows - 1 - y) * stride);
+				head[y * stride..(y + 1) * stride].swap_with_slice(&mut tail[..stride]);
+			}
+			self.top_down = top_down;
+		}
+	}
+
+	/// Returns the red, green and blue `BI_BITFIELDS` masks of a 16 or 32-bit
+	/// bitmap, if any. If there are no masks, 16-bit pixels are 5-5-5 and
+	/// 32-bit pixels are 8-8-8.
+	#[must_use]
+	pub const fn bit_masks(&self) -> Option<[u32; 3]> {
+		self.masks
+	}
+
+	/// Sets the red, green and blue `BI_BITFIELDS` masks. The pixel data is not
+	/// changed.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER)
+	/// if the bitmap is not 16 or 32-bit.
+	pub fn set_bit_masks(&mut self, masks: Option<[u32; 3]>) -> SysResult<()> {
+		if masks.is_some() && self.bit_count != 16 && self.bit_count != 32 {
+			return Err(co::ERROR::INVALID_PARAMETER);
+		}
+		self.masks = masks;
+		Ok(())
+	}
+
+	/// Returns the color palette. Bitmaps with more than 8 bits per pixel
+	/// usually have no palette.
+	#[must_use]
+	pub fn palette(&self) -> &[RGBQUAD] {
+		&self.palette
+	}
+
+	/// Replaces the color palette. The pixel data is not changed.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER)
+	/// if the palette has more colors than the bit count allows, or more than
+	/// 256 colors.
+	pub fn set_palette(&mut self, palette: Vec<RGBQUAD>) -> SysResult<()> {
+		if palette.len() > 256
+			|| (self.bit_count <= 8 && palette.len() > 1 << self.bit_count)
+		{
+			return Err(co::ERROR::INVALID_PARAMETER);
+		}
+		self.palette = palette;
+		Ok(())
+	}
+
+	/// Returns the horizontal and vertical resolution, in pixels per meter.
+	#[must_use]
+	pub const fn resolution(&self) -> (i32, i32) {
+		self.resolution
+	}
+
+	/// Sets the horizontal and vertical resolution, in pixels per meter.
+	pub fn set_resolution(&mut self, x_pels_per_meter: i32, y_pels_per_meter: i32) {
+		self.resolution = (x_pels_per_meter, y_pels_per_meter);
+	}
+
+	/// Returns the raw pixel data, with the rows in storage order.
+	#[must_use]
+	pub fn bits(&self) -> &[u8] {
+		&self.bits
+	}
+
+	/// Returns the raw pixel data, with the rows in storage order.
+	#[must_use]
+	pub fn bits_mut(&mut self) -> &mut [u8] {
+		&mut self.bits
+	}
+
+	/// Returns the raw bytes of the given row, counted from the top of the
+	/// image regardless of the storage order.
+	///
+	/// # Panics
+	///
+	/// Panics if `y` is out of bounds.
+	#[must_use]
+	pub fn row(&self, y: u32) -> &[u8] {
+		let off = self.row_offset(y);
+		&self.bits[off..off + self.stride()]
+	}
+
+	/// Returns the raw bytes of the given row, counted from the top of the
+	/// image regardless of the storage order.
+	///
+	/// # Panics
+	///
+	/// Panics if `y` is out of bounds.
+	#[must_use]
+	pub fn row_mut(&mut self, y: u32) -> &mut [u8] {
+		let off = self.row_offset(y);
+		let stride = self.stride();
+		&mut self.bits[off..off + stride]
+	}
+
+	/// Returns the raw value of the given pixel: a palette index for bitmaps
+	/// up to 8 bits per pixel, otherwise the packed color.
+	///
+	/// # Panics
+	///
+	/// Panics if `x` or `y` are out of bounds.
+	#[must_use]
+	pub fn pixel(&self, x: u32, y: u32) -> u32 {
+		assert!(x < self.width, "Pixel out of bounds.");
+		let row = self.row(y);
+		let x = x as usize;
+		match self.bit_count {
+			1 => ((row[x / 8] >> (7 - x % 8)) & 0b1) as _,
+			4 => ((row[x / 2] >> ((1 - x % 2) * 4)) & 0xf) as _,
+			8 => row[x] as _,
+			16 => u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]) as _,
+			24 => u32::from_le_bytes([row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 0]),
+			_ => u32::from_le_bytes([row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]]),
+		}
+	}
+
+	/// Sets the raw value of the given pixel: a palette index for bitmaps up
+	/// to 8 bits per pixel, otherwise the packed color. Exceeding bits are
+	/// ignored.
+	///
+	/// # Panics
+	///
+	/// Panics if `x` or `y` are out of bounds.
+	pub fn set_pixel(&mut self, x: u32, y: u32, value: u32) {
+		assert!(x < self.width, "Pixel out of bounds.");
+		let bit_count = self.bit_count;
+		let row = self.row_mut(y);
+		let x = x as usize;
+		match bit_count {
+			1 => {
+				let shift = 7 - x % 8;
+				row[x / 8] = (row[x / 8] & !(1 << shift)) | (((value & 0b1) as u8) << shift);
+			},
+			4 => {
+				let shift = (1 - x % 2) * 4;
+				row[x / 2] = (row[x / 2] & !(0xf << shift)) | (((value & 0xf) as u8) << shift);
+			},
+			8 => row[x] = value as _,
+			16 => row[x * 2..x * 2 + 2].copy_from_slice(&(value as u16).to_le_bytes()),
+			24 => row[x * 3..x * 3 + 3].copy_from_slice(&value.to_le_bytes()[..3]),
+			_ => row[x * 4..x * 4 + 4].copy_from_slice(&value.to_le_bytes()),
+		}
+	}
+
+	/// Returns the color of the given pixel, resolving the palette or the bit
+	/// masks. Palette indexes beyond the palette return black.
+	///
+	/// # Panics
+	///
+	/// Panics if `x` or `y` are out of bounds.
+	#[must_use]
+	pub fn color(&self, x: u32, y: u32) -> COLORREF {
+		let val = self.pixel(x, y);
+		if self.bit_count <= 8 {
+			self.palette.get(val as usize)
+				.map_or(COLORREF::new(0, 0, 0), |c| COLORREF::new(c.rgbRed, c.rgbGreen, c.rgbBlue))
+		} else if self.bit_count == 24 {
+			let [blue, green, red, _] = val.to_le_bytes();
+			COLORREF::new(red, green, blue)
+		} else {
+			let [mr, mg, mb] = self.effective_masks();
+			COLORREF::new(
+				extract_channel(val, mr),
+				extract_channel(val, mg),
+				extract_channel(val, mb),
+			)
+		}
+	}
+
+	/// Sets the color of the given pixel. For bitmaps up to 8 bits per pixel,
+	/// the nearest palette color is used.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER)
+	/// if the pixel is out of bounds, or if the palette is empty.
+	pub fn set_color(&mut self, x: u32, y: u32, color: COLORREF) -> SysResult<()> {
+		if x >= self.width || y >= self.height {
+			return Err(co::ERROR::INVALID_PARAMETER);
+		}
+		let (red, green, blue) = (color.GetRValue(), color.GetGValue(), color.GetBValue());
+
+		let val = if self.bit_count <= 8 {
+			self.palette.iter()
+				.enumerate()
+				.min_by_key(|(_, c)| {
+					let dr = c.rgbRed as i32 - red as i32;
+					let dg = c.rgbGreen as i32 - green as i32;
+					let db = c.rgbBlue as i32 - blue as i32;
+					dr * dr + dg * dg + db * db
+				})
+				.map(|(idx, _)| idx as u32)
+				.ok_or(co::ERROR::INVALID_PARAMETER)?
+		} else if self.bit_count == 24 {
+			u32::from_le_bytes([blue, green, red, 0])
+		} else {
+			let [mr, mg, mb] = self.effective_masks();
+			insert_channel(red, mr) | insert_channel(green, mg) | insert_channel(blue, mb)
+		};
+
+		self.set_pixel(x, y, val);
+		Ok(())
+	}
+
+	/// Returns the row stride, or `None` if the parameters are invalid or the
+	/// total size would overflow.
+	fn calc_stride(width: u32, height: u32, bit_count: u16) -> Option<usize> {
+		if width == 0 || height == 0 || width > i32::MAX as _ || height > i32::MAX as _
+			|| ![1, 4, 8, 16, 24, 32].contains(&bit_count)
+		{
+			return None;
+		}
+		let stride = (width as usize).checked_mul(bit_count as _)?
+			.checked_add(31)? / 32 * 4;
+		stride.checked_mul(height as _)
+			.filter(|tot| *tot <= u32::MAX as usize - 0x1_0000) // leave room for the headers
+			.map(|_| stride)
+	}
+
+	fn row_offset(&self, y: u32) -> usize {
+		assert!(y < self.height, "Row out of bounds.");
+		let storage_row = if self.top_down { y } else { self.height - 1 - y };
+		storage_row as usize * self.stride()
+	}
+
+	fn effective_masks(&self) -> [u32; 3] {
+		self.masks.unwrap_or(if self.bit_count == 16 {
+			[0x7c00, 0x03e0, 0x001f]
+		} else {
+			[0x00ff_0000, 0x0000_ff00, 0x0000_00ff]
+		})
+	}
+
+	/// Serializes the `BITMAPINFOHEADER`, the masks and the palette.
+	fn info_bytes(&self) -> Vec<u8> {
+		let mut hdr = BITMAPINFOHEADER::default();
+		hdr.biWidth = self.width as _;
+		hdr.biHeight = if self.top_down { -(self.height as i32) } else { self.height as _ };
+		hdr.biPlanes = 1;
+		hdr.biBitCount = self.bit_count;
+		hdr.biCompression = if self.masks.is_some() { co::BI::BITFIELDS } else { co::BI::RGB };
+		hdr.biSizeImage = self.bits.len() as _;
+		hdr.biXPelsPerMeter = self.resolution.0;
+		hdr.biYPelsPerMeter = self.resolution.1;
+		hdr.biClrUsed = if self.bit_count <= 8 && self.palette.len() == 1 << self.bit_count {
+			0 // full palette
+		} else {
+			self.palette.len() as _
+		};
+
+		let mut buf = Vec::with_capacity(40 + 12 + self.palette.len() * 4);
+		buf.extend_from_slice(hdr.serialize());
+		if let Some(masks) = self.masks {
+			masks.iter().for_each(|mask| buf.extend_from_slice(&mask.to_le_bytes()));
+		}
+		for c in self.palette.iter() {
+			buf.extend_from_slice(&[c.rgbBlue, c.rgbGreen, c.rgbRed, 0]);
+		}
+		buf
+	}
+
+	/// Returns the `BITMAPINFO` data in a buffer aligned for the struct. For
+	/// indexed formats, there is always room for the full palette.
+	fn info_buf(&self) -> Vec<u32> {
+		let info = self.info_bytes();
+		let num_colors = if self.bit_count <= 8 { 1 << self.bit_count } else { 0 };
+		let mut buf = vec![0u32; (info.len() / 4).max(10 + 3 + num_colors)];
+		info.chunks(4)
+			.zip(buf.iter_mut())
+			.for_each(|(b, dest)| *dest = u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+		buf
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Extracts a color channel given its mask, scaling it to 8 bits.
+fn extract_channel(val: u32, mask: u32) -> u8 {
+	if mask == 0 {
+		return 0;
+	}
+	let max = mask >> mask.trailing_zeros();
+	let chan = (val & mask) >> mask.trailing_zeros();
+	((chan as u64 * 255 + max as u64 / 2) / max as u64) as _
+}
+
+/// Scales an 8-bit color channel to the given mask.
+fn insert_channel(chan: u8, mask: u32) -> u32 {
+	if mask == 0 {
+		return 0;
+	}
+	let max = mask >> mask.trailing_zeros();
+	let scaled = (chan as u64 * max as u64 + 127) / 255;
+	(scaled as u32) << mask.trailing_zeros()
+}
+
+fn read_u16(data: &[u8], off: usize) -> SysResult<u16> {
+	data.get(off..off + 2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
+
+fn read_u32(data: &[u8], off: usize) -> SysResult<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
@@ -0,0 +1,3 @@
+mod dib;
+
+pub use dib::Dib;
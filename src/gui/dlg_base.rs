@@ -4,12 +4,42 @@ use crate::gui::{events::*, privs::*};
 use crate::msg::*;
 use crate::prelude::*;
 
+/// Where a dialog window is loaded from.
+pub(in crate::gui) enum DlgSrc {
+	/// A dialog resource ID.
+	Id(u16),
+	/// A serialized `DLGTEMPLATEEX`, kept in a `u32` buffer because the
+	/// template must be aligned on a DWORD boundary.
+	Template(Vec<u32>),
+}
+
+impl DlgSrc {
+	/// Serializes the template.
+	///
+	/// # Panics
+	///
+	/// Panics if the template exceeds the limits of the format.
+	pub(in crate::gui) fn from_template(template: &DialogTemplate) -> Self {
+		let bytes = template.build()
+			.unwrap_or_else(|err| panic!("Invalid dialog template: {err}"));
+		Self::Template(
+			bytes.chunks(4)
+				.map(|chunk| {
+					let mut dword = [0u8; 4];
+					dword[..chunk.len()].copy_from_slice(chunk);
+					u32::from_ne_bytes(dword)
+				})
+				.collect(),
+		)
+	}
+}
+
 /// Base to all dialog windows.
 ///
 /// Owns the window procedure for all dialog windows.
 pub(in crate::gui) struct DlgBase {
 	base: Base,
-	dialog_id: u16,
+	src: DlgSrc,
 }
 
 impl Drop for DlgBase {
@@ -21,10 +51,10 @@ impl Drop for DlgBase {
 }
 
 impl DlgBase {
-	pub(in crate::gui) fn new(parent: Option<&Base>, dialog_id: u16) -> Self {
+	pub(in crate::gui) fn new(parent: Option<&Base>, src: DlgSrc) -> Self {
 		Self {
 			base: Base::new(true, parent),
-			dialog_id,
+			src,
 		}
 	}
 
@@ -56,15 +86,19 @@ impl DlgBase {
 
 		// Our hwnd member is set during WM_INITDIALOG processing; already set
 		// when CreateDialogParam returns.
+		let hinst = self.base.parent_hinstance()?;
+		let hparent = self.base.parent().map(|parent| parent.hwnd());
+		// Pass pointer to Self.
+		// At this moment, the parent struct is already created and pinned.
+		let init_param = Some(self as *const _ as _);
 		unsafe {
-			self.base.parent_hinstance()?.CreateDialogParam(
-				IdStr::Id(self.dialog_id),
-				self.base.parent().map(|parent| parent.hwnd()),
-				Self::dialog_proc,
-				// Pass pointer to Self.
-				// At this moment, the parent struct is already created and pinned.
-				Some(self as *const _ as _),
-			)?;
+			match &self.src {
+				DlgSrc::Id(dialog_id) => hinst.CreateDialogParam(
+					IdStr::Id(*dialog_id), hparent, Self::dialog_proc, init_param)?,
+				DlgSrc::Template(buf) => hinst.CreateDialogIndirectParam(
+					&*(buf.as_ptr() as *const DLGTEMPLATE),
+					hparent, Self::dialog_proc, init_param)?,
+			};
 		}
 
 		Ok(())
@@ -77,15 +111,19 @@ impl DlgBase {
 
 		// Our hwnd member is set during WM_INITDIALOG processing; already set
 		// when DialogBoxParam returns.
+		let hinst = self.base.parent_hinstance()?;
+		let hparent = self.base.parent().map(|parent| parent.hwnd());
+		// Pass pointer to Self.
+		// At this moment, the parent struct is already created and pinned.
+		let init_param = Some(self as *const _ as _);
 		let ret = unsafe {
-			self.base.parent_hinstance()?.DialogBoxParam(
-				IdStr::Id(self.dialog_id),
-				self.base.parent().map(|parent| parent.hwnd()),
-				Self::dialog_proc,
-				// Pass pointer to Self.
-				// At this moment, the parent struct is already created and pinned.
-				Some(self as *const _ as _),
-			)?
+			match &self.src {
+				DlgSrc::Id(dialog_id) => hinst.DialogBoxParam(
+					IdStr::Id(*dialog_id), hparent, Self::dialog_proc, init_param)?,
+				DlgSrc::Template(buf) => hinst.DialogBoxIndirectParam(
+					&*(buf.as_ptr() as *const DLGTEMPLATE),
+					hparent, Self::dialog_proc, init_param)?,
+			}
 		};
 
 		Ok(ret as _)
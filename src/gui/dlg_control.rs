@@ -23,7 +23,7 @@ pub(in crate::gui) struct DlgControl(Pin<Arc<Obj>>);
 impl DlgControl {
 	pub(in crate::gui) fn new(
 		parent: &Base,
-		dialog_id: u16,
+		src: DlgSrc,
 		position: POINT,
 		resize_behavior: (Horz, Vert),
 		ctrl_id: Option<u16>,
@@ -32,7 +32,7 @@ impl DlgControl {
 		let new_self = Self(
 			Arc::pin(
 				Obj {
-					dlg_base: DlgBase::new(Some(parent), dialog_id),
+					dlg_base: DlgBase::new(Some(parent), src),
 					position,
 					ctrl_id: ctrl_id.unwrap_or_else(|| auto_ctrl_id()),
 					_pin: PhantomPinned,
@@ -23,7 +23,7 @@ pub(in crate::gui) struct DlgMain(Pin<Arc<Obj>>);
 
 impl DlgMain {
 	pub(in crate::gui) fn new(
-		dialog_id: u16,
+		src: DlgSrc,
 		icon_id: Option<u16>,
 		accel_table_id: Option<u16>,
 	) -> Self
@@ -31,7 +31,7 @@ impl DlgMain {
 		let new_self = Self(
 			Arc::pin(
 				Obj {
-					dlg_base: DlgBase::new(None, dialog_id),
+					dlg_base: DlgBase::new(None, src),
 					icon_id,
 					accel_table_id,
 					_pin: PhantomPinned,
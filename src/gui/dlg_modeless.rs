@@ -22,14 +22,14 @@ pub(in crate::gui) struct DlgModeless(Pin<Arc<Obj>>);
 impl DlgModeless {
 	pub(in crate::gui) fn new(
 		parent: &Base,
-		dialog_id: u16,
+		src: DlgSrc,
 		position: POINT,
 	) -> Self
 	{
 		let new_self = Self(
 			Arc::pin(
 				Obj {
-					dlg_base: DlgBase::new(Some(parent), dialog_id),
+					dlg_base: DlgBase::new(Some(parent), src),
 					position,
 					_pin: PhantomPinned,
 				},
@@ -178,7 +178,7 @@ impl UpDown {
 					self.set_range(opts.range.0, opts.range.1);
 					if opts.up_down_style.has(co::UDS::AUTOBUDDY) {
 						let prev_ctrl = self.hwnd().GetWindow(co::GW::HWNDPREV)?;
-						prev_ctrl.SetWindowText(&opts.range.0.to_string())?;
+						prev_ctrl.SetWindowText(opts.range.0.to_string())?;
 					}
 				}
 			},
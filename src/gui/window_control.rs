@@ -127,7 +127,51 @@ impl WindowControl {
 			RawDlg::Dlg(
 				DlgControl::new(
 					parent_base_ref,
-					dialog_id,
+					DlgSrc::Id(dialog_id),
+					position,
+					resize_behavior,
+					ctrl_id,
+				),
+			),
+		)
+	}
+
+	/// Instantiates a new `WindowControl` object, to be created from an
+	/// in-memory [`DialogTemplate`](crate::DialogTemplate) with
+	/// [`HINSTANCE::CreateDialogIndirectParam`](crate::prelude::user_Hinstance::CreateDialogIndirectParam).
+	///
+	/// The template should have the [`co::WS::CHILD`](crate::co::WS::CHILD)
+	/// style and the [`co::DS::CONTROL`](crate::co::DS::CONTROL) dialog style.
+	///
+	/// If the parent window is a dialog, position is in Dialog Template Units;
+	/// otherwise in pixels, which will be multiplied to match current system
+	/// DPI.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `WindowControl` in an event closure.
+	///
+	/// Panics if the template exceeds the limits of the format.
+	#[must_use]
+	pub fn new_dlg_template(
+		parent: &impl GuiParent,
+		template: &DialogTemplate,
+		position: POINT,
+		resize_behavior: (Horz, Vert),
+		ctrl_id: Option<u16>,
+	) -> Self
+	{
+		if *parent.hwnd() != HWND::NULL {
+			panic!("Cannot create a custom child control after the parent window is created.");
+		}
+
+		let parent_base_ref = unsafe { Base::from_guiparent(parent) };
+		Self(
+			RawDlg::Dlg(
+				DlgControl::new(
+					parent_base_ref,
+					DlgSrc::from_template(template),
 					position,
 					resize_behavior,
 					ctrl_id,
@@ -95,7 +95,31 @@ impl WindowMain {
 	{
 		Self(
 			RawDlg::Dlg(
-				DlgMain::new(dialog_id, icon_id, accel_table_id),
+				DlgMain::new(DlgSrc::Id(dialog_id), icon_id, accel_table_id),
+			),
+		)
+	}
+
+	/// Instantiates a new `WindowMain` object, to be created from an in-memory
+	/// [`DialogTemplate`](crate::DialogTemplate) with
+	/// [`HINSTANCE::CreateDialogIndirectParam`](crate::prelude::user_Hinstance::CreateDialogIndirectParam).
+	///
+	/// The icon and the accelerator table, if any, are still loaded from the
+	/// resources.
+	///
+	/// # Panics
+	///
+	/// Panics if the template exceeds the limits of the format.
+	#[must_use]
+	pub fn new_dlg_template(
+		template: &DialogTemplate,
+		icon_id: Option<u16>,
+		accel_table_id: Option<u16>,
+	) -> Self
+	{
+		Self(
+			RawDlg::Dlg(
+				DlgMain::new(DlgSrc::from_template(template), icon_id, accel_table_id),
 			),
 		)
 	}
@@ -89,7 +89,28 @@ impl WindowModal {
 		let parent_base_ref = unsafe { Base::from_guiparent(parent) };
 		Self(
 			RawDlg::Dlg(
-				DlgModal::new(parent_base_ref, dialog_id),
+				DlgModal::new(parent_base_ref, DlgSrc::Id(dialog_id)),
+			),
+		)
+	}
+
+	/// Instantiates a new `WindowModal` object, to be created from an
+	/// in-memory [`DialogTemplate`](crate::DialogTemplate) with
+	/// [`HINSTANCE::DialogBoxIndirectParam`](crate::prelude::user_Hinstance::DialogBoxIndirectParam).
+	///
+	/// # Panics
+	///
+	/// Panics if the template exceeds the limits of the format.
+	#[must_use]
+	pub fn new_dlg_template(
+		parent: &impl GuiParent,
+		template: &DialogTemplate,
+	) -> Self
+	{
+		let parent_base_ref = unsafe { Base::from_guiparent(parent) };
+		Self(
+			RawDlg::Dlg(
+				DlgModal::new(parent_base_ref, DlgSrc::from_template(template)),
 			),
 		)
 	}
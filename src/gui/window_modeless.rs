@@ -119,7 +119,44 @@ impl WindowModeless {
 			RawDlg::Dlg(
 				DlgModeless::new(
 					parent_base_ref,
-					dialog_id,
+					DlgSrc::Id(dialog_id),
+					position,
+				),
+			),
+		)
+	}
+
+	/// Instantiates a new `WindowModeless` object, to be created from an
+	/// in-memory [`DialogTemplate`](crate::DialogTemplate) with
+	/// [`HINSTANCE::CreateDialogIndirectParam`](crate::prelude::user_Hinstance::CreateDialogIndirectParam).
+	///
+	/// If the parent window is a dialog, position is in Dialog Template Units;
+	/// otherwise in pixels, which will be multiplied to match current system
+	/// DPI.
+	///
+	/// # Panics
+	///
+	/// Panics if the parent dialog was already created – that is, you cannot
+	/// dynamically create a `WindowModeless` in an event closure.
+	///
+	/// Panics if the template exceeds the limits of the format.
+	#[must_use]
+	pub fn new_dlg_template(
+		parent: &impl GuiParent,
+		template: &DialogTemplate,
+		position: POINT,
+	) -> Self
+	{
+		if *parent.hwnd() != HWND::NULL {
+			panic!("Cannot create a modeless window after the parent window is created.");
+		}
+
+		let parent_base_ref = unsafe { Base::from_guiparent(parent) };
+		Self(
+			RawDlg::Dlg(
+				DlgModeless::new(
+					parent_base_ref,
+					DlgSrc::from_template(template),
 					position,
 				),
 			),
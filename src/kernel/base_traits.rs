@@ -1,6 +1,6 @@
 #![allow(non_snake_case)]
 
-use std::{fmt, hash, ops};
+use std::{borrow::Cow, fmt, hash, ops};
 
 use crate::co;
 use crate::decl::*;
@@ -35,35 +35,153 @@ pub trait IntUnderlying: Sized
 /// If the values of this constant type can be combined as bitflags, it will
 /// also implement the [`NativeBitflag`](crate::prelude::NativeBitflag) trait.
 ///
+/// The [`Debug`](std::fmt::Debug) implementation shows the symbolic name of
+/// the value, like `WM::SIZE`, if it matches one of the public constants.
+///
 /// Prefer importing this trait through the prelude:
 ///
 /// ```no_run
 /// use winsafe::prelude::*;
 /// ```
-pub trait NativeConst: IntUnderlying
+pub trait NativeConst: IntUnderlying + 'static
 	+ Default + fmt::Debug + fmt::Display
 	+ fmt::LowerHex + fmt::UpperHex
 	+ fmt::Binary + fmt::Octal
-{}
+{
+	/// The name of the constant type, like `WM`.
+	///
+	/// Defaults to an empty string, for types which don't declare it.
+	const TYPE_NAME: &'static str = "";
+
+	/// Returns the names and values of all public constants of this type.
+	///
+	/// There is more than one table when some values are declared by other
+	/// features, which are included only if enabled. Defaults to no tables, so
+	/// no value has a symbolic name.
+	#[must_use]
+	fn name_tables() -> &'static [&'static [(&'static str, Self)]] {
+		&[]
+	}
+
+	/// Returns the symbolic name of the value, without the type prefix, if it
+	/// matches one of the public constants of this type.
+	///
+	/// If more than one constant has this value, the first one declared is
+	/// returned.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// assert_eq!(co::WM::SIZE.name(), Some("SIZE"));
+	/// ```
+	#[must_use]
+	fn name(&self) -> Option<&'static str> {
+		Self::name_tables()
+			.iter()
+			.flat_map(|table| table.iter())
+			.find(|(_, val)| val == self)
+			.map(|(name, _)| *name)
+	}
+
+	/// Returns the public constant with the given symbolic name, which is
+	/// case-sensitive and may be prefixed by the type name, like `WM::SIZE`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// assert_eq!(co::WM::from_name("SIZE"), Some(co::WM::SIZE));
+	/// assert_eq!(co::WM::from_name("WM::SIZE"), Some(co::WM::SIZE));
+	/// ```
+	#[must_use]
+	fn from_name(name: &str) -> Option<Self> {
+		let name = name.trim();
+		let name = name.strip_prefix(Self::TYPE_NAME)
+			.and_then(|rest| rest.strip_prefix("::"))
+			.unwrap_or(name);
+		Self::name_tables()
+			.iter()
+			.flat_map(|table| table.iter())
+			.find(|(val_name, _)| *val_name == name)
+			.map(|(_, val)| *val)
+	}
+}
 
 /// A native typed bitflag constant.
 ///
+/// The [`Display`](std::fmt::Display) and [`Debug`](std::fmt::Debug)
+/// implementations show the flags returned by
+/// [`iter_flags`](crate::prelude::NativeBitflag::iter_flags), like
+/// `WS::CHILD | WS::VISIBLE | 0x8`. The same syntax is parsed by the
+/// [`FromStr`](std::str::FromStr) implementation, which also accepts flag
+/// names without the type prefix, and decimal numbers.
+///
 /// Prefer importing this trait through the prelude:
 ///
 /// ```no_run
 /// use winsafe::prelude::*;
 /// ```
 pub trait NativeBitflag: NativeConst
-	+ ops::BitAnd + ops::BitAndAssign
-	+ ops::BitOr + ops::BitOrAssign
-	+ ops::BitXor + ops::BitXorAssign
-	+ ops::Not
+	+ ops::BitAnd<Output = Self> + ops::BitAndAssign
+	+ ops::BitOr<Output = Self> + ops::BitOrAssign
+	+ ops::BitXor<Output = Self> + ops::BitXorAssign
+	+ ops::Not<Output = Self>
 {
 	/// Tells whether other bitflag style is present.
 	///
 	/// Equivalent to `(val & other) != 0`.
 	#[must_use]
 	fn has(&self, other: Self) -> bool;
+
+	/// Returns an iterator over the public constants whose bits are all set in
+	/// the value, followed by the remaining unknown bits, if any.
+	///
+	/// Constants with more bits set are matched first, so combinations like
+	/// [`WS::CAPTION`](crate::co::WS::CAPTION) are returned instead of their
+	/// parts, [`WS::BORDER`](crate::co::WS::BORDER) and
+	/// [`WS::DLGFRAME`](crate::co::WS::DLGFRAME). Among constants with the same
+	/// value, the first one declared is returned, so
+	/// [`WS::OVERLAPPEDWINDOW`](crate::co::WS::OVERLAPPEDWINDOW) is shown as its
+	/// alias `WS::TILEDWINDOW`. Constants with zero value are never returned.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let style = co::WS::CHILD | co::WS::VISIBLE;
+	/// for flag in style.iter_flags() {
+	///     println!("{:?}", flag);
+	/// }
+	/// ```
+	#[must_use]
+	fn iter_flags(&self) -> std::vec::IntoIter<Self> {
+		let zero = Self::default();
+		let bit_count = |val: &Self| format!("{:b}", val).matches('1').count();
+		let mut known = Self::name_tables()
+			.iter()
+			.flat_map(|table| table.iter())
+			.map(|(_, val)| *val)
+			.filter(|val| *val != zero)
+			.collect::<Vec<_>>();
+		known.sort_by_key(|val| std::cmp::Reverse(bit_count(val))); // stable, keeps declaration order
+
+		let mut flags = Vec::new();
+		let mut rest = *self;
+		for val in known {
+			if (rest & val) == val {
+				flags.push(val);
+				rest &= !val;
+			}
+		}
+		if rest != zero {
+			flags.push(rest); // unknown bits
+		}
+		flags.into_iter()
+	}
 }
 
 /// A native typed constant mapped to a string.
@@ -73,6 +191,71 @@ pub trait NativeStrConst: PartialEq + Eq
 	+ fmt::Debug + fmt::Display
 {}
 
+/// A string which can be passed to native Win32 functions as a null-terminated
+/// [`WStr`](crate::WStr).
+///
+/// Implemented for [`&str`](str) and [`String`](std::string::String), which are
+/// converted on each call, and for [`WStr`](crate::WStr) and
+/// [`WString`](crate::WString), which are borrowed without any allocation.
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait ToWStr {
+	/// Returns the null-terminated wide string, converting it only if needed.
+	#[must_use]
+	fn to_wstr(&self) -> Cow<'_, WStr>;
+}
+
+/// Attaches a context string to the error of a [`Result`](std::result::Result),
+/// like [`SysResult`](crate::SysResult) and [`HrResult`](crate::HrResult),
+/// converting it into a [`ContextError`](crate::ContextError).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// fn open_key(name: &str) -> Result<w::guard::RegCloseKeyGuard, w::ContextError<co::ERROR>> {
+///     w::HKEY::LOCAL_MACHINE.RegOpenKeyEx(
+///         Some(name),
+///         co::REG_OPTION::default(),
+///         co::KEY::READ,
+///     ).err_context_with(|| format!("while opening HKEY_LOCAL_MACHINE\\{}", name))
+/// }
+/// ```
+pub trait ResultContext<T, E> {
+	/// Attaches the given context string to the error, if any.
+	fn err_context(self, context: impl Into<String>) -> Result<T, ContextError<E>>;
+
+	/// Attaches the context string returned by the closure to the error, if
+	/// any. The closure is called only in case of error.
+	fn err_context_with<S, F>(self, func: F) -> Result<T, ContextError<E>>
+		where S: Into<String>,
+			F: FnOnce() -> S;
+}
+
+impl<T, E> ResultContext<T, E> for Result<T, E> {
+	fn err_context(self, context: impl Into<String>) -> Result<T, ContextError<E>> {
+		self.map_err(|err| ContextError::new(context, err))
+	}
+
+	fn err_context_with<S, F>(self, func: F) -> Result<T, ContextError<E>>
+		where S: Into<String>,
+			F: FnOnce() -> S,
+	{
+		self.map_err(|err| ContextError::new(func(), err))
+	}
+}
+
 /// A system error which can be formatted with
 /// [`FormatMessage`](crate::FormatMessage).
 pub trait FormattedError: Into<u32> {
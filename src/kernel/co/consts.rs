@@ -550,6 +550,171 @@ const_ordinary! { HF32: u32;
 	DEFAULT 1
 }
 
+const_ordinary! { IMAGE_DIRECTORY_ENTRY: u16;
+	/// [`IMAGE_OPTIONAL_HEADER64`](crate::IMAGE_OPTIONAL_HEADER64)
+	/// `DataDirectory` index (`u16`).
+	=>
+	=>
+	EXPORT 0
+	IMPORT 1
+	RESOURCE 2
+	EXCEPTION 3
+	SECURITY 4
+	BASERELOC 5
+	DEBUG 6
+	ARCHITECTURE 7
+	GLOBALPTR 8
+	TLS 9
+	LOAD_CONFIG 10
+	BOUND_IMPORT 11
+	IAT 12
+	DELAY_IMPORT 13
+	COM_DESCRIPTOR 14
+}
+
+const_bitflag! { IMAGE_DLLCHARACTERISTICS: u16;
+	/// [`IMAGE_OPTIONAL_HEADER64`](crate::IMAGE_OPTIONAL_HEADER64)
+	/// `DllCharacteristics` (`u16`).
+	=>
+	=>
+	HIGH_ENTROPY_VA 0x0020
+	DYNAMIC_BASE 0x0040
+	FORCE_INTEGRITY 0x0080
+	NX_COMPAT 0x0100
+	NO_ISOLATION 0x0200
+	NO_SEH 0x0400
+	NO_BIND 0x0800
+	APPCONTAINER 0x1000
+	WDM_DRIVER 0x2000
+	GUARD_CF 0x4000
+	TERMINAL_SERVER_AWARE 0x8000
+}
+
+const_bitflag! { IMAGE_FILE: u16;
+	/// [`IMAGE_FILE_HEADER`](crate::IMAGE_FILE_HEADER) `Characteristics`
+	/// (`u16`).
+	=>
+	=>
+	RELOCS_STRIPPED 0x0001
+	EXECUTABLE_IMAGE 0x0002
+	LINE_NUMS_STRIPPED 0x0004
+	LOCAL_SYMS_STRIPPED 0x0008
+	AGGRESIVE_WS_TRIM 0x0010
+	LARGE_ADDRESS_AWARE 0x0020
+	BYTES_REVERSED_LO 0x0080
+	MACHINE_32BIT 0x0100
+	DEBUG_STRIPPED 0x0200
+	REMOVABLE_RUN_FROM_SWAP 0x0400
+	NET_RUN_FROM_SWAP 0x0800
+	SYSTEM 0x1000
+	DLL 0x2000
+	UP_SYSTEM_ONLY 0x4000
+	BYTES_REVERSED_HI 0x8000
+}
+
+const_ordinary! { IMAGE_FILE_MACHINE: u16;
+	/// [`IMAGE_FILE_HEADER`](crate::IMAGE_FILE_HEADER) `Machine`
+	/// [types](https://learn.microsoft.com/en-us/windows/win32/sysinfo/image-file-machine-constants)
+	/// (`u16`).
+	=>
+	=>
+	UNKNOWN 0
+	TARGET_HOST 0x0001
+	I386 0x014c
+	R3000 0x0162
+	R4000 0x0166
+	R10000 0x0168
+	WCEMIPSV2 0x0169
+	ALPHA 0x0184
+	SH3 0x01a2
+	SH3DSP 0x01a3
+	SH3E 0x01a4
+	SH4 0x01a6
+	SH5 0x01a8
+	ARM 0x01c0
+	THUMB 0x01c2
+	ARMNT 0x01c4
+	AM33 0x01d3
+	POWERPC 0x01f0
+	POWERPCFP 0x01f1
+	IA64 0x0200
+	MIPS16 0x0266
+	ALPHA64 0x0284
+	MIPSFPU 0x0366
+	MIPSFPU16 0x0466
+	TRICORE 0x0520
+	CEF 0x0cef
+	EBC 0x0ebc
+	AMD64 0x8664
+	M32R 0x9041
+	ARM64 0xaa64
+	CEE 0xc0ee
+}
+
+const_bitflag! { IMAGE_SCN: u32;
+	/// [`IMAGE_SECTION_HEADER`](crate::IMAGE_SECTION_HEADER) `Characteristics`
+	/// (`u32`).
+	=>
+	=>
+	TYPE_NO_PAD 0x0000_0008
+	CNT_CODE 0x0000_0020
+	CNT_INITIALIZED_DATA 0x0000_0040
+	CNT_UNINITIALIZED_DATA 0x0000_0080
+	LNK_OTHER 0x0000_0100
+	LNK_INFO 0x0000_0200
+	LNK_REMOVE 0x0000_0800
+	LNK_COMDAT 0x0000_1000
+	NO_DEFER_SPEC_EXC 0x0000_4000
+	GPREL 0x0000_8000
+	MEM_PURGEABLE 0x0002_0000
+	MEM_LOCKED 0x0004_0000
+	MEM_PRELOAD 0x0008_0000
+	ALIGN_1BYTES 0x0010_0000
+	ALIGN_2BYTES 0x0020_0000
+	ALIGN_4BYTES 0x0030_0000
+	ALIGN_8BYTES 0x0040_0000
+	ALIGN_16BYTES 0x0050_0000
+	ALIGN_32BYTES 0x0060_0000
+	ALIGN_64BYTES 0x0070_0000
+	ALIGN_128BYTES 0x0080_0000
+	ALIGN_256BYTES 0x0090_0000
+	ALIGN_512BYTES 0x00a0_0000
+	ALIGN_1024BYTES 0x00b0_0000
+	ALIGN_2048BYTES 0x00c0_0000
+	ALIGN_4096BYTES 0x00d0_0000
+	ALIGN_8192BYTES 0x00e0_0000
+	ALIGN_MASK 0x00f0_0000
+	LNK_NRELOC_OVFL 0x0100_0000
+	MEM_DISCARDABLE 0x0200_0000
+	MEM_NOT_CACHED 0x0400_0000
+	MEM_NOT_PAGED 0x0800_0000
+	MEM_SHARED 0x1000_0000
+	MEM_EXECUTE 0x2000_0000
+	MEM_READ 0x4000_0000
+	MEM_WRITE 0x8000_0000
+}
+
+const_ordinary! { IMAGE_SUBSYSTEM: u16;
+	/// [`IMAGE_OPTIONAL_HEADER64`](crate::IMAGE_OPTIONAL_HEADER64) `Subsystem`
+	/// (`u16`).
+	=>
+	=>
+	UNKNOWN 0
+	NATIVE 1
+	WINDOWS_GUI 2
+	WINDOWS_CUI 3
+	OS2_CUI 5
+	POSIX_CUI 7
+	NATIVE_WINDOWS 8
+	WINDOWS_CE_GUI 9
+	EFI_APPLICATION 10
+	EFI_BOOT_SERVICE_DRIVER 11
+	EFI_RUNTIME_DRIVER 12
+	EFI_ROM 13
+	XBOX 14
+	WINDOWS_BOOT_APPLICATION 16
+}
+
 const_bitflag! { KEY: u32;
 	/// [Registry access rights](https://learn.microsoft.com/en-us/windows/win32/sysinfo/registry-key-security-and-access-rights)
 	/// (`u32`).
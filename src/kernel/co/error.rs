@@ -32,7 +32,8 @@ const_no_debug_display! { ERROR: u32;
 	/// ```
 	///
 	/// The numbers inside the brackets are the system error code, in
-	/// hexadecimal and decimal formats for convenience.
+	/// hexadecimal and decimal formats for convenience. The `Debug` output is
+	/// also prefixed by the symbolic name, like `ERROR::OUT_OF_PAPER`.
 }
 
 impl std::error::Error for ERROR {
@@ -54,12 +55,109 @@ impl std::fmt::Display for ERROR {
 }
 impl std::fmt::Debug for ERROR {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if let Some(val_name) = crate::prelude::NativeConst::name(self) {
+			write!(f, "ERROR::{} ", val_name)?;
+		}
 		std::fmt::Display::fmt(self, f)
 	}
 }
 
 impl FormattedError for ERROR {}
 
+impl From<ERROR> for std::io::Error {
+	/// Converts into an [`std::io::Error`](std::io::Error) whose
+	/// [`raw_os_error`](std::io::Error::raw_os_error) is the error code, so no
+	/// information is lost.
+	fn from(err: ERROR) -> Self {
+		Self::from_raw_os_error(err.0 as _)
+	}
+}
+
+impl TryFrom<std::io::Error> for ERROR {
+	type Error = std::io::Error;
+
+	/// Retrieves the error code from an [`std::io::Error`](std::io::Error)
+	/// created from a raw OS error, or wrapping an `ERROR`, possibly within a
+	/// [`ContextError`](crate::ContextError). Otherwise, the original error is
+	/// given back.
+	fn try_from(err: std::io::Error) -> Result<Self, Self::Error> {
+		if let Some(code) = err.raw_os_error() {
+			return Ok(unsafe { Self::from_raw(code as _) });
+		}
+		match err.get_ref() {
+			Some(inner) => if let Some(inner) = inner.downcast_ref::<Self>() {
+				Ok(*inner)
+			} else if let Some(inner) = inner.downcast_ref::<crate::ContextError<Self>>() {
+				Ok(*inner.error())
+			} else {
+				Err(err)
+			},
+			None => Err(err),
+		}
+	}
+}
+
+impl ERROR {
+	/// Classifies the error code into an
+	/// [`std::io::ErrorKind`](std::io::ErrorKind), using the same table of the
+	/// standard library on Windows.
+	///
+	/// Codes without a matching kind return
+	/// [`ErrorKind::Other`](std::io::ErrorKind::Other).
+	#[must_use]
+	pub const fn kind(self) -> std::io::ErrorKind {
+		use std::io::ErrorKind;
+		match self {
+			Self::ACCESS_DENIED => ErrorKind::PermissionDenied,
+			Self::ALREADY_EXISTS
+				| Self::FILE_EXISTS => ErrorKind::AlreadyExists,
+			Self::BROKEN_PIPE
+				| Self::NO_DATA => ErrorKind::BrokenPipe,
+			Self::FILE_NOT_FOUND
+				| Self::PATH_NOT_FOUND
+				| Self::INVALID_DRIVE
+				| Self::BAD_NETPATH
+				| Self::BAD_NET_NAME => ErrorKind::NotFound,
+			Self::INVALID_NAME
+				| Self::BAD_PATHNAME
+				| Self::FILENAME_EXCED_RANGE => ErrorKind::InvalidFilename,
+			Self::INVALID_PARAMETER => ErrorKind::InvalidInput,
+			Self::NOT_ENOUGH_MEMORY
+				| Self::OUTOFMEMORY => ErrorKind::OutOfMemory,
+			Self::SEM_TIMEOUT
+				| Self::DRIVER_CANCEL_TIMEOUT
+				| Self::OPERATION_ABORTED
+				| Self::SERVICE_REQUEST_TIMEOUT
+				| Self::COUNTER_TIMEOUT
+				| Self::TIMEOUT
+				| Self::RESOURCE_CALL_TIMED_OUT
+				| Self::CTX_MODEM_RESPONSE_TIMEOUT
+				| Self::CTX_CLIENT_QUERY_TIMEOUT
+				| Self::DS_TIMELIMIT_EXCEEDED
+				| Self::IPSEC_IKE_TIMED_OUT
+				| Self::RUNLEVEL_SWITCH_TIMEOUT
+				| Self::RUNLEVEL_SWITCH_AGENT_TIMEOUT => ErrorKind::TimedOut,
+			Self::CALL_NOT_IMPLEMENTED => ErrorKind::Unsupported,
+			Self::HOST_UNREACHABLE => ErrorKind::HostUnreachable,
+			Self::NETWORK_UNREACHABLE => ErrorKind::NetworkUnreachable,
+			Self::DIRECTORY => ErrorKind::NotADirectory,
+			Self::DIRECTORY_NOT_SUPPORTED => ErrorKind::IsADirectory,
+			Self::DIR_NOT_EMPTY => ErrorKind::DirectoryNotEmpty,
+			Self::WRITE_PROTECT => ErrorKind::ReadOnlyFilesystem,
+			Self::DISK_FULL
+				| Self::HANDLE_DISK_FULL => ErrorKind::StorageFull,
+			Self::SEEK_ON_DEVICE => ErrorKind::NotSeekable,
+			Self::DISK_QUOTA_EXCEEDED => ErrorKind::QuotaExceeded,
+			Self::FILE_TOO_LARGE => ErrorKind::FileTooLarge,
+			Self::BUSY => ErrorKind::ResourceBusy,
+			Self::POSSIBLE_DEADLOCK => ErrorKind::Deadlock,
+			Self::NOT_SAME_DEVICE => ErrorKind::CrossesDevices,
+			Self::TOO_MANY_LINKS => ErrorKind::TooManyLinks,
+			_ => ErrorKind::Other,
+		}
+	}
+}
+
 const_values! { ERROR;
 	=>
 	/// The operation completed successfully.
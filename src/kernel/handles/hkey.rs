@@ -786,7 +786,38 @@ pub trait kernel_Hkey: Handle {
 		value_name: Option<&str>,
 	) -> SysResult<RegistryValue>
 	{
-		let value_name_w = WString::from_opt_str(value_name);
+		let value_name_w = value_name.map(WString::from_str);
+		self.RegQueryValueEx_wstr(value_name_w.as_deref())
+	}
+
+	/// [`RegQueryValueEx`](https://learn.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regqueryvalueexw)
+	/// function, with the value name as a borrowed [`WStr`](crate::WStr).
+	///
+	/// Unlike
+	/// [`HKEY::RegQueryValueEx`](crate::prelude::kernel_Hkey::RegQueryValueEx),
+	/// the name is not converted on each call, so a name built with the
+	/// [`w!`](crate::w) macro involves no allocation.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hkey = w::HKEY::CURRENT_USER.RegOpenKeyEx(
+	///     Some("Control Panel\\Mouse"),
+	///     co::REG_OPTION::default(),
+	///     co::KEY::READ,
+	/// )?;
+	///
+	/// let val = hkey.RegQueryValueEx_wstr(Some(w::w!("Beep")))?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	fn RegQueryValueEx_wstr(&self,
+		value_name: Option<&WStr>,
+	) -> SysResult<RegistryValue>
+	{
+		let value_name_ptr = value_name.map_or(std::ptr::null(), |name| name.as_ptr());
 		let mut raw_data_type1 = u32::default();
 		let mut data_len1 = u32::default();
 
@@ -795,7 +826,7 @@ pub trait kernel_Hkey: Handle {
 			unsafe {
 				ffi::RegQueryValueExW(
 					self.ptr(),
-					value_name_w.as_ptr(),
+					value_name_ptr,
 					std::ptr::null_mut(),
 					&mut raw_data_type1,
 					std::ptr::null_mut(),
@@ -815,7 +846,7 @@ pub trait kernel_Hkey: Handle {
 			unsafe {
 				ffi::RegQueryValueExW(
 					self.ptr(),
-					value_name_w.as_ptr(),
+					value_name_ptr,
 					std::ptr::null_mut(),
 					&mut raw_data_type2,
 					buf.as_mut_ptr() as _,
@@ -1,5 +1,6 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use crate::co;
 use crate::decl::*;
 use crate::guard::*;
 use crate::kernel::{ffi, privs::*};
@@ -13,6 +14,45 @@ impl_handle! { HPIPE;
 
 impl kernel_Hpipe for HPIPE {}
 
+impl std::io::Read for HPIPE {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		std::io::Read::read(&mut &*self, buf)
+	}
+}
+impl std::io::Read for &HPIPE {
+	/// Reads with [`HPIPE::ReadFile`](crate::prelude::kernel_Hpipe::ReadFile).
+	/// When the write end of the pipe is closed, returns `Ok(0)`, signaling the
+	/// end of the stream.
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // ReadFile takes an u32 length
+		match self.ReadFile(&mut buf[..len], None) {
+			Ok(num_read) => Ok(num_read as _),
+			Err(co::ERROR::BROKEN_PIPE) => Ok(0),
+			Err(e) => Err(e.into()),
+		}
+	}
+}
+
+impl std::io::Write for HPIPE {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		std::io::Write::write(&mut &*self, buf)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		std::io::Write::flush(&mut &*self)
+	}
+}
+impl std::io::Write for &HPIPE {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // WriteFile takes an u32 length
+		Ok(self.WriteFile(&buf[..len], None)? as _)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(()) // writes aren't buffered
+	}
+}
+
 /// This trait is enabled with the `kernel` feature, and provides methods for
 /// [`HPIPE`](crate::HPIPE).
 ///
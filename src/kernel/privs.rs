@@ -14,6 +14,9 @@ pub(crate) const MAX_MODULE_NAME32: usize = 255;
 pub(crate) const MAX_PATH: usize = 260;
 pub(crate) const SECURITY_DESCRIPTOR_REVISION: u32 = 1;
 pub(crate) const SECURITY_SQOS_PRESENT: u32 = 0x0010_0000;
+pub(crate) const TICKS_PER_MS: u64 = 10_000; // FILETIME 100-nanosecond intervals
+pub(crate) const TICKS_PER_SEC: u64 = 1_000 * TICKS_PER_MS;
+pub(crate) const TICKS_PER_DAY: u64 = 86_400 * TICKS_PER_SEC;
 
 /// [`IS_INTRESOURCE`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-is_intresource)
 /// macro.
@@ -143,3 +146,62 @@ pub(crate) fn create_wstr_ptr_vecs(
 		None => (Vec::default(), Vec::default()),
 	}
 }
+
+/// Returns the number of days from January 1, 1601 to the given date, in the
+/// proleptic Gregorian calendar. Earlier dates return negative numbers.
+///
+/// Algorithm from:
+/// * https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub(crate) const fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let yoe = year - era * 400; // [0, 399]
+	let mp = ((month + 9) % 12) as i64; // [0, 11], starting in March
+	let doy = (153 * mp + 2) / 5 + day as i64 - 1; // [0, 365]
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // [0, 146096]
+	era * 146_097 + doe - 584_694 // days from 0000-03-01 to 1601-01-01
+}
+
+/// Inverse of [`days_from_civil`], returns the year, month and day.
+pub(crate) const fn civil_from_days(days: i64) -> (i64, u32, u32) {
+	let days = days + 584_694;
+	let era = days.div_euclid(146_097);
+	let doe = days - era * 146_097; // [0, 146096]
+	let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365; // [0, 399]
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+	let mp = (5 * doy + 2) / 153; // [0, 11], starting in March
+	let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+	let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+	(year, month, day)
+}
+
+/// Returns the number of days in the given month, taking leap years into
+/// account.
+pub(crate) const fn days_in_month(year: i64, month: u32) -> u32 {
+	match month {
+		2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// Converts a [`Duration`](std::time::Duration) to FILETIME 100-nanosecond
+/// intervals, truncating the remaining nanoseconds. Returns `None` on
+/// overflow.
+pub(crate) const fn duration_to_ticks(dur: &std::time::Duration) -> Option<u64> {
+	match dur.as_secs().checked_mul(TICKS_PER_SEC) {
+		Some(ticks) => ticks.checked_add(dur.subsec_nanos() as u64 / 100),
+		None => None,
+	}
+}
+
+/// Converts FILETIME 100-nanosecond intervals to a
+/// [`Duration`](std::time::Duration).
+pub(crate) const fn ticks_to_duration(ticks: u64) -> std::time::Duration {
+	std::time::Duration::new(
+		ticks / TICKS_PER_SEC,
+		(ticks % TICKS_PER_SEC) as u32 * 100,
+	)
+}
@@ -71,8 +71,24 @@ pub struct DISK_SPACE_INFORMATION {
 /// [`FILETIME`](https://learn.microsoft.com/en-us/windows/win32/api/minwinbase/ns-minwinbase-filetime)
 /// struct.
 ///
+/// Holds the number of 100-nanosecond intervals since January 1, 1601 (UTC),
+/// which can be directly read with [`ticks`](crate::FILETIME::ticks).
+///
 /// Can be converted to [`SYSTEMTIME`](crate::SYSTEMTIME) with
-/// [`FileTimeToSystemTime`](crate::FileTimeToSystemTime) function.
+/// [`SYSTEMTIME::from_filetime`](crate::SYSTEMTIME::from_filetime), or with
+/// [`FileTimeToSystemTime`](crate::FileTimeToSystemTime) function. Can also be
+/// converted from and to [`SystemTime`](std::time::SystemTime) and
+/// [`Duration`](std::time::Duration):
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+/// use std::time::{Duration, SystemTime};
+///
+/// let ft = w::FILETIME::try_from(SystemTime::now())?;
+/// let later = ft.checked_add(Duration::from_secs(60)).unwrap();
+/// let st = SystemTime::try_from(later)?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
 #[repr(C)]
 #[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FILETIME {
@@ -80,11 +96,187 @@ pub struct FILETIME {
 	pub dwHighDateTime: u32,
 }
 
+impl std::cmp::PartialOrd for FILETIME {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl std::cmp::Ord for FILETIME {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.ticks().cmp(&other.ticks())
+	}
+}
+
+impl std::ops::Add<std::time::Duration> for FILETIME {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics on overflow. See
+	/// [`checked_add`](crate::FILETIME::checked_add) for a version without
+	/// panic.
+	fn add(self, rhs: std::time::Duration) -> Self::Output {
+		self.checked_add(rhs)
+			.expect("Overflow when adding duration to FILETIME.")
+	}
+}
+impl std::ops::Sub<std::time::Duration> for FILETIME {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics on overflow. See
+	/// [`checked_sub`](crate::FILETIME::checked_sub) for a version without
+	/// panic.
+	fn sub(self, rhs: std::time::Duration) -> Self::Output {
+		self.checked_sub(rhs)
+			.expect("Overflow when subtracting duration from FILETIME.")
+	}
+}
+
+impl From<FILETIME> for std::time::Duration {
+	/// Converts the 100-nanosecond intervals into a `Duration`, which is useful
+	/// when the `FILETIME` holds a time interval, like the ones returned by
+	/// [`HPROCESS::GetProcessTimes`](crate::prelude::kernel_Hprocess::GetProcessTimes).
+	fn from(ft: FILETIME) -> Self {
+		ticks_to_duration(ft.ticks())
+	}
+}
+
+impl TryFrom<std::time::Duration> for FILETIME {
+	type Error = co::ERROR;
+
+	/// Converts the `Duration` into 100-nanosecond intervals, truncating the
+	/// remaining nanoseconds.
+	///
+	/// Fails with
+	/// [`co::ERROR::ARITHMETIC_OVERFLOW`](crate::co::ERROR::ARITHMETIC_OVERFLOW)
+	/// if the `Duration` doesn't fit.
+	fn try_from(dur: std::time::Duration) -> Result<Self, Self::Error> {
+		duration_to_ticks(&dur)
+			.map(Self::from_ticks)
+			.ok_or(co::ERROR::ARITHMETIC_OVERFLOW)
+	}
+}
+
+impl TryFrom<FILETIME> for std::time::SystemTime {
+	type Error = co::ERROR;
+
+	/// Fails with
+	/// [`co::ERROR::ARITHMETIC_OVERFLOW`](crate::co::ERROR::ARITHMETIC_OVERFLOW)
+	/// if the `FILETIME` can't be represented as a `SystemTime` in the current
+	/// platform.
+	fn try_from(ft: FILETIME) -> Result<Self, Self::Error> {
+		let (ticks, epoch) = (ft.ticks(), FILETIME::UNIX_EPOCH.ticks());
+		if ticks >= epoch {
+			Self::UNIX_EPOCH.checked_add(ticks_to_duration(ticks - epoch))
+		} else {
+			Self::UNIX_EPOCH.checked_sub(ticks_to_duration(epoch - ticks))
+		}.ok_or(co::ERROR::ARITHMETIC_OVERFLOW)
+	}
+}
+
+impl TryFrom<std::time::SystemTime> for FILETIME {
+	type Error = co::ERROR;
+
+	/// Converts the `SystemTime` into 100-nanosecond intervals, truncating the
+	/// remaining nanoseconds.
+	///
+	/// Fails with
+	/// [`co::ERROR::ARITHMETIC_OVERFLOW`](crate::co::ERROR::ARITHMETIC_OVERFLOW)
+	/// if the `SystemTime` is before January 1, 1601, or too far in the future.
+	fn try_from(time: std::time::SystemTime) -> Result<Self, Self::Error> {
+		match time.duration_since(std::time::SystemTime::UNIX_EPOCH) {
+			Ok(after) => Self::UNIX_EPOCH.checked_add(after),
+			Err(e) => { // before the Unix epoch, round to the earlier tick
+				let before = e.duration();
+				let ceil = before + std::time::Duration::from_nanos(99);
+				Self::UNIX_EPOCH.checked_sub(ceil)
+			},
+		}.ok_or(co::ERROR::ARITHMETIC_OVERFLOW)
+	}
+}
+
+impl FILETIME {
+	/// The Unix epoch, January 1, 1970 (UTC).
+	pub const UNIX_EPOCH: Self = Self::from_ticks(116_444_736_000_000_000);
+
+	/// Creates a new `FILETIME` from the number of 100-nanosecond intervals
+	/// since January 1, 1601 (UTC).
+	#[must_use]
+	pub const fn from_ticks(ticks: u64) -> Self {
+		Self {
+			dwLowDateTime: ticks as u32,
+			dwHighDateTime: (ticks >> 32) as u32,
+		}
+	}
+
+	/// Returns the number of 100-nanosecond intervals since January 1, 1601
+	/// (UTC), by joining the high and low parts.
+	#[must_use]
+	pub const fn ticks(&self) -> u64 {
+		((self.dwHighDateTime as u64) << 32) | self.dwLowDateTime as u64
+	}
+
+	/// Adds a [`Duration`](std::time::Duration), truncating the remaining
+	/// nanoseconds. Returns `None` on overflow.
+	#[must_use]
+	pub const fn checked_add(&self, dur: std::time::Duration) -> Option<Self> {
+		match duration_to_ticks(&dur) {
+			Some(dur_ticks) => match self.ticks().checked_add(dur_ticks) {
+				Some(ticks) => Some(Self::from_ticks(ticks)),
+				None => None,
+			},
+			None => None,
+		}
+	}
+
+	/// Subtracts a [`Duration`](std::time::Duration), truncating the remaining
+	/// nanoseconds. Returns `None` on overflow.
+	#[must_use]
+	pub const fn checked_sub(&self, dur: std::time::Duration) -> Option<Self> {
+		match duration_to_ticks(&dur) {
+			Some(dur_ticks) => match self.ticks().checked_sub(dur_ticks) {
+				Some(ticks) => Some(Self::from_ticks(ticks)),
+				None => None,
+			},
+			None => None,
+		}
+	}
+
+	/// Returns the amount of time elapsed from an earlier time, or `None` if
+	/// `earlier` is actually later than `self`.
+	#[must_use]
+	pub const fn duration_since(&self, earlier: &FILETIME) -> Option<std::time::Duration> {
+		match self.ticks().checked_sub(earlier.ticks()) {
+			Some(ticks) => Some(ticks_to_duration(ticks)),
+			None => None,
+		}
+	}
+}
+
 /// [`GUID`](https://learn.microsoft.com/en-us/windows/win32/api/guiddef/ns-guiddef-guid)
 /// struct.
 ///
 /// The [`Default`](std::default::Default) implementation returns `GUID::NULL`
 /// (all zeros).
+///
+/// Can be parsed from a string with [`FromStr`](std::str::FromStr), accepting
+/// both braced and bare forms, in any case. When formatting, the alternate
+/// flag adds braces, and [`UpperHex`](std::fmt::UpperHex) gives uppercase
+/// digits:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let g: w::GUID = "{43826D1E-E718-42EE-BC55-A1E261C37BFE}".parse()?;
+///
+/// println!("{}", g);     // 43826d1e-e718-42ee-bc55-a1e261c37bfe
+/// println!("{:#}", g);   // {43826d1e-e718-42ee-bc55-a1e261c37bfe}
+/// println!("{:X}", g);   // 43826D1E-E718-42EE-BC55-A1E261C37BFE
+/// println!("{:#X}", g);  // {43826D1E-E718-42EE-BC55-A1E261C37BFE}
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct GUID {
@@ -96,11 +288,7 @@ pub struct GUID {
 
 impl std::fmt::Display for GUID {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
-			self.data1, self.data2, self.data3,
-			self.data4.swap_bytes() >> 48,
-			self.data4.swap_bytes() & 0x0000_ffff_ffff_ffff,
-		)
+		self.fmt_hex(f, false)
 	}
 }
 impl std::fmt::Debug for GUID {
@@ -108,11 +296,40 @@ impl std::fmt::Debug for GUID {
 		std::fmt::Display::fmt(self, f)
 	}
 }
+impl std::fmt::LowerHex for GUID {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.fmt_hex(f, false)
+	}
+}
+impl std::fmt::UpperHex for GUID {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.fmt_hex(f, true)
+	}
+}
+
+impl std::str::FromStr for GUID {
+	type Err = co::ERROR;
+
+	/// Parses a `GUID` in the braced `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}`
+	/// or bare `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` forms, with hex digits
+	/// in any case, ignoring surrounding whitespace.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// string has an invalid format.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::parse_str(s.trim().as_bytes())
+			.ok_or(co::ERROR::INVALID_DATA)
+	}
+}
 
 impl GUID {
 	/// Creates a new `GUID` from a representative hex string, which can be
 	/// copied straight from standard `GUID` declarations.
 	///
+	/// To parse strings at runtime, use the [`FromStr`](std::str::FromStr)
+	/// implementation, which doesn't panic.
+	///
 	/// # Panics
 	///
 	/// Panics if the string has an invalid format.
@@ -126,57 +343,70 @@ impl GUID {
 	/// ```
 	#[must_use]
 	pub const fn new(guid_str: &str) -> Self {
-		if guid_str.len() != 36 {
-			panic!("Bad number of GUID chars.");
-		}
-
-		let chs = guid_str.as_bytes();
-		let p1 = Self::parse_block([chs[0], chs[1], chs[2], chs[3], chs[4],
-			chs[5], chs[6], chs[7]]);
-		let p2 = Self::parse_block([chs[9], chs[10], chs[11], chs[12]]);
-		let p3 = Self::parse_block([chs[14], chs[15], chs[16], chs[17]]);
-		let p4 = Self::parse_block([chs[19], chs[20], chs[21], chs[22]]);
-		let p5 = Self::parse_block([chs[24], chs[25], chs[26], chs[27], chs[28],
-			chs[29], chs[30], chs[31], chs[32], chs[33], chs[34], chs[35]]);
-
-		Self {
-			data1: p1 as _,
-			data2: p2 as _,
-			data3: p3 as _,
-			data4: ((p4 << 48) | p5).swap_bytes(),
+		match Self::parse_str(guid_str.as_bytes()) {
+			Some(guid) => guid,
+			None => panic!("Bad GUID format."),
 		}
 	}
 
-	const fn parse_block<const N: usize>(chars: [u8; N]) -> u64 {
-		let mut res: u64 = 0;
+	const fn parse_str(chs: &[u8]) -> Option<Self> {
+		let start = match chs.len() {
+			36 => 0,
+			38 if chs[0] == b'{' && chs[37] == b'}' => 1,
+			_ => return None,
+		};
+
+		let mut val: u128 = 0;
 		let mut idx: usize = 0;
-		while idx < N {
-			let ch = chars[idx];
-			if !Self::valid_char(ch) {
-				panic!("Bad GUID char.");
+		while idx < 36 {
+			let ch = chs[start + idx];
+			if idx == 8 || idx == 13 || idx == 18 || idx == 23 {
+				if ch != b'-' {
+					return None;
+				}
+			} else {
+				let num = match ch {
+					b'0'..=b'9' => ch - b'0',
+					b'A'..=b'F' => ch - b'A' + 10,
+					b'a'..=b'f' => ch - b'a' + 10,
+					_ => return None,
+				};
+				val = (val << 4) | num as u128;
 			}
-			res += Self::char_to_num(ch) * 16_u64.pow((N - idx - 1) as _);
 			idx += 1;
 		}
-		res
-	}
 
-	const fn valid_char(ch: u8) -> bool {
-		(ch >= 48 && ch <= 57) // 0-9
-			|| (ch >= 65 && ch <= 70) // A-F
-			|| (ch >= 97 && ch <= 102) // a-f
+		Some(Self {
+			data1: (val >> 96) as _,
+			data2: (val >> 80) as _,
+			data3: (val >> 64) as _,
+			data4: (val as u64).swap_bytes(),
+		})
 	}
 
-	const fn char_to_num(ch: u8) -> u64 {
-		if ch >= 48 && ch <= 57 {
-			ch as u64 - 48
-		} else if ch >= 65 && ch <= 70 {
-			ch as u64 - 65 + 10
-		} else if ch >= 97 && ch <= 102 {
-			ch as u64 - 97 + 10
+	fn fmt_hex(&self,
+		f: &mut std::fmt::Formatter<'_>,
+		upper: bool,
+	) -> std::fmt::Result
+	{
+		let (p4, p5) = (
+			self.data4.swap_bytes() >> 48,
+			self.data4.swap_bytes() & 0x0000_ffff_ffff_ffff,
+		);
+		if f.alternate() {
+			write!(f, "{{")?;
+		}
+		if upper {
+			write!(f, "{:08X}-{:04X}-{:04X}-{:04X}-{:012X}",
+				self.data1, self.data2, self.data3, p4, p5)?;
 		} else {
-			panic!("Bad GUID char in conversion.");
+			write!(f, "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+				self.data1, self.data2, self.data3, p4, p5)?;
 		}
+		if f.alternate() {
+			write!(f, "}}")?;
+		}
+		Ok(())
 	}
 }
 
@@ -192,6 +422,141 @@ pub struct HEAPLIST32 {
 
 impl_default_with_size!(HEAPLIST32, dwSize);
 
+/// [`IMAGE_DATA_DIRECTORY`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_data_directory)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct IMAGE_DATA_DIRECTORY {
+	pub VirtualAddress: u32,
+	pub Size: u32,
+}
+
+/// [`IMAGE_FILE_HEADER`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_file_header)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct IMAGE_FILE_HEADER {
+	pub Machine: co::IMAGE_FILE_MACHINE,
+	pub NumberOfSections: u16,
+	pub TimeDateStamp: u32,
+	pub PointerToSymbolTable: u32,
+	pub NumberOfSymbols: u32,
+	pub SizeOfOptionalHeader: u16,
+	pub Characteristics: co::IMAGE_FILE,
+}
+
+/// [`IMAGE_OPTIONAL_HEADER32`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_optional_header32)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct IMAGE_OPTIONAL_HEADER32 {
+	pub Magic: u16,
+	pub MajorLinkerVersion: u8,
+	pub MinorLinkerVersion: u8,
+	pub SizeOfCode: u32,
+	pub SizeOfInitializedData: u32,
+	pub SizeOfUninitializedData: u32,
+	pub AddressOfEntryPoint: u32,
+	pub BaseOfCode: u32,
+	pub BaseOfData: u32,
+	pub ImageBase: u32,
+	pub SectionAlignment: u32,
+	pub FileAlignment: u32,
+	pub MajorOperatingSystemVersion: u16,
+	pub MinorOperatingSystemVersion: u16,
+	pub MajorImageVersion: u16,
+	pub MinorImageVersion: u16,
+	pub MajorSubsystemVersion: u16,
+	pub MinorSubsystemVersion: u16,
+	pub Win32VersionValue: u32,
+	pub SizeOfImage: u32,
+	pub SizeOfHeaders: u32,
+	pub CheckSum: u32,
+	pub Subsystem: co::IMAGE_SUBSYSTEM,
+	pub DllCharacteristics: co::IMAGE_DLLCHARACTERISTICS,
+	pub SizeOfStackReserve: u32,
+	pub SizeOfStackCommit: u32,
+	pub SizeOfHeapReserve: u32,
+	pub SizeOfHeapCommit: u32,
+	pub LoaderFlags: u32,
+	pub NumberOfRvaAndSizes: u32,
+	pub DataDirectory: [IMAGE_DATA_DIRECTORY; 16],
+}
+
+/// [`IMAGE_OPTIONAL_HEADER64`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_optional_header64)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct IMAGE_OPTIONAL_HEADER64 {
+	pub Magic: u16,
+	pub MajorLinkerVersion: u8,
+	pub MinorLinkerVersion: u8,
+	pub SizeOfCode: u32,
+	pub SizeOfInitializedData: u32,
+	pub SizeOfUninitializedData: u32,
+	pub AddressOfEntryPoint: u32,
+	pub BaseOfCode: u32,
+	pub ImageBase: u64,
+	pub SectionAlignment: u32,
+	pub FileAlignment: u32,
+	pub MajorOperatingSystemVersion: u16,
+	pub MinorOperatingSystemVersion: u16,
+	pub MajorImageVersion: u16,
+	pub MinorImageVersion: u16,
+	pub MajorSubsystemVersion: u16,
+	pub MinorSubsystemVersion: u16,
+	pub Win32VersionValue: u32,
+	pub SizeOfImage: u32,
+	pub SizeOfHeaders: u32,
+	pub CheckSum: u32,
+	pub Subsystem: co::IMAGE_SUBSYSTEM,
+	pub DllCharacteristics: co::IMAGE_DLLCHARACTERISTICS,
+	pub SizeOfStackReserve: u64,
+	pub SizeOfStackCommit: u64,
+	pub SizeOfHeapReserve: u64,
+	pub SizeOfHeapCommit: u64,
+	pub LoaderFlags: u32,
+	pub NumberOfRvaAndSizes: u32,
+	pub DataDirectory: [IMAGE_DATA_DIRECTORY; 16],
+}
+
+/// [`IMAGE_SECTION_HEADER`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_section_header)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct IMAGE_SECTION_HEADER {
+	Name: [u8; 8],
+	pub VirtualSize: u32,
+	pub VirtualAddress: u32,
+	pub SizeOfRawData: u32,
+	pub PointerToRawData: u32,
+	pub PointerToRelocations: u32,
+	pub PointerToLinenumbers: u32,
+	pub NumberOfRelocations: u16,
+	pub NumberOfLinenumbers: u16,
+	pub Characteristics: co::IMAGE_SCN,
+}
+
+impl IMAGE_SECTION_HEADER {
+	/// Returns the `Name` field, which is an UTF-8 string with up to 8 bytes.
+	#[must_use]
+	pub fn Name(&self) -> String {
+		let len = self.Name.iter()
+			.position(|ch| *ch == 0x00)
+			.unwrap_or(self.Name.len());
+		String::from_utf8_lossy(&self.Name[..len]).into_owned()
+	}
+
+	/// Sets the `Name` field, which will be truncated to 8 bytes.
+	pub fn set_Name(&mut self, val: &str) {
+		self.Name = [0; 8];
+		val.bytes()
+			.take(self.Name.len())
+			.zip(self.Name.iter_mut())
+			.for_each(|(src, dest)| *dest = src);
+	}
+}
+
 /// [`LANGID`](https://learn.microsoft.com/en-us/windows/win32/intl/language-identifiers)
 /// language identifier.
 #[repr(transparent)]
@@ -724,7 +1089,25 @@ impl_default!(SYSTEM_INFO);
 /// struct.
 ///
 /// Can be converted to [`FILETIME`](crate::FILETIME) with
-/// [`SystemTimeToFileTime`](crate::SystemTimeToFileTime) function.
+/// [`to_filetime`](crate::SYSTEMTIME::to_filetime), or with
+/// [`SystemTimeToFileTime`](crate::SystemTimeToFileTime) function. Can also be
+/// converted from and to [`SystemTime`](std::time::SystemTime) and OLE
+/// automation dates, the ones stored in a `VARIANT`.
+///
+/// The [`Display`](std::fmt::Display) implementation and the
+/// [`FromStr`](std::str::FromStr) parsing use the
+/// [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) format:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+/// use std::time::Duration;
+///
+/// let st: w::SYSTEMTIME = "2024-02-28T23:30:00Z".parse()?;
+/// let next = st.checked_add(Duration::from_secs(3600)).unwrap();
+///
+/// println!("{}", next); // 2024-02-29T00:30:00.000
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
 #[repr(C)]
 #[derive(Default, Clone, PartialEq, Eq)]
 pub struct SYSTEMTIME {
@@ -738,6 +1121,404 @@ pub struct SYSTEMTIME {
 	pub wMilliseconds: u16,
 }
 
+impl std::fmt::Display for SYSTEMTIME {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}",
+			self.wYear, self.wMonth, self.wDay,
+			self.wHour, self.wMinute, self.wSecond, self.wMilliseconds)
+	}
+}
+
+impl std::str::FromStr for SYSTEMTIME {
+	type Err = co::ERROR;
+
+	/// Parses an [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) date, in
+	/// the `YYYY-MM-DD[THH:MM[:SS[.fff]]][Z|±HH[:MM]]` format. A space is also
+	/// accepted instead of `T`, and a comma instead of the dot. Fractions of a
+	/// second beyond milliseconds are truncated. Years beyond 9999 are written
+	/// with 5 digits, as the [`Display`](std::fmt::Display) implementation does.
+	///
+	/// If a UTC offset is present, the time is converted to UTC; otherwise it's
+	/// returned as it is. The day of the week is always computed.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if the
+	/// string is malformed or the date is not valid.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		fn digits(b: &[u8], idx: &mut usize, n: usize) -> Option<u16> {
+			let chunk = b.get(*idx..*idx + n)?;
+			let mut val = 0;
+			for ch in chunk.iter() {
+				if !ch.is_ascii_digit() {
+					return None;
+				}
+				val = val * 10 + (ch - b'0') as u16;
+			}
+			*idx += n;
+			Some(val)
+		}
+		fn eat(b: &[u8], idx: &mut usize, ch: u8) -> Option<()> {
+			if b.get(*idx) == Some(&ch) {
+				*idx += 1;
+				Some(())
+			} else {
+				None
+			}
+		}
+
+		fn parse(b: &[u8]) -> Option<SYSTEMTIME> {
+			let mut idx = b.iter().take_while(|ch| ch.is_ascii_digit()).count();
+			if idx != 4 && idx != 5 {
+				return None; // years beyond 9999 are written with 5 digits
+			}
+			let year = b[..idx].iter()
+				.fold(0u32, |acc, ch| acc * 10 + (ch - b'0') as u32);
+			eat(b, &mut idx, b'-')?;
+			let month = digits(b, &mut idx, 2)?;
+			eat(b, &mut idx, b'-')?;
+			let day = digits(b, &mut idx, 2)?;
+			let mut st = SYSTEMTIME {
+				wYear: u16::try_from(year).ok()?,
+				wMonth: month,
+				wDay: day,
+				..Default::default()
+			};
+
+			if eat(b, &mut idx, b'T').or_else(|| eat(b, &mut idx, b' ')).is_some() {
+				st.wHour = digits(b, &mut idx, 2)?;
+				eat(b, &mut idx, b':')?;
+				st.wMinute = digits(b, &mut idx, 2)?;
+				if eat(b, &mut idx, b':').is_some() {
+					st.wSecond = digits(b, &mut idx, 2)?;
+					if eat(b, &mut idx, b'.').or_else(|| eat(b, &mut idx, b',')).is_some() {
+						let start = idx;
+						while b.get(idx).is_some_and(|ch| ch.is_ascii_digit()) {
+							if idx - start < 3 {
+								st.wMilliseconds = st.wMilliseconds * 10 + (b[idx] - b'0') as u16;
+							}
+							idx += 1;
+						}
+						match idx - start {
+							0 => return None,
+							1 => st.wMilliseconds *= 100,
+							2 => st.wMilliseconds *= 10,
+							_ => {},
+						}
+					}
+				}
+			}
+
+			let offset_mins = match b.get(idx) {
+				None => 0,
+				Some(b'Z' | b'z') => {
+					idx += 1;
+					0
+				},
+				Some(sign @ (b'+' | b'-')) => {
+					let sign = if *sign == b'-' { -1 } else { 1 };
+					idx += 1;
+					let hours = digits(b, &mut idx, 2)? as i64;
+					let mins = if idx == b.len() {
+						0
+					} else {
+						let _ = eat(b, &mut idx, b':');
+						digits(b, &mut idx, 2)? as i64
+					};
+					if hours > 23 || mins > 59 {
+						return None;
+					}
+					sign * (hours * 60 + mins)
+				},
+				Some(_) => return None,
+			};
+			if idx != b.len() || !st.is_valid() {
+				return None;
+			}
+
+			let ticks = st.ticks() as i64 - offset_mins * 60 * TICKS_PER_SEC as i64;
+			SYSTEMTIME::from_ticks(u64::try_from(ticks).ok()?)
+		}
+
+		parse(s.trim().as_bytes()).ok_or(co::ERROR::INVALID_DATA)
+	}
+}
+
+impl std::cmp::PartialOrd for SYSTEMTIME {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl std::cmp::Ord for SYSTEMTIME {
+	/// Compares the date and time fields, from the year down to the
+	/// milliseconds. The day of the week is compared last, so that the
+	/// ordering is consistent with equality.
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		let fields = |st: &Self| (st.wYear, st.wMonth, st.wDay, st.wHour,
+			st.wMinute, st.wSecond, st.wMilliseconds, st.wDayOfWeek);
+		fields(self).cmp(&fields(other))
+	}
+}
+
+impl std::ops::Add<std::time::Duration> for SYSTEMTIME {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics on overflow, or if the date is not valid. See
+	/// [`checked_add`](crate::SYSTEMTIME::checked_add) for a version without
+	/// panic.
+	fn add(self, rhs: std::time::Duration) -> Self::Output {
+		self.checked_add(rhs)
+			.expect("Overflow when adding duration to SYSTEMTIME.")
+	}
+}
+impl std::ops::Sub<std::time::Duration> for SYSTEMTIME {
+	type Output = Self;
+
+	/// # Panics
+	///
+	/// Panics on overflow, or if the date is not valid. See
+	/// [`checked_sub`](crate::SYSTEMTIME::checked_sub) for a version without
+	/// panic.
+	fn sub(self, rhs: std::time::Duration) -> Self::Output {
+		self.checked_sub(rhs)
+			.expect("Overflow when subtracting duration from SYSTEMTIME.")
+	}
+}
+
+impl TryFrom<&SYSTEMTIME> for std::time::SystemTime {
+	type Error = co::ERROR;
+
+	/// Assumes the `SYSTEMTIME` is in UTC. Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+	/// the date is not valid.
+	fn try_from(st: &SYSTEMTIME) -> Result<Self, Self::Error> {
+		Self::try_from(st.to_filetime()?)
+	}
+}
+
+impl TryFrom<std::time::SystemTime> for SYSTEMTIME {
+	type Error = co::ERROR;
+
+	/// The result is in UTC. Fails with
+	/// [`co::ERROR::ARITHMETIC_OVERFLOW`](crate::co::ERROR::ARITHMETIC_OVERFLOW)
+	/// if the time is out of the `SYSTEMTIME` range.
+	fn try_from(time: std::time::SystemTime) -> Result<Self, Self::Error> {
+		Self::from_filetime(&FILETIME::try_from(time)?)
+	}
+}
+
+impl SYSTEMTIME {
+	/// Greatest number of 100-nanosecond intervals which can be represented,
+	/// which is the end of year 30827.
+	const MAX_TICKS: u64 = days_from_civil(30_828, 1, 1) as u64 * TICKS_PER_DAY - 1;
+
+	/// OLE automation dates count the days since December 30, 1899.
+	const OLE_EPOCH_DAYS: i64 = days_from_civil(1899, 12, 30);
+
+	/// Returns `true` if all the date and time fields are within their valid
+	/// ranges, including the number of days in the month. The year must be
+	/// between 1601 and 30827. The day of the week is not checked.
+	#[must_use]
+	pub const fn is_valid(&self) -> bool {
+		self.wYear >= 1601 && self.wYear <= 30827 && self.are_fields_valid()
+	}
+
+	/// Checks all the date and time fields, except the year range.
+	const fn are_fields_valid(&self) -> bool {
+		self.wMonth >= 1 && self.wMonth <= 12
+			&& self.wDay >= 1
+			&& self.wDay as u32 <= days_in_month(self.wYear as _, self.wMonth as _)
+			&& self.wHour < 24
+			&& self.wMinute < 60
+			&& self.wSecond < 60
+			&& self.wMilliseconds < 1000
+	}
+
+	/// Converts a [`FILETIME`](crate::FILETIME) to a `SYSTEMTIME`, computing
+	/// the day of the week. Unlike
+	/// [`FileTimeToSystemTime`](crate::FileTimeToSystemTime), this conversion
+	/// is made in pure Rust.
+	///
+	/// Fails with
+	/// [`co::ERROR::ARITHMETIC_OVERFLOW`](crate::co::ERROR::ARITHMETIC_OVERFLOW)
+	/// if the `FILETIME` is beyond year 30827.
+	#[must_use]
+	pub const fn from_filetime(ft: &FILETIME) -> SysResult<Self> {
+		match Self::from_ticks(ft.ticks()) {
+			Some(st) => Ok(st),
+			None => Err(co::ERROR::ARITHMETIC_OVERFLOW),
+		}
+	}
+
+	/// Converts the `SYSTEMTIME` to a [`FILETIME`](crate::FILETIME). Unlike
+	/// [`SystemTimeToFileTime`](crate::SystemTimeToFileTime), this conversion
+	/// is made in pure Rust.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+	/// the date is not [valid](crate::SYSTEMTIME::is_valid).
+	#[must_use]
+	pub const fn to_filetime(&self) -> SysResult<FILETIME> {
+		if self.is_valid() {
+			Ok(FILETIME::from_ticks(self.ticks()))
+		} else {
+			Err(co::ERROR::INVALID_PARAMETER)
+		}
+	}
+
+	/// Converts an
+	/// [OLE automation date](https://learn.microsoft.com/en-us/cpp/atl-mfc-shared/date-type)
+	/// – the number of days since December 30, 1899, with the fraction as the
+	/// time of the day – to a `SYSTEMTIME`, rounded to the nearest millisecond.
+	/// Unlike [`VariantTimeToSystemTime`](crate::VariantTimeToSystemTime), the
+	/// milliseconds are kept.
+	///
+	/// Fails with
+	/// [`co::ERROR::ARITHMETIC_OVERFLOW`](crate::co::ERROR::ARITHMETIC_OVERFLOW)
+	/// if the date is before year 100, after year 9999, or not a number.
+	#[must_use]
+	pub fn from_ole_date(ole_date: f64) -> SysResult<Self> {
+		const MS_PER_DAY: i64 = 86_400_000;
+		if !(-657_434.0..2_958_466.0).contains(&ole_date) { // years 100 to 9999
+			return Err(co::ERROR::ARITHMETIC_OVERFLOW);
+		}
+
+		// For negative dates, the fraction still moves forward in the day.
+		let ole_days = ole_date.trunc();
+		let mut ms = ((ole_date - ole_days).abs() * MS_PER_DAY as f64).round() as i64;
+		let mut days = ole_days as i64 + Self::OLE_EPOCH_DAYS; // since January 1, 1601
+		if ms == MS_PER_DAY {
+			days += 1;
+			ms = 0;
+		}
+
+		let (year, month, day) = civil_from_days(days);
+		if year > 9999 {
+			return Err(co::ERROR::ARITHMETIC_OVERFLOW);
+		}
+		Ok(Self {
+			wYear: year as _,
+			wMonth: month as _,
+			wDayOfWeek: (days + 1).rem_euclid(7) as _, // January 1, 1601 was a Monday
+			wDay: day as _,
+			wHour: (ms / 3_600_000) as _,
+			wMinute: (ms / 60_000 % 60) as _,
+			wSecond: (ms / 1_000 % 60) as _,
+			wMilliseconds: (ms % 1_000) as _,
+		})
+	}
+
+	/// Converts the `SYSTEMTIME` to an
+	/// [OLE automation date](https://learn.microsoft.com/en-us/cpp/atl-mfc-shared/date-type),
+	/// the inverse of [`from_ole_date`](crate::SYSTEMTIME::from_ole_date).
+	/// Unlike [`SystemTimeToVariantTime`](crate::SystemTimeToVariantTime), the
+	/// milliseconds are kept.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+	/// any of the date and time fields is out of range, or with
+	/// [`co::ERROR::ARITHMETIC_OVERFLOW`](crate::co::ERROR::ARITHMETIC_OVERFLOW)
+	/// if the year is before 100 or after 9999.
+	#[must_use]
+	pub fn to_ole_date(&self) -> SysResult<f64> {
+		const MS_PER_DAY: i64 = 86_400_000;
+		if !self.are_fields_valid() {
+			return Err(co::ERROR::INVALID_PARAMETER);
+		} else if self.wYear < 100 || self.wYear > 9999 {
+			return Err(co::ERROR::ARITHMETIC_OVERFLOW);
+		}
+
+		let days = (days_from_civil(self.wYear as _, self.wMonth as _, self.wDay as _)
+			- Self::OLE_EPOCH_DAYS) as f64;
+		let ms = (((self.wHour as i64 * 60 + self.wMinute as i64) * 60
+			+ self.wSecond as i64) * 1_000) + self.wMilliseconds as i64;
+		let frac = ms as f64 / MS_PER_DAY as f64;
+		Ok(if days < 0.0 { days - frac } else { days + frac }) // for negative dates, the fraction still moves forward
+	}
+
+	/// Adds a [`Duration`](std::time::Duration), truncating the remaining
+	/// microseconds, and computing the new day of the week. Returns `None` on
+	/// overflow, or if the date is not [valid](crate::SYSTEMTIME::is_valid).
+	#[must_use]
+	pub const fn checked_add(&self, dur: std::time::Duration) -> Option<Self> {
+		if !self.is_valid() {
+			return None;
+		}
+		match duration_to_ticks(&dur) {
+			Some(dur_ticks) => match self.ticks().checked_add(dur_ticks) {
+				Some(ticks) => Self::from_ticks(ticks),
+				None => None,
+			},
+			None => None,
+		}
+	}
+
+	/// Subtracts a [`Duration`](std::time::Duration), truncating the remaining
+	/// microseconds, and computing the new day of the week. Returns `None` on
+	/// overflow, or if the date is not [valid](crate::SYSTEMTIME::is_valid).
+	#[must_use]
+	pub const fn checked_sub(&self, dur: std::time::Duration) -> Option<Self> {
+		if !self.is_valid() {
+			return None;
+		}
+		match duration_to_ticks(&dur) {
+			Some(dur_ticks) => match self.ticks().checked_sub(dur_ticks) {
+				Some(ticks) => Self::from_ticks(ticks),
+				None => None,
+			},
+			None => None,
+		}
+	}
+
+	/// Returns the amount of time elapsed from an earlier time, or `None` if
+	/// `earlier` is actually later than `self`, or if any of the dates is not
+	/// [valid](crate::SYSTEMTIME::is_valid).
+	#[must_use]
+	pub const fn duration_since(&self,
+		earlier: &SYSTEMTIME,
+	) -> Option<std::time::Duration>
+	{
+		if !self.is_valid() || !earlier.is_valid() {
+			return None;
+		}
+		match self.ticks().checked_sub(earlier.ticks()) {
+			Some(ticks) => Some(ticks_to_duration(ticks)),
+			None => None,
+		}
+	}
+
+	/// Number of 100-nanosecond intervals since January 1, 1601, assuming the
+	/// date is valid.
+	const fn ticks(&self) -> u64 {
+		let days = days_from_civil(self.wYear as _, self.wMonth as _, self.wDay as _) as u64;
+		let secs = ((days * 24 + self.wHour as u64) * 60 + self.wMinute as u64) * 60
+			+ self.wSecond as u64;
+		secs * TICKS_PER_SEC + self.wMilliseconds as u64 * TICKS_PER_MS
+	}
+
+	/// Builds the date from 100-nanosecond intervals since January 1, 1601,
+	/// truncating the sub-millisecond part. Returns `None` beyond year 30827.
+	const fn from_ticks(ticks: u64) -> Option<Self> {
+		if ticks > Self::MAX_TICKS {
+			return None;
+		}
+		let days = ticks / TICKS_PER_DAY;
+		let ms = (ticks % TICKS_PER_DAY) / TICKS_PER_MS;
+		let (year, month, day) = civil_from_days(days as _);
+		Some(Self {
+			wYear: year as _,
+			wMonth: month as _,
+			wDayOfWeek: ((days + 1) % 7) as _, // January 1, 1601 was a Monday
+			wDay: day as _,
+			wHour: (ms / 3_600_000) as _,
+			wMinute: (ms / 60_000 % 60) as _,
+			wSecond: (ms / 1_000 % 60) as _,
+			wMilliseconds: (ms % 1_000) as _,
+		})
+	}
+}
+
 /// [`THREADENTRY32`](https://learn.microsoft.com/en-us/windows/win32/api/tlhelp32/ns-tlhelp32-threadentry32)
 /// struct.
 #[repr(C)]
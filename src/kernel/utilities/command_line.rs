@@ -0,0 +1,264 @@
+//! Command line splitting and quoting.
+//!
+//! These functions are implemented in pure Rust, following the rules of the
+//! [MSVC C runtime](https://learn.microsoft.com/en-us/cpp/c-language/parsing-c-command-line-arguments),
+//! which are the same rules of
+//! [`CommandLineToArgv`](crate::CommandLineToArgv), but without requiring the
+//! shell.
+
+use std::borrow::Cow;
+
+use crate::co;
+use crate::decl::*;
+
+/// Splits a command line into its arguments, the first one being the program
+/// name.
+///
+/// The program name is special: quotes only toggle whether whitespace ends it,
+/// and backslashes are always taken literally. The other arguments follow
+/// these rules:
+///
+/// * arguments are separated by spaces or tabs, unless within quotes;
+/// * a quote toggles the quoted mode, unless escaped;
+/// * backslashes immediately followed by a quote are halved, and if they were
+///   an odd number, the quote is escaped and taken literally;
+/// * backslashes not followed by a quote are taken literally;
+/// * within quotes, two consecutive quotes produce a literal quote.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let args = w::command_line::split(
+///     "\"C:\\Program Files\\foo.exe\" a\\\\\"b c\" \"d\\\"e\"",
+/// ); // ["C:\Program Files\foo.exe", "a\b c", "d\"e"]
+/// ```
+#[must_use]
+pub fn split(cmd_line: &str) -> Vec<String> {
+	let mut args = Vec::<String>::new();
+	if cmd_line.is_empty() {
+		return args;
+	}
+	let mut chars = cmd_line.chars().peekable();
+
+	// The program name at the beginning is special.
+	let mut cur = String::new();
+	let mut in_quotes = false;
+	for ch in chars.by_ref() {
+		match ch {
+			'"' => in_quotes = !in_quotes, // no escape characters in program name
+			' ' | '\t' if !in_quotes => break,
+			ch => cur.push(ch),
+		}
+	}
+	args.push(cur);
+	while chars.next_if(|ch| *ch == ' ' || *ch == '\t').is_some() {}
+
+	let mut cur = String::new();
+	let mut in_quotes = false;
+	while let Some(ch) = chars.next() {
+		match ch {
+			' ' | '\t' if !in_quotes => {
+				args.push(std::mem::take(&mut cur));
+				while chars.next_if(|ch| *ch == ' ' || *ch == '\t').is_some() {}
+			},
+			'\\' => {
+				let mut num_backslashes = 1;
+				while chars.next_if_eq(&'\\').is_some() {
+					num_backslashes += 1;
+				}
+				if chars.peek() == Some(&'"') {
+					cur.extend(std::iter::repeat_n('\\', num_backslashes / 2));
+					if num_backslashes % 2 == 1 { // quote is escaped
+						chars.next();
+						cur.push('"');
+					}
+				} else {
+					cur.extend(std::iter::repeat_n('\\', num_backslashes));
+				}
+			},
+			'"' if in_quotes => match chars.peek() {
+				Some('"') => { // two consecutive quotes produce a literal quote
+					cur.push('"');
+					chars.next();
+				},
+				Some(_) => in_quotes = false,
+				None => break, // end of the command line, push even if empty
+			},
+			'"' => in_quotes = true,
+			ch => cur.push(ch),
+		}
+	}
+	if !cur.is_empty() || in_quotes {
+		args.push(cur);
+	}
+	args
+}
+
+/// Quotes a single argument, so that it's parsed back unchanged by
+/// [`split`](crate::command_line::split). Not suitable for the program name,
+/// which has different rules.
+///
+/// The argument is quoted only if empty or containing spaces or tabs;
+/// otherwise, only the quotes within it are escaped. If nothing needs to be
+/// changed, no allocation is made.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let a = w::command_line::quote_arg("C:\\some dir\\"); // "C:\some dir\\"
+/// let b = w::command_line::quote_arg("say \"hi\"");     // "say \"hi\""
+/// ```
+#[must_use]
+pub fn quote_arg(arg: &str) -> Cow<'_, str> {
+	let needs_quotes = arg.is_empty() || arg.contains([' ', '\t']);
+	if !needs_quotes && !arg.contains('"') {
+		return Cow::Borrowed(arg);
+	}
+
+	let mut quoted = String::with_capacity(arg.len() + 2);
+	if needs_quotes {
+		quoted.push('"');
+	}
+	let mut num_backslashes = 0;
+	for ch in arg.chars() {
+		if ch == '\\' {
+			num_backslashes += 1;
+		} else {
+			if ch == '"' { // double the preceding backslashes, plus one to escape the quote
+				quoted.extend(std::iter::repeat_n('\\', num_backslashes + 1));
+			}
+			num_backslashes = 0;
+		}
+		quoted.push(ch);
+	}
+	if needs_quotes { // trailing backslashes would escape the closing quote
+		quoted.extend(std::iter::repeat_n('\\', num_backslashes));
+		quoted.push('"');
+	}
+	Cow::Owned(quoted)
+}
+
+/// Builds a command line from the program name followed by its arguments,
+/// which is the inverse of [`split`](crate::command_line::split). The result
+/// can be passed to [`HPROCESS::CreateProcess`](crate::prelude::kernel_Hprocess::CreateProcess).
+///
+/// The program name is quoted if it contains spaces or tabs, and the
+/// arguments are quoted with [`quote_arg`](crate::command_line::quote_arg).
+///
+/// Fails with
+/// [`co::ERROR::INVALID_PARAMETER`](crate::co::ERROR::INVALID_PARAMETER) if
+/// `args` is empty, if the program name is empty or contains quotes, or if
+/// any string contains a null character, since these can't be represented.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let cmd_line = w::command_line::join(
+///     &["C:\\Program Files\\foo.exe", "a b", "c\\"],
+/// )?; // "C:\Program Files\foo.exe" "a b" c\
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub fn join(args: &[impl AsRef<str>]) -> SysResult<String> {
+	let (program, args) = args.split_first()
+		.ok_or(co::ERROR::INVALID_PARAMETER)?;
+	let program = program.as_ref();
+	if program.is_empty() || program.contains(['"', '\0'])
+		|| args.iter().any(|arg| arg.as_ref().contains('\0'))
+	{
+		return Err(co::ERROR::INVALID_PARAMETER);
+	}
+
+	let mut cmd_line = if program.contains([' ', '\t']) {
+		format!("\"{}\"", program)
+	} else {
+		program.to_owned()
+	};
+	for arg in args.iter() {
+		cmd_line.push(' ');
+		cmd_line.push_str(&quote_arg(arg.as_ref()));
+	}
+	Ok(cmd_line)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Command lines and the arguments they're split into, the first one being
+	/// the program name.
+	const CASES: &[(&str, &[&str])] = &[
+		// Empty and whitespace.
+		("", &[]),
+		("prog", &["prog"]),
+		("prog a b", &["prog", "a", "b"]),
+		("prog\ta\t\tb", &["prog", "a", "b"]),
+		("prog a b  \t", &["prog", "a", "b"]),
+		("  prog a", &["", "prog", "a"]),
+		("prog   a", &["prog", "a"]),
+		// Empty arguments.
+		("prog \"\"", &["prog", ""]),
+		("prog \"\" \"\"", &["prog", "", ""]),
+		("prog a \"\" b", &["prog", "a", "", "b"]),
+		("prog \"", &["prog", ""]),
+		// Quotes.
+		("prog \"a b c\" d e", &["prog", "a b c", "d", "e"]),
+		("prog a\"b c\"d", &["prog", "ab cd"]),
+		("prog d\"e f\"g h", &["prog", "de fg", "h"]),
+		("prog \"unterminated arg", &["prog", "unterminated arg"]),
+		// Two quotes inside a quoted argument.
+		("prog \"a\"\"b\"", &["prog", "a\"b"]),
+		("prog a\"b\"\" c d", &["prog", "ab\" c d"]),
+		("prog \"\"\"\"", &["prog", "\""]),
+		// Backslashes.
+		("prog a\\\\\\b", &["prog", "a\\\\\\b"]),
+		("prog \"ab\\\"c\" \"\\\\\" d", &["prog", "ab\"c", "\\", "d"]),
+		("prog a\\\\\\\"b c d", &["prog", "a\\\"b", "c", "d"]),
+		("prog a\\\\\\\\\"b c\" d e", &["prog", "a\\\\b c", "d", "e"]),
+		("prog \\\\\\\"", &["prog", "\\\""]),
+		("prog \\\"", &["prog", "\""]),
+		// Trailing backslashes.
+		("prog C:\\dir\\ x", &["prog", "C:\\dir\\", "x"]),
+		("prog C:\\dir\\\\ x", &["prog", "C:\\dir\\\\", "x"]),
+		("prog \"C:\\some dir\\\\\" x", &["prog", "C:\\some dir\\", "x"]),
+		("prog \"C:\\some dir\\\" x", &["prog", "C:\\some dir\" x"]),
+		("prog \"a\\\\ b\\\\\\\\\"", &["prog", "a\\\\ b\\\\"]),
+		// Program name: quotes only toggle, backslashes are literal.
+		("\"C:\\Program Files\\foo.exe\" a", &["C:\\Program Files\\foo.exe", "a"]),
+		("C:\\Program\" \"Files\\foo.exe a", &["C:\\Program Files\\foo.exe", "a"]),
+		("\"C:\\dir\\\\\"b c", &["C:\\dir\\\\b", "c"]),
+		("\"C:\\dir\\\" a", &["C:\\dir\\", "a"]),
+		("\"C:\\a\"\"b\" c", &["C:\\ab", "c"]),
+		("prog\\\"x y", &["prog\\x y"]),
+	];
+
+	#[test]
+	fn split_cases() {
+		for (cmd_line, expected) in CASES.iter() {
+			assert_eq!(split(cmd_line), *expected, "split({:?})", cmd_line);
+		}
+	}
+
+	#[test]
+	fn join_round_trips() {
+		for (_, args) in CASES.iter()
+			.filter(|(_, args)| args.first().is_some_and(|p| !p.is_empty()))
+		{
+			let cmd_line = join(args).unwrap();
+			assert_eq!(split(&cmd_line), *args, "join({:?}) = {:?}", args, cmd_line);
+		}
+	}
+
+	#[test]
+	fn join_rejects_invalid() {
+		assert_eq!(join(&[] as &[&str]), Err(co::ERROR::INVALID_PARAMETER));
+		assert_eq!(join(&["", "a"]), Err(co::ERROR::INVALID_PARAMETER));
+		assert_eq!(join(&["a\"b", "c"]), Err(co::ERROR::INVALID_PARAMETER));
+		assert_eq!(join(&["prog", "a\0b"]), Err(co::ERROR::INVALID_PARAMETER));
+	}
+}
@@ -0,0 +1,83 @@
+use crate::co;
+
+/// An error with an attached context string, describing what was being done
+/// when the error happened. Usually created with
+/// [`ResultContext`](crate::prelude::ResultContext).
+///
+/// The [`Display`](std::fmt::Display) implementation shows the context
+/// followed by the original error, while the original error itself is
+/// returned by [`source`](std::error::Error::source), so that error chains
+/// can be walked by logging libraries.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hkey = w::HKEY::LOCAL_MACHINE.RegOpenKeyEx(
+///     Some("Software\\X"),
+///     co::REG_OPTION::default(),
+///     co::KEY::READ,
+/// ).err_context("while opening HKEY_LOCAL_MACHINE\\Software\\X");
+///
+/// if let Err(e) = hkey {
+///     println!("{}", e); // while opening HKEY_LOCAL_MACHINE\Software\X: [0x0002 2] The system cannot...
+///     assert_eq!(*e.error(), co::ERROR::FILE_NOT_FOUND);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ContextError<E> {
+	context: String,
+	error: E,
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ContextError<E> {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.error)
+	}
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ContextError<E> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}: {}", self.context, self.error)
+	}
+}
+impl<E: std::fmt::Debug> std::fmt::Debug for ContextError<E> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}: {:?}", self.context, self.error)
+	}
+}
+
+impl From<ContextError<co::ERROR>> for std::io::Error {
+	/// Wraps the error into an [`std::io::Error`](std::io::Error), whose
+	/// [`kind`](std::io::Error::kind) is the one of the original error.
+	fn from(err: ContextError<co::ERROR>) -> Self {
+		Self::new(err.error.kind(), err)
+	}
+}
+
+impl<E> ContextError<E> {
+	/// Creates a new `ContextError` by attaching a context string to an error.
+	#[must_use]
+	pub fn new(context: impl Into<String>, error: E) -> Self {
+		Self { context: context.into(), error }
+	}
+
+	/// Returns the context string.
+	#[must_use]
+	pub fn context(&self) -> &str {
+		&self.context
+	}
+
+	/// Returns the original error.
+	#[must_use]
+	pub const fn error(&self) -> &E {
+		&self.error
+	}
+
+	/// Discards the context, returning the original error.
+	#[must_use]
+	pub fn into_error(self) -> E {
+		self.error
+	}
+}
@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
 
+use crate::decl::*;
+
 /// String encodings.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Encoding {
@@ -66,6 +68,114 @@ impl Encoding {
 		}
 	}
 
+	/// Returns the [BOM](https://en.wikipedia.org/wiki/Byte_order_mark) bytes
+	/// of the encoding, or an empty slice if it has none.
+	#[must_use]
+	pub const fn bom(&self) -> &'static [u8] {
+		match self {
+			Self::Unknown | Self::Ansi | Self::Win1252 => &[],
+			Self::Utf8 => &[0xef, 0xbb, 0xbf],
+			Self::Utf16be => &[0xfe, 0xff],
+			Self::Utf16le => &[0xff, 0xfe],
+			Self::Utf32be => &[0x00, 0x00, 0xfe, 0xff],
+			Self::Utf32le => &[0xff, 0xfe, 0x00, 0x00],
+			Self::Scsu => &[0x0e, 0xfe, 0xff],
+			Self::Bocu1 => &[0xfb, 0xee, 0x28, 0xff], // reset byte restores the initial state
+		}
+	}
+
+	/// Decodes the raw data into a string.
+	///
+	/// A leading BOM is skipped, and decoding stops at the first null
+	/// character, if any. Invalid sequences are replaced by U+FFFD. If the
+	/// encoding is [`Unknown`](crate::Encoding::Unknown), it will be guessed
+	/// with [`Encoding::guess`](crate::Encoding::guess).
+	///
+	/// This is a pure Rust implementation, no system calls are made.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let raw = [0x80, 0x20, 0x31, 0x30];
+	/// let text = w::Encoding::Win1252.decode(&raw).to_string();
+	/// assert_eq!(text, "€ 10");
+	/// ```
+	#[must_use]
+	pub fn decode(&self, data: &[u8]) -> WString {
+		WString::from_wchars_slice(&self.decode_to_wchars(data))
+	}
+
+	/// Encodes the string into raw data, without a BOM. Characters which can't
+	/// be represented in the encoding are replaced by `?`. If the encoding is
+	/// [`Unknown`](crate::Encoding::Unknown), UTF-8 is used.
+	///
+	/// This is a pure Rust implementation, no system calls are made.
+	///
+	/// # Examples
+	///
+	/// Writing a UTF-16 file with a BOM:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let enc = w::Encoding::Utf16le;
+	/// let mut data = enc.bom().to_vec();
+	/// data.extend(enc.encode("Hello"));
+	///
+	/// let f = w::File::open("C:\\Temp\\foo.txt", w::FileAccess::OpenOrCreateRW)?;
+	/// f.erase_and_write(&data)?;
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	#[must_use]
+	pub fn encode(&self, text: &str) -> Vec<u8> {
+		match self {
+			Self::Ansi => text.chars()
+				.map(|ch| if (ch as u32) < 0x80 { ch as u8 } else { b'?' })
+				.collect(),
+			Self::Win1252 => text.chars()
+				.map(|ch| win1252_from_char(ch).unwrap_or(b'?'))
+				.collect(),
+			Self::Unknown | Self::Utf8 => text.as_bytes().to_vec(),
+			Self::Utf16be => text.encode_utf16().flat_map(|ch| ch.to_be_bytes()).collect(),
+			Self::Utf16le => text.encode_utf16().flat_map(|ch| ch.to_le_bytes()).collect(),
+			Self::Utf32be => text.chars().flat_map(|ch| (ch as u32).to_be_bytes()).collect(),
+			Self::Utf32le => text.chars().flat_map(|ch| (ch as u32).to_le_bytes()).collect(),
+			Self::Scsu => scsu::encode(text),
+			Self::Bocu1 => bocu1::encode(text),
+		}
+	}
+
+	fn decode_to_wchars(&self, data: &[u8]) -> Vec<u16> {
+		let mut wchars = match self {
+			Self::Unknown => return Self::guess(data).0.decode_to_wchars(data),
+			Self::Ansi => data.iter().map(|ch| *ch as u16).collect(), // raw u8 to u16 conversion
+			Self::Win1252 => data.iter().map(|ch| win1252_to_wchar(*ch)).collect(),
+			Self::Utf8 => String::from_utf8_lossy(data).encode_utf16().collect(),
+			Self::Utf16be => data.chunks_exact(2)
+				.map(|ch2| u16::from_be_bytes([ch2[0], ch2[1]]))
+				.collect(),
+			Self::Utf16le => data.chunks_exact(2)
+				.map(|ch2| u16::from_le_bytes([ch2[0], ch2[1]]))
+				.collect(),
+			Self::Utf32be => code_points_to_wchars(data.chunks_exact(4)
+				.map(|ch4| u32::from_be_bytes([ch4[0], ch4[1], ch4[2], ch4[3]]))),
+			Self::Utf32le => code_points_to_wchars(data.chunks_exact(4)
+				.map(|ch4| u32::from_le_bytes([ch4[0], ch4[1], ch4[2], ch4[3]]))),
+			Self::Scsu => scsu::decode(data),
+			Self::Bocu1 => code_points_to_wchars(bocu1::decode(data).into_iter()),
+		};
+
+		if let Some(null_idx) = wchars.iter().position(|ch| *ch == 0x0000) {
+			wchars.truncate(null_idx); // ignore terminating null, if any
+		}
+		if wchars.first() == Some(&0xfeff) {
+			wchars.remove(0); // BOM
+		}
+		wchars
+	}
+
 	fn guess_bom(data: &[u8]) -> Option<(Self, usize)> {
 		let has_bom = |bom_bytes: &[u8]| -> bool {
 			data.len() >= bom_bytes.len()
@@ -82,21 +192,21 @@ impl Encoding {
 			return Some((Self::Utf16be, UTF16BE.len()));
 		}
 
-		const UTF16LE: [u8; 2] = [0xff, 0xfe];
-		if has_bom(&UTF16LE) {
-			return Some((Self::Utf16le, UTF16LE.len()));
-		}
-
 		const UTF32BE: [u8; 4] = [0x00, 0x00, 0xfe, 0xff];
 		if has_bom(&UTF32BE) {
 			return Some((Self::Utf32be, UTF32BE.len()));
 		}
 
-		const UTF32LE: [u8; 4] = [0xff, 0xfe, 0x00, 0x00];
+		const UTF32LE: [u8; 4] = [0xff, 0xfe, 0x00, 0x00]; // must come before UTF-16 LE
 		if has_bom(&UTF32LE) {
 			return Some((Self::Utf32le, UTF32LE.len()));
 		}
 
+		const UTF16LE: [u8; 2] = [0xff, 0xfe];
+		if has_bom(&UTF16LE) {
+			return Some((Self::Utf16le, UTF16LE.len()));
+		}
+
 		const SCSU: [u8; 3] = [0x0e, 0xfe, 0xff];
 		if has_bom(&SCSU) {
 			return Some((Self::Scsu, SCSU.len()));
@@ -196,3 +306,454 @@ impl Encoding {
 		true // all the conditions accepted through the whole string
 	}
 }
+
+//------------------------------------------------------------------------------
+
+/// Windows-1252 characters in the 0x80-0x9f block. Undefined positions are
+/// mapped to the C1 control characters, as Windows does.
+const WIN1252_80_9F: [u16; 32] = [
+	0x20ac, 0x0081, 0x201a, 0x0192, 0x201e, 0x2026, 0x2020, 0x2021,
+	0x02c6, 0x2030, 0x0160, 0x2039, 0x0152, 0x008d, 0x017d, 0x008f,
+	0x0090, 0x2018, 0x2019, 0x201c, 0x201d, 0x2022, 0x2013, 0x2014,
+	0x02dc, 0x2122, 0x0161, 0x203a, 0x0153, 0x009d, 0x017e, 0x0178,
+];
+
+fn win1252_to_wchar(ch: u8) -> u16 {
+	match ch {
+		0x80..=0x9f => WIN1252_80_9F[(ch - 0x80) as usize],
+		_ => ch as _,
+	}
+}
+
+fn win1252_from_char(ch: char) -> Option<u8> {
+	let ch = ch as u32;
+	if ch < 0x80 || (0xa0..=0xff).contains(&ch) {
+		Some(ch as _)
+	} else {
+		WIN1252_80_9F.iter()
+			.position(|w| *w as u32 == ch)
+			.map(|idx| 0x80 + idx as u8)
+	}
+}
+
+/// Converts Unicode code points to UTF-16, replacing invalid ones by U+FFFD.
+fn code_points_to_wchars(code_points: impl Iterator<Item = u32>) -> Vec<u16> {
+	let mut wchars = Vec::<u16>::default();
+	for cp in code_points {
+		let ch = char::from_u32(cp).unwrap_or(char::REPLACEMENT_CHARACTER);
+		let mut buf = [0u16; 2];
+		wchars.extend_from_slice(ch.encode_utf16(&mut buf));
+	}
+	wchars
+}
+
+/// [SCSU](https://www.unicode.org/reports/tr6/) encoding and decoding.
+mod scsu {
+	const STATIC_WINDOWS: [u32; 8] = [
+		0x0000, 0x0080, 0x0100, 0x0300, 0x2000, 0x2080, 0x2100, 0x3000,
+	];
+	const INITIAL_DYNAMIC_WINDOWS: [u32; 8] = [
+		0x0080, 0x00c0, 0x0400, 0x0600, 0x0900, 0x3040, 0x30a0, 0xff00,
+	];
+
+	const SQ0: u8 = 0x01;
+	const SDX: u8 = 0x0b;
+	const SQU: u8 = 0x0e;
+	const SCU: u8 = 0x0f;
+	const SC0: u8 = 0x10;
+	const SD0: u8 = 0x18;
+	const UC0: u8 = 0xe0;
+	const UD0: u8 = 0xe8;
+	const UQU: u8 = 0xf0;
+	const UDX: u8 = 0xf1;
+
+	/// Returns the offset of a window defined with SDn/UDn, if valid.
+	fn window_offset(idx: u8) -> Option<u32> {
+		match idx {
+			0x01..=0x67 => Some(idx as u32 * 0x80),
+			0x68..=0xa7 => Some(idx as u32 * 0x80 + 0xac00),
+			0xf9 => Some(0x00c0),
+			0xfa => Some(0x0250),
+			0xfb => Some(0x0370),
+			0xfc => Some(0x0530),
+			0xfd => Some(0x3040),
+			0xfe => Some(0x30a0),
+			0xff => Some(0xff60),
+			_ => None,
+		}
+	}
+
+	/// Returns the SDn/UDn index of the window which contains the code point,
+	/// if any.
+	fn window_index(cp: u32) -> Option<u8> {
+		match cp {
+			0x0080..=0x337f => Some((cp >> 7) as _),
+			0xe000..=0xffff => Some(((cp - 0xac00) >> 7) as _),
+			_ => None,
+		}
+	}
+
+	/// Tells whether the character is written as-is in single-byte mode.
+	fn is_direct(cp: u32) -> bool {
+		matches!(cp, 0x00 | 0x09 | 0x0a | 0x0d | 0x20..=0x7f)
+	}
+
+	pub(super) fn decode(data: &[u8]) -> Vec<u16> {
+		let mut windows = INITIAL_DYNAMIC_WINDOWS;
+		let mut active = 0usize;
+		let mut unicode_mode = false;
+		let mut out = Vec::<u16>::with_capacity(data.len());
+		let mut i = 0;
+
+		let push_cp = |out: &mut Vec<u16>, cp: u32| {
+			let ch = char::from_u32(cp).unwrap_or(char::REPLACEMENT_CHARACTER);
+			let mut buf = [0u16; 2];
+			out.extend_from_slice(ch.encode_utf16(&mut buf));
+		};
+		let replacement = char::REPLACEMENT_CHARACTER as u16;
+
+		while i < data.len() {
+			let b = data[i];
+			i += 1;
+
+			if unicode_mode {
+				match b {
+					UC0..=0xe7 => {
+						active = (b - UC0) as _;
+						unicode_mode = false;
+					},
+					UD0..=0xef => match data.get(i).and_then(|idx| window_offset(*idx)) {
+						Some(off) => {
+							active = (b - UD0) as _;
+							windows[active] = off;
+							unicode_mode = false;
+							i += 1;
+						},
+						None => { out.push(replacement); break; },
+					},
+					UQU => match data.get(i..i + 2) {
+						Some(hl) => { out.push(u16::from_be_bytes([hl[0], hl[1]])); i += 2; },
+						None => { out.push(replacement); break; },
+					},
+					UDX => match data.get(i..i + 2) {
+						Some(hl) => {
+							active = (hl[0] >> 5) as _;
+							windows[active] = 0x10000 + ((((hl[0] & 0x1f) as u32) << 8 | hl[1] as u32) << 7);
+							unicode_mode = false;
+							i += 2;
+						},
+						None => { out.push(replacement); break; },
+					},
+					0xf2 => out.push(replacement), // reserved
+					_ => match data.get(i) {
+						Some(lo) => { out.push(u16::from_be_bytes([b, *lo])); i += 1; },
+						None => { out.push(replacement); break; },
+					},
+				}
+				continue;
+			}
+
+			match b {
+				SQ0..=0x08 => match data.get(i) { // quote from window
+					Some(q) => {
+						let n = (b - SQ0) as usize;
+						push_cp(&mut out, if *q < 0x80 {
+							STATIC_WINDOWS[n] + *q as u32
+						} else {
+							windows[n] + (*q - 0x80) as u32
+						});
+						i += 1;
+					},
+					None => { out.push(replacement); break; },
+				},
+				SDX => match data.get(i..i + 2) {
+					Some(hl) => {
+						active = (hl[0] >> 5) as _;
+						windows[active] = 0x10000 + ((((hl[0] & 0x1f) as u32) << 8 | hl[1] as u32) << 7);
+						i += 2;
+					},
+					None => { out.push(replacement); break; },
+				},
+				0x0c => out.push(replacement), // reserved
+				SQU => match data.get(i..i + 2) {
+					Some(hl) => { out.push(u16::from_be_bytes([hl[0], hl[1]])); i += 2; },
+					None => { out.push(replacement); break; },
+				},
+				SCU => unicode_mode = true,
+				SC0..=0x17 => active = (b - SC0) as _,
+				SD0..=0x1f => match data.get(i).and_then(|idx| window_offset(*idx)) {
+					Some(off) => {
+						active = (b - SD0) as _;
+						windows[active] = off;
+						i += 1;
+					},
+					None => { out.push(replacement); break; },
+				},
+				0x80..=0xff => push_cp(&mut out, windows[active] + (b - 0x80) as u32),
+				_ => out.push(b as _), // 0x00, 0x09, 0x0a, 0x0d and 0x20-0x7f
+			}
+		}
+		out
+	}
+
+	/// Writes a BMP character in Unicode mode.
+	fn push_unicode(out: &mut Vec<u8>, cp: u32) {
+		if (0xe0..=0xf2).contains(&(cp >> 8)) {
+			out.push(UQU); // high byte would be taken as a tag
+		}
+		out.extend_from_slice(&(cp as u16).to_be_bytes());
+	}
+
+	pub(super) fn encode(text: &str) -> Vec<u8> {
+		let mut windows = INITIAL_DYNAMIC_WINDOWS;
+		let mut active = 0usize;
+		let mut next_to_define = 0usize; // round-robin redefinition of windows
+		let mut unicode_mode = false;
+		let mut out = Vec::<u8>::with_capacity(text.len());
+
+		let chars = text.chars().map(|ch| ch as u32).collect::<Vec<_>>();
+		let in_window = |cp: u32, off: u32| (off..off + 0x80).contains(&cp);
+
+		for (idx, cp) in chars.iter().copied().enumerate() {
+			let next = chars.get(idx + 1).copied();
+
+			if unicode_mode {
+				let back_to_bytes = is_direct(cp)
+					|| windows.iter().any(|off| in_window(cp, *off))
+					|| (cp >= 0x10000);
+				if back_to_bytes {
+					out.push(UC0 + active as u8);
+					unicode_mode = false;
+				} else {
+					push_unicode(&mut out, cp);
+					continue;
+				}
+			}
+
+			if is_direct(cp) {
+				out.push(cp as _);
+			} else if cp < 0x20 {
+				out.extend_from_slice(&[SQ0, cp as _]); // quote from static window 0
+			} else if in_window(cp, windows[active]) {
+				out.push((0x80 + cp - windows[active]) as _);
+			} else if let Some(n) = windows.iter().position(|off| in_window(cp, *off)) {
+				if next.is_some_and(|nx| in_window(nx, windows[n])) {
+					active = n;
+					out.extend_from_slice(&[SC0 + n as u8, (0x80 + cp - windows[n]) as _]);
+				} else {
+					out.extend_from_slice(&[SQ0 + n as u8, (0x80 + cp - windows[n]) as _]);
+				}
+			} else if let Some(n) = STATIC_WINDOWS.iter().skip(1).position(|off| in_window(cp, *off)) {
+				out.extend_from_slice(&[SQ0 + n as u8 + 1, (cp - STATIC_WINDOWS[n + 1]) as _]);
+			} else if cp >= 0x10000 {
+				let n = next_to_define;
+				next_to_define = (next_to_define + 1) % 8;
+				let off_idx = (cp - 0x10000) >> 7;
+				windows[n] = 0x10000 + (off_idx << 7);
+				active = n;
+				out.extend_from_slice(&[SDX, ((n as u32) << 5 | off_idx >> 8) as _,
+					(off_idx & 0xff) as _, (0x80 + (cp & 0x7f)) as _]);
+			} else if let Some(win_idx) = window_index(cp) {
+				let n = next_to_define;
+				next_to_define = (next_to_define + 1) % 8;
+				windows[n] = window_offset(win_idx).unwrap();
+				active = n;
+				out.extend_from_slice(&[SD0 + n as u8, win_idx, (0x80 + cp - windows[n]) as _]);
+			} else if next.is_some_and(|nx| !is_direct(nx) && window_index(nx).is_none() && nx < 0x10000) {
+				out.push(SCU); // a run of characters without windows, like CJK
+				unicode_mode = true;
+				push_unicode(&mut out, cp);
+			} else {
+				out.push(SQU);
+				out.extend_from_slice(&(cp as u16).to_be_bytes());
+			}
+		}
+		out
+	}
+}
+
+/// [BOCU-1](https://www.unicode.org/notes/tn6/) encoding and decoding, ported
+/// from the reference implementation.
+mod bocu1 {
+	const ASCII_PREV: i32 = 0x40;
+	const MIN: i32 = 0x21;
+	const MIDDLE: i32 = 0x90;
+	const RESET: u8 = 0xff;
+	const TRAIL_CONTROLS_COUNT: i32 = 20;
+	const TRAIL_BYTE_OFFSET: i32 = MIN - TRAIL_CONTROLS_COUNT;
+	const TRAIL_COUNT: i32 = (0xff - MIN + 1) + TRAIL_CONTROLS_COUNT;
+	const SINGLE: i32 = 64;
+	const LEAD_2: i32 = 43;
+	const LEAD_3: i32 = 3;
+	const REACH_POS_1: i32 = SINGLE - 1;
+	const REACH_NEG_1: i32 = -SINGLE;
+	const REACH_POS_2: i32 = REACH_POS_1 + LEAD_2 * TRAIL_COUNT;
+	const REACH_NEG_2: i32 = REACH_NEG_1 - LEAD_2 * TRAIL_COUNT;
+	const REACH_POS_3: i32 = REACH_POS_2 + LEAD_3 * TRAIL_COUNT * TRAIL_COUNT;
+	const REACH_NEG_3: i32 = REACH_NEG_2 - LEAD_3 * TRAIL_COUNT * TRAIL_COUNT;
+	const START_POS_2: i32 = MIDDLE + REACH_POS_1 + 1;
+	const START_POS_3: i32 = START_POS_2 + LEAD_2;
+	const START_POS_4: i32 = START_POS_3 + LEAD_3;
+	const START_NEG_2: i32 = MIDDLE + REACH_NEG_1;
+	const START_NEG_3: i32 = START_NEG_2 - LEAD_2;
+	const START_NEG_4: i32 = START_NEG_3 - LEAD_3;
+
+	/// C0 control bytes used as the first trail values.
+	const TRAIL_TO_BYTE: [u8; TRAIL_CONTROLS_COUNT as usize] = [
+		0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x10, 0x11, 0x12, 0x13,
+		0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1c, 0x1d, 0x1e, 0x1f,
+	];
+
+	fn trail_to_byte(t: i32) -> u8 {
+		if t < TRAIL_CONTROLS_COUNT {
+			TRAIL_TO_BYTE[t as usize]
+		} else {
+			(t + TRAIL_BYTE_OFFSET) as _
+		}
+	}
+
+	fn byte_to_trail(b: u8) -> Option<i32> {
+		if b as i32 >= MIN {
+			Some(b as i32 - TRAIL_BYTE_OFFSET)
+		} else {
+			TRAIL_TO_BYTE.iter().position(|tb| *tb == b).map(|t| t as _)
+		}
+	}
+
+	/// Computes the next "previous" code point, placed in the middle of its
+	/// script block.
+	fn next_prev(cp: i32) -> i32 {
+		match cp {
+			0x3040..=0x309f => 0x3070, // Hiragana
+			0x4e00..=0x9fa5 => 0x4e00 - REACH_NEG_2, // CJK Unihan
+			0xac00..=0xd7a3 => (0xd7a3 + 0xac00) / 2, // Hangul
+			_ => (cp & !0x7f) + ASCII_PREV,
+		}
+	}
+
+	pub(super) fn decode(data: &[u8]) -> Vec<u32> {
+		let mut prev = ASCII_PREV;
+		let mut count = 0; // pending trail bytes
+		let mut diff = 0;
+		let mut out = Vec::<u32>::with_capacity(data.len());
+
+		for b in data.iter().copied() {
+			if count == 0 {
+				let bi = b as i32;
+				if b <= 0x20 {
+					if b != 0x20 {
+						prev = ASCII_PREV;
+					}
+					out.push(b as _);
+				} else if (START_NEG_2..START_POS_2).contains(&bi) { // single-byte difference
+					let cp = prev + bi - MIDDLE;
+					prev = next_prev(cp);
+					out.push(cp as _);
+				} else if b == RESET {
+					prev = ASCII_PREV;
+				} else { // lead byte
+					(diff, count) = if bi >= START_NEG_2 {
+						if bi < START_POS_3 {
+							((bi - START_POS_2) * TRAIL_COUNT + REACH_POS_1 + 1, 1)
+						} else if bi < START_POS_4 {
+							((bi - START_POS_3) * TRAIL_COUNT * TRAIL_COUNT + REACH_POS_2 + 1, 2)
+						} else {
+							(REACH_POS_3 + 1, 3)
+						}
+					} else if bi >= START_NEG_3 {
+						((bi - START_NEG_2) * TRAIL_COUNT + REACH_NEG_1, 1)
+					} else if bi > MIN {
+						((bi - START_NEG_3) * TRAIL_COUNT * TRAIL_COUNT + REACH_NEG_2, 2)
+					} else {
+						(-TRAIL_COUNT * TRAIL_COUNT * TRAIL_COUNT + REACH_NEG_3, 3)
+					};
+				}
+				continue;
+			}
+
+			let t = match byte_to_trail(b) { // trail byte
+				Some(t) => t,
+				None => {
+					prev = ASCII_PREV;
+					count = 0;
+					out.push(0xfffd);
+					continue;
+				},
+			};
+			match count {
+				1 => {
+					let cp = prev + diff + t;
+					count = 0;
+					if (0..=0x10ffff).contains(&cp) {
+						prev = next_prev(cp);
+						out.push(cp as _);
+					} else {
+						prev = ASCII_PREV;
+						out.push(0xfffd);
+					}
+				},
+				2 => { diff += t * TRAIL_COUNT; count = 1; },
+				_ => { diff += t * TRAIL_COUNT * TRAIL_COUNT; count = 2; },
+			}
+		}
+
+		if count > 0 {
+			out.push(0xfffd); // truncated sequence
+		}
+		out
+	}
+
+	pub(super) fn encode(text: &str) -> Vec<u8> {
+		let mut prev = ASCII_PREV;
+		let mut out = Vec::<u8>::with_capacity(text.len());
+
+		for cp in text.chars().map(|ch| ch as i32) {
+			if cp <= 0x20 {
+				if cp != 0x20 {
+					prev = ASCII_PREV;
+				}
+				out.push(cp as _);
+				continue;
+			}
+
+			let mut diff = cp - prev;
+			prev = next_prev(cp);
+
+			if (REACH_NEG_1..=REACH_POS_1).contains(&diff) {
+				out.push((MIDDLE + diff) as _);
+				continue;
+			}
+
+			let (lead, count) = if diff >= REACH_NEG_1 {
+				if diff <= REACH_POS_2 {
+					diff -= REACH_POS_1 + 1;
+					(START_POS_2, 1)
+				} else if diff <= REACH_POS_3 {
+					diff -= REACH_POS_2 + 1;
+					(START_POS_3, 2)
+				} else {
+					diff -= REACH_POS_3 + 1;
+					(START_POS_4, 3)
+				}
+			} else if diff >= REACH_NEG_2 {
+				diff -= REACH_NEG_1;
+				(START_NEG_2, 1)
+			} else if diff >= REACH_NEG_3 {
+				diff -= REACH_NEG_2;
+				(START_NEG_3, 2)
+			} else {
+				diff -= REACH_NEG_3;
+				(START_NEG_4, 3)
+			};
+
+			let mut trail = [0u8; 3];
+			for idx in (0..count).rev() { // trail bytes like digits, least significant last
+				trail[idx] = trail_to_byte(diff.rem_euclid(TRAIL_COUNT));
+				diff = diff.div_euclid(TRAIL_COUNT);
+			}
+			out.push((lead + diff) as _);
+			out.extend_from_slice(&trail[..count]);
+		}
+		out
+	}
+}
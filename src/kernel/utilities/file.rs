@@ -30,6 +30,12 @@ pub enum FileAccess {
 /// If you just want to read the file, consider memory-mapping it with
 /// [`FileMapped`](crate::FileMapped), which tends to be faster.
 ///
+/// `File` and `&File` implement [`Read`](std::io::Read),
+/// [`Write`](std::io::Write) and [`Seek`](std::io::Seek), so they can be used
+/// with the standard library and other crates. The reads and writes aren't
+/// buffered, so consider wrapping the file in a
+/// [`BufReader`](std::io::BufReader) or a [`BufWriter`](std::io::BufWriter).
+///
 /// # Examples
 ///
 /// Reading the contents as a string:
@@ -62,6 +68,54 @@ pub struct File {
 	hfile: CloseHandleGuard<HFILE>,
 }
 
+impl std::io::Read for File {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		std::io::Read::read(&mut &*self, buf)
+	}
+}
+impl std::io::Read for &File {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // ReadFile takes an u32 length
+		Ok(self.hfile.ReadFile(&mut buf[..len], None)? as _)
+	}
+}
+
+impl std::io::Write for File {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		std::io::Write::write(&mut &*self, buf)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		std::io::Write::flush(&mut &*self)
+	}
+}
+impl std::io::Write for &File {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // WriteFile takes an u32 length
+		Ok(self.hfile.WriteFile(&buf[..len], None)? as _)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(()) // writes aren't buffered
+	}
+}
+
+impl std::io::Seek for File {
+	fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+		std::io::Seek::seek(&mut &*self, pos)
+	}
+}
+impl std::io::Seek for &File {
+	fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+		let (off, move_method) = match pos {
+			std::io::SeekFrom::Start(off) => (off as i64, co::FILE_STARTING_POINT::BEGIN),
+			std::io::SeekFrom::Current(off) => (off, co::FILE_STARTING_POINT::CURRENT),
+			std::io::SeekFrom::End(off) => (off, co::FILE_STARTING_POINT::END),
+		};
+		Ok(self.hfile.SetFilePointerEx(off, move_method)? as _)
+	}
+}
+
 impl File {
 	/// Opens a file with the desired access.
 	#[must_use]
@@ -140,6 +194,26 @@ impl File {
 		Ok(data)
 	}
 
+	/// Reads all the bytes from the file and decodes them as text with
+	/// [`Encoding::decode`](crate::Encoding::decode). If the encoding is
+	/// [`Unknown`](crate::Encoding::Unknown), it will be guessed.
+	///
+	/// The internal file pointer will be rewound to the beginning of the file.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let f = w::File::open("C:\\Temp\\foo.txt", w::FileAccess::ExistingReadOnly)?;
+	/// let text = f.read_all_text(w::Encoding::Win1252)?;
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	#[must_use]
+	pub fn read_all_text(&self, encoding: Encoding) -> SysResult<String> {
+		Ok(encoding.decode(&self.read_all()?).to_string())
+	}
+
 	/// Truncates or expands the file, according to the new size. Zero will empty
 	/// the file.
 	///
@@ -85,6 +85,24 @@ impl FileMapped {
 		self.hview.as_slice(self.size as _)
 	}
 
+	/// Decodes the mapped memory as text with
+	/// [`Encoding::decode`](crate::Encoding::decode). If the encoding is
+	/// [`Unknown`](crate::Encoding::Unknown), it will be guessed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let f = w::FileMapped::open("C:\\Temp\\foo.txt", w::FileAccess::ExistingReadOnly)?;
+	/// let text = f.to_text(w::Encoding::Unknown);
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	#[must_use]
+	pub fn to_text(&self, encoding: Encoding) -> String {
+		encoding.decode(self.as_slice()).to_string()
+	}
+
 	/// Returns the underlying file handle.
 	#[must_use]
 	pub fn hfile(&self) -> &HFILE {
@@ -0,0 +1,418 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// A single image of an [`IconFile`](crate::IconFile).
+///
+/// The fields hold the raw values of the `ICONDIRENTRY` structure, so an
+/// image is written back exactly as it was read.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IconImage {
+	/// Width in pixels, where zero means 256.
+	pub width: u8,
+	/// Height in pixels, where zero means 256.
+	pub height: u8,
+	/// Number of colors in the palette, zero if there's no palette.
+	pub color_count: u8,
+	/// Reserved, usually zero.
+	pub reserved: u8,
+	/// Color planes of an icon, or the horizontal hotspot of a cursor.
+	pub planes: u16,
+	/// Bits per pixel of an icon, or the vertical hotspot of a cursor.
+	pub bit_count: u16,
+	/// The image payload: either a PNG file, or a `BITMAPINFOHEADER` followed
+	/// by the palette, the XOR mask and the AND mask.
+	pub data: Vec<u8>,
+}
+
+impl IconImage {
+	/// Returns the width and height in pixels, as stored in the image payload.
+	/// If the payload can't be read, returns the dimensions declared in the
+	/// directory entry.
+	#[must_use]
+	pub fn size(&self) -> (u32, u32) {
+		if self.is_png() {
+			if let (Some(cx), Some(cy)) = (be_u32(&self.data, 16), be_u32(&self.data, 20)) {
+				return (cx, cy);
+			}
+		} else if let (Some(cx), Some(cy)) = (le_u32(&self.data, 4), le_u32(&self.data, 8)) {
+			return (cx, (cy as i32).unsigned_abs() / 2); // height includes the AND mask
+		}
+		(
+			if self.width == 0 { 256 } else { self.width as _ },
+			if self.height == 0 { 256 } else { self.height as _ },
+		)
+	}
+
+	/// Returns the bits per pixel, as stored in the image payload. If the
+	/// payload can't be read, returns the `bit_count` field.
+	#[must_use]
+	pub fn bit_depth(&self) -> u16 {
+		if self.is_png() {
+			if let (Some(depth), Some(color_type)) = (self.data.get(24), self.data.get(25)) {
+				let channels = match color_type {
+					2 => 3, // RGB
+					4 => 2, // gray + alpha
+					6 => 4, // RGBA
+					_ => 1, // gray or palette
+				};
+				return *depth as u16 * channels;
+			}
+		} else if let Some(bit_count) = le_u16(&self.data, 14) {
+			return bit_count;
+		}
+		self.bit_count
+	}
+
+	/// Returns the horizontal and vertical hotspot of a cursor image, which are
+	/// stored in the `planes` and `bit_count` fields.
+	#[must_use]
+	pub const fn hotspot(&self) -> (u16, u16) {
+		(self.planes, self.bit_count)
+	}
+
+	/// Tells whether the image payload is a PNG file, instead of a bitmap.
+	#[must_use]
+	pub fn is_png(&self) -> bool {
+		self.data.starts_with(&PNG_SIGNATURE)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// The data of an [`IconFile`](crate::IconFile) converted to the
+/// `RT_GROUP_ICON`/`RT_GROUP_CURSOR` and `RT_ICON`/`RT_CURSOR` resource layout,
+/// as generated by [`IconFile::to_resources`](crate::IconFile::to_resources).
+#[derive(Clone)]
+pub struct IconResources {
+	/// Whether the resources are cursors, instead of icons.
+	pub is_cursor: bool,
+	/// The `GRPICONDIR` or `GRPCURSORDIR` data, which refers to the images by
+	/// their IDs.
+	pub group: Vec<u8>,
+	/// The ID and the data of each `RT_ICON` or `RT_CURSOR` resource.
+	pub images: Vec<(u16, Vec<u8>)>,
+}
+
+impl IconResources {
+	/// Writes the group and all the images to an executable by calling
+	/// [`HUPDATERSRC::UpdateResource`](crate::prelude::kernel_Hupdatersrc::UpdateResource).
+	///
+	/// # Examples
+	///
+	/// Replacing the main icon of an executable:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let ico = w::IconFile::parse_from_file("C:\\Temp\\app.ico")?;
+	/// let res = ico.to_resources(1)?;
+	///
+	/// let hupd = w::HUPDATERSRC::BeginUpdateResource("C:\\Temp\\app.exe", false)?;
+	/// res.update_resource(&hupd, w::IdStr::Id(1), w::LANGID::USER_DEFAULT)?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	pub fn update_resource(&self,
+		hupdatersrc: &HUPDATERSRC,
+		group_id: IdStr,
+		language: LANGID,
+	) -> SysResult<()>
+	{
+		let (rt_group, rt_image) = if self.is_cursor {
+			(co::RT::GROUP_CURSOR, co::RT::CURSOR)
+		} else {
+			(co::RT::GROUP_ICON, co::RT::ICON)
+		};
+
+		for (id, data) in self.images.iter() {
+			hupdatersrc.UpdateResource(RtStr::Rt(rt_image), IdStr::Id(*id), language, data)?;
+		}
+		hupdatersrc.UpdateResource(RtStr::Rt(rt_group), group_id, language, &self.group)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// Reads and writes the images of an `.ico` or `.cur` file, and converts them
+/// to and from the resource layout used inside executables.
+///
+/// This is a pure Rust implementation, no system calls are made, except when
+/// reading and writing files. Malformed data fails with
+/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA).
+///
+/// The image payloads are written contiguously after the directory, in the
+/// directory order – which is how virtually all files are laid out – so
+/// parsing and serializing such a file gives back the same bytes.
+///
+/// # Examples
+///
+/// Listing the images of an icon:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let ico = w::IconFile::parse_from_file("C:\\Temp\\app.ico")?;
+///
+/// for img in ico.images.iter() {
+///     let (cx, cy) = img.size();
+///     println!("{}x{}, {} bpp, {}", cx, cy, img.bit_depth(),
+///         if img.is_png() { "PNG" } else { "BMP" });
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct IconFile {
+	/// Whether the file is a cursor, instead of an icon.
+	pub is_cursor: bool,
+	/// The images, in the order they appear in the directory.
+	pub images: Vec<IconImage>,
+}
+
+impl IconFile {
+	/// Creates a new, empty icon or cursor.
+	#[must_use]
+	pub const fn new(is_cursor: bool) -> Self {
+		Self { is_cursor, images: Vec::new() }
+	}
+
+	/// Parses the contents of an `.ico` or `.cur` file.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		let is_cursor = read_dir_header(data)?;
+		let count = le_u16(data, 4).ok_or(co::ERROR::INVALID_DATA)? as usize;
+
+		let images = (0..count)
+			.map(|idx| {
+				let entry = data.get(6 + idx * 16..6 + (idx + 1) * 16) // ICONDIRENTRY
+					.ok_or(co::ERROR::INVALID_DATA)?;
+				let sz = le_u32(entry, 8).unwrap() as usize;
+				let off = le_u32(entry, 12).unwrap() as usize;
+				let payload = off.checked_add(sz)
+					.and_then(|end| data.get(off..end))
+					.ok_or(co::ERROR::INVALID_DATA)?;
+
+				Ok(IconImage {
+					width: entry[0],
+					height: entry[1],
+					color_count: entry[2],
+					reserved: entry[3],
+					planes: le_u16(entry, 4).unwrap(),
+					bit_count: le_u16(entry, 6).unwrap(),
+					data: payload.to_vec(),
+				})
+			})
+			.collect::<SysResult<Vec<_>>>()?;
+
+		Ok(Self { is_cursor, images })
+	}
+
+	/// Parses an `.ico` or `.cur` file directly from the disk. The file will be
+	/// [mapped in memory](crate::FileMapped) during reading.
+	#[must_use]
+	pub fn parse_from_file(file_path: &str) -> SysResult<Self> {
+		let fin = FileMapped::open(file_path, FileAccess::ExistingReadOnly)?;
+		Self::parse(fin.as_slice())
+	}
+
+	/// Builds an `IconFile` from an `RT_GROUP_ICON` or `RT_GROUP_CURSOR`
+	/// resource. The callback receives the ID of each `RT_ICON` or `RT_CURSOR`
+	/// resource referenced by the group, and must return its data.
+	///
+	/// For cursors, the resource layout doesn't keep the `color_count` and
+	/// `reserved` fields, which are then recomputed from the bitmap header.
+	///
+	/// # Examples
+	///
+	/// Extracting the main icon of an executable:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let f = w::FileMapped::open("C:\\Temp\\app.exe", w::FileAccess::ExistingReadOnly)?;
+	/// let pe = w::PeFile::parse(f.as_slice())?;
+	///
+	/// let group = pe.find_resource(
+	///     &w::RtStr::Rt(co::RT::GROUP_ICON), &w::IdStr::Id(1), None)?
+	///     .ok_or(co::ERROR::RESOURCE_NAME_NOT_FOUND)?;
+	///
+	/// let ico = w::IconFile::from_resources(group.data, |id| {
+	///     pe.find_resource(&w::RtStr::Rt(co::RT::ICON), &w::IdStr::Id(id), None)?
+	///         .map(|res| res.data.to_vec())
+	///         .ok_or(co::ERROR::RESOURCE_NAME_NOT_FOUND)
+	/// })?;
+	///
+	/// ico.serialize_to_file("C:\\Temp\\app.ico")?;
+	/// # Ok::<_, co::ERROR>(())
+	/// ```
+	#[must_use]
+	pub fn from_resources<F>(group: &[u8], mut image_data: F) -> SysResult<Self>
+		where F: FnMut(u16) -> SysResult<Vec<u8>>,
+	{
+		let is_cursor = read_dir_header(group)?;
+		let count = le_u16(group, 4).ok_or(co::ERROR::INVALID_DATA)? as usize;
+
+		let images = (0..count)
+			.map(|idx| {
+				let entry = group.get(6 + idx * 14..6 + (idx + 1) * 14) // GRPICONDIRENTRY
+					.ok_or(co::ERROR::INVALID_DATA)?;
+				let id = le_u16(entry, 12).unwrap();
+				let data = image_data(id)?;
+
+				if is_cursor {
+					let hot_x = le_u16(&data, 0).ok_or(co::ERROR::INVALID_DATA)?;
+					let hot_y = le_u16(&data, 2).ok_or(co::ERROR::INVALID_DATA)?;
+					let mut img = IconImage {
+						width: le_u16(entry, 0).unwrap() as _, // 256 becomes zero
+						height: (le_u16(entry, 2).unwrap() / 2) as _,
+						color_count: 0,
+						reserved: 0,
+						planes: hot_x,
+						bit_count: hot_y,
+						data: data[4..].to_vec(),
+					};
+					let bpp = img.bit_depth();
+					if !img.is_png() && bpp < 8 {
+						img.color_count = 1 << bpp;
+					}
+					Ok(img)
+				} else {
+					Ok(IconImage {
+						width: entry[0],
+						height: entry[1],
+						color_count: entry[2],
+						reserved: entry[3],
+						planes: le_u16(entry, 4).unwrap(),
+						bit_count: le_u16(entry, 6).unwrap(),
+						data,
+					})
+				}
+			})
+			.collect::<SysResult<Vec<_>>>()?;
+
+		Ok(Self { is_cursor, images })
+	}
+
+	/// Serializes the directory and the images to the contents of an `.ico` or
+	/// `.cur` file.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> SysResult<Vec<u8>> {
+		let count = u16::try_from(self.images.len())
+			.map_err(|_| co::ERROR::INVALID_DATA)?;
+		let dir_sz = 6 + self.images.len() * 16;
+		let tot_sz = dir_sz + self.images.iter().map(|img| img.data.len()).sum::<usize>();
+		u32::try_from(tot_sz).map_err(|_| co::ERROR::INVALID_DATA)?;
+
+		let mut buf = Vec::with_capacity(tot_sz);
+		write_dir_header(&mut buf, self.is_cursor, count);
+
+		let mut off = dir_sz;
+		for img in self.images.iter() {
+			buf.extend_from_slice(&[img.width, img.height, img.color_count, img.reserved]);
+			buf.extend_from_slice(&img.planes.to_le_bytes());
+			buf.extend_from_slice(&img.bit_count.to_le_bytes());
+			buf.extend_from_slice(&(img.data.len() as u32).to_le_bytes());
+			buf.extend_from_slice(&(off as u32).to_le_bytes());
+			off += img.data.len();
+		}
+		for img in self.images.iter() {
+			buf.extend_from_slice(&img.data);
+		}
+		Ok(buf)
+	}
+
+	/// Serializes the directory and the images directly to a file.
+	pub fn serialize_to_file(&self, file_path: &str) -> SysResult<()> {
+		let fout = File::open(file_path, FileAccess::OpenOrCreateRW)?;
+		fout.erase_and_write(&self.serialize_to_bytes()?)?;
+		Ok(())
+	}
+
+	/// Converts the images to the resource layout: a single `RT_GROUP_ICON`
+	/// (or `RT_GROUP_CURSOR`) resource, and one `RT_ICON` (or `RT_CURSOR`)
+	/// resource for each image, numbered sequentially from `first_id`.
+	///
+	/// Icon images are stored exactly as they are, so
+	/// [`from_resources`](crate::IconFile::from_resources) gives back the same
+	/// file. Cursor images have the hotspot prepended to their data, as
+	/// expected by the system.
+	#[must_use]
+	pub fn to_resources(&self, first_id: u16) -> SysResult<IconResources> {
+		let count = u16::try_from(self.images.len())
+			.map_err(|_| co::ERROR::INVALID_DATA)?;
+		if first_id as usize + self.images.len() > 0x1_0000 {
+			return Err(co::ERROR::INVALID_DATA); // IDs would overflow
+		}
+
+		let mut group = Vec::with_capacity(6 + self.images.len() * 14);
+		write_dir_header(&mut group, self.is_cursor, count);
+		let mut images = Vec::with_capacity(self.images.len());
+
+		for (idx, img) in self.images.iter().enumerate() {
+			let id = first_id + idx as u16;
+			let data = if self.is_cursor {
+				let (cx, cy) = img.size();
+				let cx = u16::try_from(cx).map_err(|_| co::ERROR::INVALID_DATA)?;
+				let cy = cy.checked_mul(2)
+					.and_then(|v| u16::try_from(v).ok())
+					.ok_or(co::ERROR::INVALID_DATA)?;
+				group.extend_from_slice(&cx.to_le_bytes());
+				group.extend_from_slice(&cy.to_le_bytes()); // includes the AND mask
+				group.extend_from_slice(&1u16.to_le_bytes());
+				group.extend_from_slice(&img.bit_depth().to_le_bytes());
+
+				let mut data = Vec::with_capacity(4 + img.data.len());
+				data.extend_from_slice(&img.planes.to_le_bytes()); // hotspot
+				data.extend_from_slice(&img.bit_count.to_le_bytes());
+				data.extend_from_slice(&img.data);
+				data
+			} else {
+				group.extend_from_slice(&[img.width, img.height, img.color_count, img.reserved]);
+				group.extend_from_slice(&img.planes.to_le_bytes());
+				group.extend_from_slice(&img.bit_count.to_le_bytes());
+				img.data.clone()
+			};
+
+			let data_sz = u32::try_from(data.len()).map_err(|_| co::ERROR::INVALID_DATA)?;
+			group.extend_from_slice(&data_sz.to_le_bytes());
+			group.extend_from_slice(&id.to_le_bytes());
+			images.push((id, data));
+		}
+
+		Ok(IconResources { is_cursor: self.is_cursor, group, images })
+	}
+}
+
+//------------------------------------------------------------------------------
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// Validates the `ICONDIR`/`GRPICONDIR` header, returning whether it's a
+/// cursor.
+fn read_dir_header(data: &[u8]) -> SysResult<bool> {
+	match (le_u16(data, 0), le_u16(data, 2)) { // idReserved, idType
+		(Some(0), Some(1)) => Ok(false),
+		(Some(0), Some(2)) => Ok(true),
+		_ => Err(co::ERROR::INVALID_DATA),
+	}
+}
+
+fn write_dir_header(buf: &mut Vec<u8>, is_cursor: bool, count: u16) {
+	buf.extend_from_slice(&0u16.to_le_bytes());
+	buf.extend_from_slice(&(if is_cursor { 2u16 } else { 1 }).to_le_bytes());
+	buf.extend_from_slice(&count.to_le_bytes());
+}
+
+fn le_u16(data: &[u8], off: usize) -> Option<u16> {
+	data.get(off..off + 2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn le_u32(data: &[u8], off: usize) -> Option<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn be_u32(data: &[u8], off: usize) -> Option<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
@@ -1,8 +1,15 @@
+use std::ops::{Deref, DerefMut};
+
 use crate::decl::*;
 
 /// High-level abstraction to load, manage and serialize sections and key/value
 /// pairs of a `.ini` file.
 ///
+/// Comments and blank lines are skipped when parsing, and the sections and
+/// entries are serialized in a plain `key=value` layout. To keep comments,
+/// blank lines and the original formatting of the file, use an
+/// [`IniFile`](crate::IniFile) instead.
+///
 /// # Examples
 ///
 /// Printing all sections, keys and values:
@@ -31,47 +38,53 @@ use crate::decl::*;
 /// println!("{}", ini.value("the_section", "the_key").unwrap());
 /// # Ok::<_, winsafe::co::ERROR>(())
 /// ```
+///
+/// Reading typed values:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let ini = w::Ini::parse_str("[window]\nwidth=800\nmaximized=yes");
+///
+/// let width = ini.value_as::<i32>("window", "width")?;
+/// let maximized = ini.value_bool("window", "maximized")?;
+/// # Ok::<_, w::IniError>(())
+/// ```
+#[derive(Clone, Default)]
 pub struct Ini {
 	/// All the sections of the file. They can be modified at will.
+	///
+	/// Entries which appear before the first section header are kept in a
+	/// section with an empty name.
 	pub sections: Vec<IniSection>,
 }
 
 impl Ini {
+	/// Creates a new, empty `Ini`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
 	/// Parses an `Ini` from a string.
+	///
+	/// Comments, blank lines and lines which are not section headers or
+	/// key/value pairs are skipped. Use
+	/// [`parse_str_strict`](crate::Ini::parse_str_strict) to reject the latter.
 	#[must_use]
 	pub fn parse_str(contents: &str) -> Self {
-		let mut sections = Vec::<IniSection>::default();
-		let mut cur_section = IniSection {
-			name: "".to_owned(),
-			entries: Vec::<IniEntry>::default(),
-		};
-
-		for line in contents.lines() {
-			let line = line.trim();
-
-			if line.starts_with('[') && line.ends_with(']') {
-				if cur_section.name != "" || !cur_section.entries.is_empty() {
-					sections.push(cur_section);
-				}
-				cur_section = IniSection {
-					name: line[1..line.len() - 1].to_owned(),
-					entries: Vec::<IniEntry>::default(),
-				};
-				continue;
-			}
-
-			if let Some(eq_idx) = line.find('=') {
-				cur_section.entries.push(IniEntry {
-					key: line[..eq_idx].to_owned(),
-					val: line[eq_idx + 1..].to_owned(),
-				});
-			}
-		}
-		if cur_section.name != "" || !cur_section.entries.is_empty() {
-			sections.push(cur_section);
-		}
+		let (ini_file, _) = IniFile::parse_impl(contents); // malformed lines are just skipped
+		ini_file.into_ini()
+	}
 
-		Self { sections }
+	/// Parses an `Ini` from a string, failing with
+	/// [`IniError::MalformedLine`](crate::IniError::MalformedLine) at the first
+	/// line which is not a section header, a key/value pair, a comment or
+	/// blank.
+	#[must_use]
+	pub fn parse_str_strict(contents: &str) -> Result<Self, IniError> {
+		IniFile::parse_str_strict(contents)
+			.map(|ini_file| ini_file.into_ini())
 	}
 
 	/// Parses an `Ini` from raw bytes with
@@ -117,38 +130,11 @@ impl Ini {
 			.map(|sec| sec)
 	}
 
-	/// Serializes the sections and entries to a string.
+	/// Serializes the sections and entries to a string, as `[section]` and
+	/// `key=value` lines, separated by `\r\n`.
 	#[must_use]
 	pub fn serialize_to_str(&self) -> String {
-		let mut tot_size = 0;
-		for (idx, section) in self.sections.iter().enumerate() {
-			tot_size += section.name.len() + 2 + 2;
-			for entry in section.entries.iter() {
-				tot_size += entry.key.len() + 1 + entry.val.len() + 2;
-			}
-			if idx < self.sections.len() - 1 {
-				tot_size += 2;
-			}
-		}
-
-		let mut buf = String::with_capacity(tot_size);
-		for (idx, section) in self.sections.iter().enumerate() {
-			buf.push('[');
-			buf.push_str(&section.name);
-			buf.push_str("]\r\n");
-
-			for entry in section.entries.iter() {
-				buf.push_str(&entry.key);
-				buf.push('=');
-				buf.push_str(&entry.val);
-				buf.push_str("\r\n");
-			}
-
-			if idx < self.sections.len() - 1 {
-				buf.push_str("\r\n");
-			}
-		}
-		buf
+		serialize_impl(self, &[], &[], "\r\n", true)
 	}
 
 	/// Serializes the sections and entries to raw bytes with
@@ -187,22 +173,18 @@ impl Ini {
 		match self.find_section_mut(section) {
 			Some(sec) => match sec.find_entry_mut(key) {
 				Some(ent) => ent.val = new_value.to_owned(),
-				None => sec.entries.push(IniEntry { // entry does not exist in section
-					key: key.to_owned(),
-					val: new_value.to_owned(),
-				}),
+				None => sec.entries.push(IniEntry::new(key, new_value)), // entry does not exist in section
+			},
+			None => { // section does not exist
+				let mut sec = IniSection::new(section);
+				sec.entries.push(IniEntry::new(key, new_value));
+				self.sections.push(sec);
 			},
-			None => self.sections.push(IniSection { // section does not exist
-				name: section.to_owned(),
-				entries: vec![IniEntry {
-					key: key.to_owned(),
-					val: new_value.to_owned(),
-				}],
-			}),
 		}
 	}
 
-	/// Returns a reference to the specified value, if any.
+	/// Returns a reference to the specified value, if any. If the key appears
+	/// more than once, the first one is returned.
 	///
 	/// The search is case-insensitive.
 	#[must_use]
@@ -243,11 +225,402 @@ impl Ini {
 					.map(|ent| &mut ent.val)
 			})
 	}
+
+	/// Returns all the values of a key which appears more than once in the
+	/// section, in order.
+	///
+	/// The search is case-insensitive.
+	#[must_use]
+	pub fn values(&self, section: &str, key: &str) -> Vec<&str> {
+		let key_uc = key.to_uppercase();
+		self.find_section(section)
+			.map(|sec| {
+				sec.entries.iter()
+					.filter(|ent| ent.key.to_uppercase() == key_uc)
+					.map(|ent| ent.val.as_ref())
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Returns the specified value converted with
+	/// [`str::parse`](https://doc.rust-lang.org/std/primitive.str.html#method.parse).
+	///
+	/// The search is case-insensitive.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let ini = w::Ini::parse_from_file("C:\\Temp\\foo.ini")?;
+	///
+	/// match ini.value_as::<u16>("server", "port") {
+	///     Ok(port) => println!("Port: {}", port),
+	///     Err(e) => eprintln!("{}", e),
+	/// }
+	/// # Ok::<_, winsafe::co::ERROR>(())
+	/// ```
+	pub fn value_as<T>(&self, section: &str, key: &str) -> Result<T, IniError>
+		where T: std::str::FromStr,
+	{
+		let val = self.value_or_err(section, key)?;
+		val.parse::<T>()
+			.map_err(|_| IniError::InvalidValue {
+				section: section.to_owned(),
+				key: key.to_owned(),
+				value: val.to_owned(),
+			})
+	}
+
+	/// Returns the specified value as a `bool`. The accepted values are `1`,
+	/// `yes`, `true` and `on` for `true`; `0`, `no`, `false` and `off` for
+	/// `false`.
+	///
+	/// The search and the value are case-insensitive.
+	pub fn value_bool(&self, section: &str, key: &str) -> Result<bool, IniError> {
+		let val = self.value_or_err(section, key)?;
+		match val.to_lowercase().as_str() {
+			"1" | "yes" | "true" | "on" => Ok(true),
+			"0" | "no" | "false" | "off" => Ok(false),
+			_ => Err(IniError::InvalidValue {
+				section: section.to_owned(),
+				key: key.to_owned(),
+				value: val.to_owned(),
+			}),
+		}
+	}
+
+	/// Returns the specified value split by the given separator, with each item
+	/// trimmed. An empty value returns an empty list.
+	///
+	/// The search is case-insensitive.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let ini = w::Ini::parse_str("[files]\nrecent=a.txt, b.txt, c.txt");
+	///
+	/// let recent = ini.value_list("files", "recent", ',').unwrap();
+	/// assert_eq!(recent, ["a.txt", "b.txt", "c.txt"]);
+	/// ```
+	#[must_use]
+	pub fn value_list(&self,
+		section: &str,
+		key: &str,
+		separator: char,
+	) -> Option<Vec<&str>>
+	{
+		self.value(section, key)
+			.map(|val| {
+				if val.trim().is_empty() {
+					Vec::default()
+				} else {
+					val.split(separator).map(|item| item.trim()).collect()
+				}
+			})
+	}
+
+	fn value_or_err(&self, section: &str, key: &str) -> Result<&str, IniError> {
+		self.value(section, key)
+			.ok_or_else(|| IniError::NotFound {
+				section: section.to_owned(),
+				key: key.to_owned(),
+			})
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// An [`Ini`](crate::Ini) which keeps the layout of the parsed file.
+///
+/// Parsing is lossless: comments, blank lines, duplicate keys, quoting and the
+/// original formatting of each line are kept, so a file which is parsed and
+/// serialized back is left untouched, except for the values which were
+/// actually changed.
+///
+/// The sections and entries are reached through
+/// [`Deref`](std::ops::Deref) to the [`Ini`](crate::Ini), so all its methods
+/// can be used.
+///
+/// # Examples
+///
+/// Changing a value of a commented file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let ini_path = "C:\\Temp\\foo.ini";
+/// let mut ini_file = w::IniFile::parse_from_file(ini_path)?;
+///
+/// ini_file.set_value("the_section", "the_key", "new_value");
+///
+/// ini_file.serialize_to_file(ini_path)?; // comments are kept
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone)]
+pub struct IniFile {
+	ini: Ini,
+	layout: Vec<SectionLayout>, // original lines of the parsed sections
+	trailing_lines: Vec<String>,
+	line_break: Option<&'static str>,
+	final_line_break: bool,
+}
+
+impl Default for IniFile {
+	fn default() -> Self {
+		Self::from(Ini::default())
+	}
+}
+
+impl From<Ini> for IniFile {
+	fn from(ini: Ini) -> Self {
+		Self {
+			ini,
+			layout: Vec::<SectionLayout>::default(),
+			trailing_lines: Vec::<String>::default(),
+			line_break: None,
+			final_line_break: true,
+		}
+	}
+}
+
+impl Deref for IniFile {
+	type Target = Ini;
+
+	fn deref(&self) -> &Self::Target {
+		&self.ini
+	}
+}
+
+impl DerefMut for IniFile {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.ini
+	}
+}
+
+impl IniFile {
+	/// Creates a new, empty `IniFile`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses an `IniFile` from a string.
+	///
+	/// Lines which are not section headers, key/value pairs, comments or blank
+	/// are kept verbatim, like comments. Use
+	/// [`parse_str_strict`](crate::IniFile::parse_str_strict) to reject them.
+	#[must_use]
+	pub fn parse_str(contents: &str) -> Self {
+		let (ini_file, _) = Self::parse_impl(contents); // malformed lines are kept verbatim
+		ini_file
+	}
+
+	/// Parses an `IniFile` from a string, failing with
+	/// [`IniError::MalformedLine`](crate::IniError::MalformedLine) at the first
+	/// line which is not a section header, a key/value pair, a comment or
+	/// blank.
+	#[must_use]
+	pub fn parse_str_strict(contents: &str) -> Result<Self, IniError> {
+		match Self::parse_impl(contents) {
+			(_, Some(err)) => Err(err),
+			(ini_file, None) => Ok(ini_file),
+		}
+	}
+
+	/// Parses an `IniFile` from raw bytes with
+	/// [`WString::parse`](crate::WString::parse).
+	#[must_use]
+	pub fn parse_bytes(bytes: &[u8]) -> SysResult<Self> {
+		Ok(
+			Self::parse_str(&WString::parse(bytes)?.to_string()),
+		)
+	}
+
+	/// Parses an `IniFile` directly from a file with
+	/// [`WString::parse`](crate::WString::parse). The file will be
+	/// [mapped in memory](crate::FileMapped) during reading for maximum
+	/// performance.
+	#[must_use]
+	pub fn parse_from_file(ini_path: &str) -> SysResult<Self> {
+		let fin = FileMapped::open(ini_path, FileAccess::ExistingReadOnly)?;
+		Self::parse_bytes(fin.as_slice())
+	}
+
+	/// Parses the contents, returning also the error of the first malformed
+	/// line, if any.
+	fn parse_impl(contents: &str) -> (Self, Option<IniError>) {
+		let mut ini_file = Self {
+			line_break: if contents.contains("\r\n") {
+				Some("\r\n")
+			} else if contents.contains('\n') {
+				Some("\n")
+			} else {
+				None
+			},
+			final_line_break: contents.is_empty() || contents.ends_with('\n'),
+			..Default::default()
+		};
+		let mut first_err = None;
+
+		let mut cur_section = IniSection::new("");
+		let mut cur_layout = SectionLayout::new("", None, Vec::<String>::default());
+		let mut pending = Vec::<String>::default(); // comments and blank lines
+
+		for (idx, line) in contents.lines().enumerate() {
+			let trimmed = line.trim();
+
+			if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
+				pending.push(line.to_owned());
+
+			} else if let Some(name) = SectionLayout::parse_header(trimmed) {
+				if cur_layout.header.is_some() || !cur_section.entries.is_empty() {
+					ini_file.ini.sections.push(cur_section);
+					ini_file.layout.push(cur_layout);
+				}
+				cur_section = IniSection::new(name);
+				cur_layout = SectionLayout::new(name, Some(line),
+					std::mem::take(&mut pending));
+
+			} else if let Some(entry) = EntryLayout::parse_line(line) {
+				cur_section.entries.push(IniEntry::new(&entry.key, &entry.val));
+				cur_layout.entries.push(EntryLayout {
+					leading_lines: std::mem::take(&mut pending),
+					..entry
+				});
+
+			} else {
+				if first_err.is_none() {
+					first_err = Some(IniError::MalformedLine {
+						line: idx + 1,
+						text: line.to_owned(),
+					});
+				}
+				pending.push(line.to_owned()); // keep it verbatim
+			}
+		}
+
+		if cur_layout.header.is_some() || !cur_section.entries.is_empty() {
+			ini_file.ini.sections.push(cur_section);
+			ini_file.layout.push(cur_layout);
+		}
+		ini_file.trailing_lines = pending;
+		(ini_file, first_err)
+	}
+
+	/// Returns the underlying [`Ini`](crate::Ini), discarding the layout of
+	/// the parsed file.
+	#[must_use]
+	pub fn into_ini(self) -> Ini {
+		self.ini
+	}
+
+	/// Serializes the sections and entries to a string.
+	///
+	/// Parsed lines are written back with their original formatting, along
+	/// with the comments and blank lines which preceded them; sections and
+	/// entries are matched to the parsed lines by name and key, so a renamed
+	/// one is written as new. New sections and entries are written as
+	/// `[section]` and `key=value`. The line breaks of the parsed contents are
+	/// kept, defaulting to `\r\n`.
+	#[must_use]
+	pub fn serialize_to_str(&self) -> String {
+		serialize_impl(&self.ini, &self.layout, &self.trailing_lines,
+			self.line_break.unwrap_or("\r\n"), self.final_line_break)
+	}
+
+	/// Serializes the sections and entries to raw bytes with
+	/// [`String::into_bytes`](std::string::String::into_bytes).
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> Vec<u8> {
+		self.serialize_to_str().into_bytes()
+	}
+
+	/// Serializes the data directly to a file with
+	/// [`String::into_bytes`](std::string::String::into_bytes).
+	pub fn serialize_to_file(&self, ini_path: &str) -> SysResult<()> {
+		let fout = File::open(ini_path, FileAccess::OpenOrCreateRW)?;
+		fout.erase_and_write(&self.serialize_to_bytes())?;
+		Ok(())
+	}
+}
+
+/// Writes the sections, reusing the original lines of the layout, if any.
+fn serialize_impl(
+	ini: &Ini,
+	layout: &[SectionLayout],
+	trailing_lines: &[String],
+	line_break: &str,
+	final_line_break: bool,
+) -> String
+{
+	let mut lines = Vec::<String>::default();
+	let mut used_secs = vec![false; layout.len()];
+
+	for section in ini.sections.iter() {
+		let sec_layout = layout.iter()
+			.enumerate()
+			.position(|(i, lay)| !used_secs[i] && lay.name == section.name)
+			.map(|i| {
+				used_secs[i] = true;
+				&layout[i]
+			});
+
+		match sec_layout {
+			Some(lay) => {
+				lines.extend(lay.leading_lines.iter().cloned());
+				if let Some(header) = &lay.header {
+					lines.push(header.clone());
+				}
+			},
+			None if section.name.is_empty() => {}, // entries before any section
+			None => {
+				if !lines.is_empty() {
+					lines.push(String::default()); // separate new sections
+				}
+				lines.push(format!("[{}]", section.name));
+			},
+		}
+
+		let mut used_ents = vec![false; sec_layout.map_or(0, |lay| lay.entries.len())];
+		for entry in section.entries.iter() {
+			let ent_layout = sec_layout.and_then(|lay| {
+				lay.entries.iter()
+					.enumerate()
+					.position(|(i, ent)| !used_ents[i] && ent.key == entry.key)
+					.map(|i| {
+						used_ents[i] = true;
+						&lay.entries[i]
+					})
+			});
+
+			match ent_layout {
+				Some(lay) => {
+					lines.extend(lay.leading_lines.iter().cloned());
+					lines.push(lay.serialize_line(&entry.val));
+				},
+				None => lines.push(
+					format!("{}={}", entry.key, EntryLayout::quote_val(&entry.val, None)),
+				),
+			}
+		}
+	}
+	lines.extend(trailing_lines.iter().cloned());
+
+	let mut buf = lines.join(line_break);
+	if !lines.is_empty() && final_line_break {
+		buf.push_str(line_break);
+	}
+	buf
 }
 
 //------------------------------------------------------------------------------
 
 /// A single section of an [`Ini`](crate::Ini).
+#[derive(Clone)]
 pub struct IniSection {
 	/// The name of this section.
 	pub name: String,
@@ -256,6 +629,15 @@ pub struct IniSection {
 }
 
 impl IniSection {
+	/// Creates a new, empty section.
+	#[must_use]
+	pub fn new(name: &str) -> Self {
+		Self {
+			name: name.to_owned(),
+			entries: Vec::<IniEntry>::default(),
+		}
+	}
+
 	/// Returns a reference to the [`IniEntry`](crate::IniEntry) with the given
 	/// key, if any.
 	///
@@ -285,9 +667,158 @@ impl IniSection {
 
 /// A single key/value pair of an [`IniSection`](crate::IniSection) of an
 /// [`Ini`](crate::Ini).
+#[derive(Clone)]
 pub struct IniEntry {
 	/// Key of this entry.
 	pub key: String,
-	/// Value of this entry.
+	/// Value of this entry, without the surrounding quotes, if any.
 	pub val: String,
 }
+
+impl IniEntry {
+	/// Creates a new entry.
+	#[must_use]
+	pub fn new(key: &str, val: &str) -> Self {
+		Self {
+			key: key.to_owned(),
+			val: val.to_owned(),
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// The original lines of a parsed section.
+#[derive(Clone)]
+struct SectionLayout {
+	name: String,
+	header: Option<String>, // none for the entries before any section
+	leading_lines: Vec<String>,
+	entries: Vec<EntryLayout>,
+}
+
+impl SectionLayout {
+	fn new(name: &str, header: Option<&str>, leading_lines: Vec<String>) -> Self {
+		Self {
+			name: name.to_owned(),
+			header: header.map(|h| h.to_owned()),
+			leading_lines,
+			entries: Vec::<EntryLayout>::default(),
+		}
+	}
+
+	/// Returns the name if the trimmed line is a section header, which may be
+	/// followed by a comment, like `[name] ; comment`.
+	fn parse_header(trimmed: &str) -> Option<&str> {
+		if !trimmed.starts_with('[') {
+			None
+		} else if trimmed.ends_with(']') {
+			Some(&trimmed[1..trimmed.len() - 1])
+		} else {
+			let close_idx = trimmed.find(']')?;
+			let after = trimmed[close_idx + 1..].trim_start();
+			(after.starts_with(';') || after.starts_with('#'))
+				.then(|| &trimmed[1..close_idx])
+		}
+	}
+}
+
+/// The original line of a parsed entry, and where its value is.
+#[derive(Clone)]
+struct EntryLayout {
+	key: String,
+	val: String,
+	leading_lines: Vec<String>,
+	line: String,
+	val_span: (usize, usize), // including the quotes
+	quote: Option<char>,
+}
+
+impl EntryLayout {
+	/// Parses a `key=value` line, returning `None` if there's no `=`.
+	fn parse_line(line: &str) -> Option<Self> {
+		let trimmed = line.trim();
+		let eq_idx = trimmed.find('=')?;
+		let full_val = &trimmed[eq_idx + 1..];
+		let val_start = (line.len() - line.trim_start().len()) + eq_idx + 1;
+
+		let quote = full_val.chars().next()
+			.filter(|ch| (*ch == '"' || *ch == '\'')
+				&& full_val.len() >= 2 && full_val.ends_with(*ch));
+		let val = match quote {
+			Some(_) => &full_val[1..full_val.len() - 1],
+			None => full_val,
+		};
+
+		Some(Self {
+			key: trimmed[..eq_idx].to_owned(),
+			val: val.to_owned(),
+			leading_lines: Vec::<String>::default(),
+			line: line.to_owned(),
+			val_span: (val_start, val_start + full_val.len()),
+			quote,
+		})
+	}
+
+	/// Returns the line to be written with the given value, reusing the
+	/// original formatting.
+	fn serialize_line(&self, val: &str) -> String {
+		if val == self.val {
+			self.line.clone()
+		} else {
+			let mut line = String::with_capacity(self.line.len() + val.len());
+			line.push_str(&self.line[..self.val_span.0]);
+			line.push_str(&Self::quote_val(val, self.quote));
+			line.push_str(&self.line[self.val_span.1..]);
+			line
+		}
+	}
+
+	/// Quotes the value if it was quoted before, or if it would otherwise lose
+	/// its surrounding quotes when parsed back.
+	fn quote_val(val: &str, quote: Option<char>) -> String {
+		let needs_quotes = val.len() >= 2
+			&& (val.starts_with('"') && val.ends_with('"')
+				|| val.starts_with('\'') && val.ends_with('\''));
+		match quote.or(if needs_quotes { Some('"') } else { None }) {
+			Some(q) => format!("{}{}{}", q, val, q),
+			None => val.to_owned(),
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// An error returned by [`Ini`](crate::Ini) when parsing a malformed file, or
+/// when reading a typed value.
+#[derive(Clone, PartialEq, Eq)]
+pub enum IniError {
+	/// A line which is not a section header, a key/value pair, a comment or
+	/// blank. The line number is one-based.
+	MalformedLine { line: usize, text: String },
+	/// The section or the key doesn't exist.
+	NotFound { section: String, key: String },
+	/// The value could not be converted to the requested type.
+	InvalidValue { section: String, key: String, value: String },
+}
+
+impl std::error::Error for IniError {}
+
+impl std::fmt::Display for IniError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::MalformedLine { line, text } =>
+				write!(f, "Malformed line {}: \"{}\"", line, text),
+			Self::NotFound { section, key } =>
+				write!(f, "Key \"{}\" not found in section \"{}\"", key, section),
+			Self::InvalidValue { section, key, value } =>
+				write!(f, "Invalid value \"{}\" for key \"{}\" in section \"{}\"",
+					value, key, section),
+		}
+	}
+}
+impl std::fmt::Debug for IniError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		std::fmt::Display::fmt(self, f)
+	}
+}
@@ -1,15 +1,27 @@
+mod context_error;
 mod encoding;
 mod file_mapped;
 mod file;
 mod heap_block;
+mod icon_file;
 mod ini;
+mod pe_file;
+mod registry_hive;
+mod w_str;
 mod w_string;
 
+pub mod command_line;
 pub mod path;
 
+pub use context_error::ContextError;
 pub use encoding::Encoding;
 pub use file_mapped::FileMapped;
 pub use file::{File, FileAccess};
 pub use heap_block::HeapBlock;
-pub use ini::{Ini, IniEntry, IniSection};
+pub use icon_file::{IconFile, IconImage, IconResources};
+pub use ini::{Ini, IniEntry, IniError, IniFile, IniSection};
+pub use pe_file::{PeExport, PeExports, PeExportTarget, PeFile, PeImport,
+	PeImportFunc, PeOptionalHeader, PeResource};
+pub use registry_hive::{RegistryHive, RegistryHiveKey};
+pub use w_str::WStr;
 pub use w_string::WString;
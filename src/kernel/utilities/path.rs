@@ -8,6 +8,66 @@ use crate::decl::*;
 use crate::guard::*;
 use crate::prelude::*;
 
+/// Lexically canonicalizes a path, resolving `.` and `..` parts, replacing
+/// forward slashes with backslashes, and removing repeated and trailing
+/// separators. No file system access is performed.
+///
+/// A `..` part never goes above the root of an absolute or rooted path;
+/// leading `..` parts of a relative path are kept. An empty relative path is
+/// returned as `.`.
+///
+/// Verbatim paths, starting with `\\?\`, are returned unchanged, since Windows
+/// doesn't normalize them either.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let p = w::path::canonicalize("C:/Temp/./foo/../bar\\"); // C:\Temp\bar
+/// let q = w::path::canonicalize("..\\a\\..\\..\\b");        // ..\..\b
+/// ```
+#[must_use]
+pub fn canonicalize(path: &str) -> String {
+	let parsed = parse(path);
+	if parsed.prefix.as_ref().is_some_and(|prefix| prefix.is_verbatim()) {
+		return path.to_owned();
+	}
+
+	let anchored = parsed.has_root || parsed.is_absolute();
+	let mut parts: Vec<&str> = Vec::with_capacity(parsed.parts.len());
+	for part in parsed.parts.iter() {
+		match *part {
+			"." => {},
+			".." => match parts.last() {
+				Some(last) if *last != ".." => { parts.pop(); },
+				_ => if !anchored {
+					parts.push(".."); // can't go above the root of an anchored path
+				},
+			},
+			part => parts.push(part),
+		}
+	}
+
+	ParsedPath { parts, ..parsed }.to_string()
+}
+
+/// Compares two paths, case-insensitive, after
+/// [`canonicalize`](crate::path::canonicalize) them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let ord = w::path::cmp_ignore_case("C:\\Temp\\a.txt", "c:/temp/B.TXT"); // Less
+/// ```
+#[must_use]
+pub fn cmp_ignore_case(path1: &str, path2: &str) -> std::cmp::Ordering {
+	canonicalize(path1).chars().flat_map(|ch| ch.to_uppercase())
+		.cmp(canonicalize(path2).chars().flat_map(|ch| ch.to_uppercase()))
+}
+
 /// Returns an iterator over the files and folders within a directory.
 /// Optionally, a wildcard can be specified to filter files by name.
 ///
@@ -82,6 +142,21 @@ pub fn dir_walk<'a>(
 	DirWalkIter::new(dir_path.to_owned())
 }
 
+/// Tells whether two paths are equal, case-insensitive, after
+/// [`canonicalize`](crate::path::canonicalize) them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let same = w::path::eq_ignore_case("C:\\Temp\\..\\a.txt", "c:/A.TXT"); // true
+/// ```
+#[must_use]
+pub fn eq_ignore_case(path1: &str, path2: &str) -> bool {
+	cmp_ignore_case(path1, path2) == std::cmp::Ordering::Equal
+}
+
 /// Returns the path of the current EXE file, without the EXE filename, and
 /// without a trailing backslash.
 ///
@@ -201,6 +276,121 @@ pub fn is_hidden(full_path: &str) -> bool {
 	flags.has(co::FILE_ATTRIBUTE::HIDDEN)
 }
 
+/// Parses a path into its prefix, root and parts, without any file system
+/// access. Both backslashes and forward slashes are accepted as separators,
+/// except in verbatim paths, which accept only backslashes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let p = w::path::parse("\\\\server\\share\\dir\\file.txt");
+///
+/// assert_eq!(p.prefix, Some(w::path::PathPrefix::Unc("server", "share")));
+/// assert!(p.is_absolute());
+/// assert_eq!(p.parts, ["dir", "file.txt"]);
+/// ```
+#[must_use]
+pub fn parse(path: &str) -> ParsedPath<'_> {
+	fn is_sep(ch: char) -> bool {
+		ch == '\\' || ch == '/'
+	}
+	fn split_first(s: &str, verbatim: bool) -> (&str, &str) {
+		match s.find(|ch: char| ch == '\\' || (!verbatim && ch == '/')) {
+			Some(idx) => (&s[..idx], &s[idx..]),
+			None => (s, ""),
+		}
+	}
+	fn drive_letter(s: &str) -> Option<char> {
+		let mut chars = s.chars();
+		match (chars.next(), chars.next()) {
+			(Some(letter), Some(':')) if letter.is_ascii_alphabetic() => Some(letter),
+			_ => None,
+		}
+	}
+
+	let (prefix, rest, verbatim) = if let Some(rest) = path.strip_prefix("\\\\?\\") {
+		if rest.get(..4).is_some_and(|unc| unc.eq_ignore_ascii_case("UNC\\")) {
+			let (server, rest) = split_first(&rest[4..], true);
+			let (share, rest) = split_first(rest.strip_prefix('\\').unwrap_or(rest), true);
+			(Some(PathPrefix::VerbatimUnc(server, share)), rest, true)
+		} else if let Some(letter) = drive_letter(rest)
+			.filter(|_| rest.len() == 2 || rest[2..].starts_with('\\'))
+		{
+			(Some(PathPrefix::VerbatimDisk(letter)), &rest[2..], true)
+		} else {
+			let (name, rest) = split_first(rest, true);
+			(Some(PathPrefix::Verbatim(name)), rest, true)
+		}
+	} else if path.len() >= 4 && path.starts_with(is_sep)
+		&& path[1..].starts_with(is_sep) && path[2..].starts_with('.')
+		&& path[3..].starts_with(is_sep)
+	{
+		let (name, rest) = split_first(&path[4..], false);
+		(Some(PathPrefix::Device(name)), rest, false)
+	} else if path.starts_with(is_sep) && path[1..].starts_with(is_sep) {
+		let (server, rest) = split_first(&path[2..], false);
+		let (share, rest) = split_first(rest.strip_prefix(is_sep).unwrap_or(rest), false);
+		(Some(PathPrefix::Unc(server, share)), rest, false)
+	} else if let Some(letter) = drive_letter(path) {
+		(Some(PathPrefix::Disk(letter)), &path[2..], false)
+	} else {
+		(None, path, false)
+	};
+
+	ParsedPath {
+		prefix,
+		has_root: if verbatim { rest.starts_with('\\') } else { rest.starts_with(is_sep) },
+		parts: rest.split(|ch: char| ch == '\\' || (!verbatim && ch == '/'))
+			.filter(|part| !part.is_empty())
+			.collect(),
+	}
+}
+
+/// Computes the relative path which leads from the `base` directory to `path`,
+/// after [`canonicalize`](crate::path::canonicalize) them. Parts are compared
+/// case-insensitive.
+///
+/// Returns `None` if the paths have different prefixes or roots, so that no
+/// relative path exists between them. If the paths are equal, returns `.`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let r = w::path::relative_to(
+///     "C:\\Temp\\foo\\a.txt",
+///     "C:\\temp\\bar",
+/// ); // Some("..\\foo\\a.txt")
+/// ```
+#[must_use]
+pub fn relative_to(path: &str, base: &str) -> Option<String> {
+	let (path, base) = (canonicalize(path), canonicalize(base));
+	let (path, base) = (parse(&path), parse(&base));
+	let prefix_str = |p: &ParsedPath| p.prefix.as_ref()
+		.map(|prefix| prefix.to_string().to_uppercase());
+	if prefix_str(&path) != prefix_str(&base) || path.has_root != base.has_root {
+		return None;
+	}
+
+	let eq_part = |a: &&str, b: &&str| a.chars().flat_map(|ch| ch.to_uppercase())
+		.eq(b.chars().flat_map(|ch| ch.to_uppercase()));
+	let num_common = path.parts.iter()
+		.zip(base.parts.iter())
+		.take_while(|(a, b)| eq_part(a, b))
+		.count();
+	if base.parts[num_common..].contains(&"..") {
+		return None; // base goes above the common parts, which are unknown
+	}
+
+	let parts = std::iter::repeat_n("..", base.parts.len() - num_common)
+		.chain(path.parts[num_common..].iter().copied())
+		.collect::<Vec<_>>();
+	Some(if parts.is_empty() { ".".to_owned() } else { parts.join("\\") })
+}
+
 /// Replaces the extension by the given one.
 ///
 /// # Examples
@@ -294,6 +484,149 @@ pub fn split_parts(full_path: &str) -> Vec<&str> {
 	no_bs.split('\\').collect()
 }
 
+/// Converts an absolute path into the extended-length form, prefixed with
+/// `\\?\` or `\\?\UNC\`, which is necessary to pass paths longer than
+/// `MAX_PATH` to some Win32 functions. The path is
+/// [`canonicalize`](crate::path::canonicalize)d first, because Windows doesn't
+/// normalize extended-length paths.
+///
+/// Verbatim and device paths are returned unchanged. Returns `None` if the path
+/// is not absolute.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let p = w::path::to_extended_length("C:/Temp/../foo"); // Some("\\\\?\\C:\\foo")
+/// let q = w::path::to_extended_length("\\\\server\\share\\a"); // Some("\\\\?\\UNC\\server\\share\\a")
+/// ```
+#[must_use]
+pub fn to_extended_length(path: &str) -> Option<String> {
+	let parsed = parse(path);
+	match parsed.prefix {
+		Some(PathPrefix::Verbatim(_))
+			| Some(PathPrefix::VerbatimDisk(_))
+			| Some(PathPrefix::VerbatimUnc(_, _))
+			| Some(PathPrefix::Device(_)) => Some(path.to_owned()),
+		Some(PathPrefix::Disk(_)) if parsed.has_root =>
+			Some(format!("\\\\?\\{}", canonicalize(path))),
+		Some(PathPrefix::Unc(_, _)) =>
+			Some(format!("\\\\?\\UNC\\{}", &canonicalize(path)[2..])),
+		_ => None,
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// The prefix of a Windows path, returned by [`parse`](crate::path::parse).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathPrefix<'a> {
+	/// Drive letter, like `C:`.
+	Disk(char),
+	/// UNC server and share, like `\\server\share`.
+	Unc(&'a str, &'a str),
+	/// Verbatim prefix, like `\\?\name`.
+	Verbatim(&'a str),
+	/// Verbatim drive letter, like `\\?\C:`.
+	VerbatimDisk(char),
+	/// Verbatim UNC server and share, like `\\?\UNC\server\share`.
+	VerbatimUnc(&'a str, &'a str),
+	/// Device namespace, like `\\.\COM1`.
+	Device(&'a str),
+}
+
+impl<'a> std::fmt::Display for PathPrefix<'a> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Disk(letter) => write!(f, "{}:", letter),
+			Self::Unc(server, share) => write!(f, "\\\\{}{}{}",
+				server, if share.is_empty() { "" } else { "\\" }, share),
+			Self::Verbatim(name) => write!(f, "\\\\?\\{}", name),
+			Self::VerbatimDisk(letter) => write!(f, "\\\\?\\{}:", letter),
+			Self::VerbatimUnc(server, share) => write!(f, "\\\\?\\UNC\\{}{}{}",
+				server, if share.is_empty() { "" } else { "\\" }, share),
+			Self::Device(name) => write!(f, "\\\\.\\{}", name),
+		}
+	}
+}
+
+impl<'a> PathPrefix<'a> {
+	/// Returns `true` if the prefix starts with `\\?\`.
+	#[must_use]
+	pub const fn is_verbatim(&self) -> bool {
+		matches!(self, Self::Verbatim(_) | Self::VerbatimDisk(_) | Self::VerbatimUnc(_, _))
+	}
+}
+
+/// A Windows path broken into its components, returned by
+/// [`parse`](crate::path::parse).
+///
+/// The [`Display`](std::fmt::Display) implementation joins the components back,
+/// using backslashes as separators.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParsedPath<'a> {
+	/// The prefix, if any.
+	pub prefix: Option<PathPrefix<'a>>,
+	/// Whether there is a separator right after the prefix, or at the
+	/// beginning of the path if there is no prefix.
+	pub has_root: bool,
+	/// The non-empty parts between separators, including `.` and `..`.
+	pub parts: Vec<&'a str>,
+}
+
+impl<'a> std::fmt::Display for ParsedPath<'a> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if let Some(prefix) = &self.prefix {
+			write!(f, "{}", prefix)?;
+		}
+		if self.has_root {
+			write!(f, "\\")?;
+		} else if !self.parts.is_empty() && matches!(self.prefix,
+			Some(PathPrefix::Unc(_, _) | PathPrefix::VerbatimUnc(_, _) | PathPrefix::Device(_)))
+		{
+			write!(f, "\\")?; // these prefixes always need a separator before the parts
+		} else if self.prefix.is_none() && self.parts.is_empty() {
+			return write!(f, ".");
+		}
+		write!(f, "{}", self.parts.join("\\"))
+	}
+}
+
+impl<'a> ParsedPath<'a> {
+	/// Returns `true` if the path doesn't depend on the current directory or
+	/// drive: it has a drive letter followed by a root, or it has an UNC,
+	/// verbatim or device prefix.
+	#[must_use]
+	pub fn is_absolute(&self) -> bool {
+		match self.prefix {
+			None => false,
+			Some(PathPrefix::Disk(_)) => self.has_root,
+			Some(_) => true,
+		}
+	}
+
+	/// Returns `true` if the path has a drive letter but no root, like
+	/// `C:foo`, thus being relative to the current directory of that drive.
+	#[must_use]
+	pub fn is_drive_relative(&self) -> bool {
+		matches!(self.prefix, Some(PathPrefix::Disk(_))) && !self.has_root
+	}
+
+	/// Returns `true` if the path has a root but no prefix, like `\foo`, thus
+	/// being relative to the current drive.
+	#[must_use]
+	pub fn is_rooted(&self) -> bool {
+		self.prefix.is_none() && self.has_root
+	}
+
+	/// Returns `true` if the path has neither prefix nor root, like `foo\bar`.
+	#[must_use]
+	pub fn is_relative(&self) -> bool {
+		self.prefix.is_none() && !self.has_root
+	}
+}
+
 //------------------------------------------------------------------------------
 
 pub(in crate::kernel) struct DirListIter<'a> {
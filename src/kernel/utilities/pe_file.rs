@@ -0,0 +1,638 @@
+use crate::co;
+use crate::decl::*;
+
+/// The optional header of a [`PeFile`](crate::PeFile), whose layout depends
+/// on the bitness of the image.
+#[derive(Clone, Copy)]
+pub enum PeOptionalHeader {
+	/// PE32 image, usually 32-bit.
+	Pe32(IMAGE_OPTIONAL_HEADER32),
+	/// PE32+ image, usually 64-bit.
+	Pe64(IMAGE_OPTIONAL_HEADER64),
+}
+
+impl PeOptionalHeader {
+	/// Returns the `AddressOfEntryPoint` field.
+	#[must_use]
+	pub const fn address_of_entry_point(&self) -> u32 {
+		match self {
+			Self::Pe32(h) => h.AddressOfEntryPoint,
+			Self::Pe64(h) => h.AddressOfEntryPoint,
+		}
+	}
+
+	/// Returns the valid entries of the `DataDirectory` field, according to
+	/// `NumberOfRvaAndSizes`.
+	#[must_use]
+	pub fn data_directories(&self) -> &[IMAGE_DATA_DIRECTORY] {
+		let (dirs, num) = match self {
+			Self::Pe32(h) => (&h.DataDirectory, h.NumberOfRvaAndSizes),
+			Self::Pe64(h) => (&h.DataDirectory, h.NumberOfRvaAndSizes),
+		};
+		&dirs[..(num as usize).min(dirs.len())]
+	}
+
+	/// Returns the `DllCharacteristics` field.
+	#[must_use]
+	pub const fn dll_characteristics(&self) -> co::IMAGE_DLLCHARACTERISTICS {
+		match self {
+			Self::Pe32(h) => h.DllCharacteristics,
+			Self::Pe64(h) => h.DllCharacteristics,
+		}
+	}
+
+	/// Returns the `ImageBase` field.
+	#[must_use]
+	pub const fn image_base(&self) -> u64 {
+		match self {
+			Self::Pe32(h) => h.ImageBase as _,
+			Self::Pe64(h) => h.ImageBase,
+		}
+	}
+
+	/// Returns the `SizeOfHeaders` field.
+	#[must_use]
+	pub const fn size_of_headers(&self) -> u32 {
+		match self {
+			Self::Pe32(h) => h.SizeOfHeaders,
+			Self::Pe64(h) => h.SizeOfHeaders,
+		}
+	}
+
+	/// Returns the `SizeOfImage` field.
+	#[must_use]
+	pub const fn size_of_image(&self) -> u32 {
+		match self {
+			Self::Pe32(h) => h.SizeOfImage,
+			Self::Pe64(h) => h.SizeOfImage,
+		}
+	}
+
+	/// Returns the `Subsystem` field.
+	#[must_use]
+	pub const fn subsystem(&self) -> co::IMAGE_SUBSYSTEM {
+		match self {
+			Self::Pe32(h) => h.Subsystem,
+			Self::Pe64(h) => h.Subsystem,
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A DLL imported by a [`PeFile`](crate::PeFile), returned by
+/// [`PeFile::imports`](crate::PeFile::imports).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeImport {
+	/// Name of the imported DLL.
+	pub dll_name: String,
+	/// Functions imported from the DLL.
+	pub functions: Vec<PeImportFunc>,
+}
+
+/// A function of a [`PeImport`](crate::PeImport).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PeImportFunc {
+	/// Function imported by its ordinal.
+	Ordinal(u16),
+	/// Function imported by its name, along with the hint to its index in the
+	/// export name table of the DLL.
+	Name(u16, String),
+}
+
+/// A function of [`PeExports`](crate::PeExports).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeExport {
+	/// Ordinal of the function, already biased by the ordinal base.
+	pub ordinal: u32,
+	/// Name of the function, if exported by name.
+	pub name: Option<String>,
+	/// Where the function is.
+	pub target: PeExportTarget,
+}
+
+/// The export directory of a [`PeFile`](crate::PeFile), returned by
+/// [`PeFile::exports`](crate::PeFile::exports).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeExports {
+	/// Name of the DLL, as stored in the export directory.
+	pub dll_name: String,
+	/// Exported functions, ordered by ordinal.
+	pub functions: Vec<PeExport>,
+}
+
+/// Target of a [`PeExport`](crate::PeExport).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PeExportTarget {
+	/// Relative virtual address of the function within the image.
+	Rva(u32),
+	/// Function forwarded to another DLL, like `NTDLL.RtlAllocateHeap`.
+	Forwarder(String),
+}
+
+/// A resource entry of a [`PeFile`](crate::PeFile), returned by
+/// [`PeFile::resources`](crate::PeFile::resources).
+///
+/// The type, name and language are the same values yielded by
+/// [`HINSTANCE::EnumResourceTypes`](crate::prelude::kernel_Hinstance::EnumResourceTypes),
+/// [`HINSTANCE::EnumResourceNames`](crate::prelude::kernel_Hinstance::EnumResourceNames)
+/// and
+/// [`HINSTANCE::EnumResourceLanguages`](crate::prelude::kernel_Hinstance::EnumResourceLanguages).
+#[derive(Clone)]
+pub struct PeResource<'a> {
+	/// Type of the resource.
+	pub resource_type: RtStr,
+	/// Name of the resource.
+	pub resource_id: IdStr,
+	/// Language of the resource.
+	pub language: LANGID,
+	/// Code page of the resource data, usually zero.
+	pub code_page: u32,
+	/// The raw resource data.
+	pub data: &'a [u8],
+}
+
+//------------------------------------------------------------------------------
+
+/// Reads the headers, sections, imports, exports and resources of a
+/// [PE](https://learn.microsoft.com/en-us/windows/win32/debug/pe-format)
+/// image – an EXE or a DLL file – directly from its raw bytes, without loading
+/// it. Both PE32 and PE32+ images can be read, regardless of the bitness of the
+/// current process.
+///
+/// This is a pure Rust implementation, no system calls are made. Malformed
+/// data fails with
+/// [`co::ERROR::BAD_EXE_FORMAT`](crate::co::ERROR::BAD_EXE_FORMAT).
+///
+/// # Examples
+///
+/// Listing the imports of a DLL mapped in memory:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let f = w::FileMapped::open(
+///     "C:\\Temp\\foo.dll",
+///     w::FileAccess::ExistingReadOnly,
+/// )?;
+/// let pe = w::PeFile::parse(f.as_slice())?;
+///
+/// for import in pe.imports()? {
+///     println!("{}", import.dll_name);
+///     for func in import.functions.iter() {
+///         match func {
+///             w::PeImportFunc::Ordinal(ord) => println!("  #{}", ord),
+///             w::PeImportFunc::Name(_, name) => println!("  {}", name),
+///         }
+///     }
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+///
+/// Listing the resources:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let raw_bytes: Vec<u8>; // initialized somewhere
+/// # let raw_bytes = Vec::<u8>::new();
+/// let pe = w::PeFile::parse(&raw_bytes)?;
+///
+/// for res in pe.resources()? {
+///     println!("Type: {}, name: {}, lang: {}, size: {}",
+///         res.resource_type, res.resource_id,
+///         u16::from(res.language), res.data.len());
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub struct PeFile<'a> {
+	data: &'a [u8],
+	file_header: IMAGE_FILE_HEADER,
+	optional_header: PeOptionalHeader,
+	sections: Vec<IMAGE_SECTION_HEADER>,
+}
+
+impl<'a> PeFile<'a> {
+	/// Parses the headers of the image. The imports, exports and resources are
+	/// parsed only when requested.
+	#[must_use]
+	pub fn parse(data: &'a [u8]) -> SysResult<Self> {
+		if data.get(..2) != Some(b"MZ") {
+			return Err(co::ERROR::BAD_EXE_FORMAT);
+		}
+		let nt_off = read_u32(data, 0x3c)? as usize; // e_lfanew
+		if data.get(nt_off..nt_off + 4) != Some(b"PE\0\0") {
+			return Err(co::ERROR::BAD_EXE_FORMAT);
+		}
+
+		let file_header_off = nt_off + 4;
+		let file_header = read_struct::<IMAGE_FILE_HEADER>(data, file_header_off)?;
+
+		let opt_off = file_header_off + std::mem::size_of::<IMAGE_FILE_HEADER>();
+		let opt_sz = file_header.SizeOfOptionalHeader as usize;
+		let opt_data = data.get(opt_off..opt_off + opt_sz)
+			.ok_or(co::ERROR::BAD_EXE_FORMAT)?;
+		let optional_header = match read_u16(opt_data, 0)? { // Magic
+			0x10b => PeOptionalHeader::Pe32(read_struct_partial(opt_data)),
+			0x20b => PeOptionalHeader::Pe64(read_struct_partial(opt_data)),
+			_ => return Err(co::ERROR::BAD_EXE_FORMAT),
+		};
+
+		let sections_off = opt_off + opt_sz;
+		let sections = (0..file_header.NumberOfSections as usize)
+			.map(|idx| {
+				read_struct::<IMAGE_SECTION_HEADER>(data,
+					sections_off + idx * std::mem::size_of::<IMAGE_SECTION_HEADER>())
+			})
+			.collect::<SysResult<Vec<_>>>()?;
+
+		Ok(Self { data, file_header, optional_header, sections })
+	}
+
+	/// Returns the whole raw data of the image.
+	#[must_use]
+	pub const fn as_slice(&self) -> &'a [u8] {
+		self.data
+	}
+
+	/// Returns the given entry of the data directory, if present and not
+	/// empty.
+	#[must_use]
+	pub fn data_directory(&self,
+		entry: co::IMAGE_DIRECTORY_ENTRY,
+	) -> Option<IMAGE_DATA_DIRECTORY>
+	{
+		self.optional_header.data_directories()
+			.get(entry.raw() as usize)
+			.filter(|dir| dir.VirtualAddress != 0 && dir.Size != 0)
+			.copied()
+	}
+
+	/// Returns the COFF file header.
+	#[must_use]
+	pub const fn file_header(&self) -> &IMAGE_FILE_HEADER {
+		&self.file_header
+	}
+
+	/// Tells whether the image is PE32+, usually a 64-bit one.
+	#[must_use]
+	pub const fn is_pe32_plus(&self) -> bool {
+		matches!(self.optional_header, PeOptionalHeader::Pe64(_))
+	}
+
+	/// Returns the optional header, which is PE32 or PE32+.
+	#[must_use]
+	pub const fn optional_header(&self) -> &PeOptionalHeader {
+		&self.optional_header
+	}
+
+	/// Converts a relative virtual address into an offset within the raw data,
+	/// if the address is backed by the file.
+	#[must_use]
+	pub fn rva_to_offset(&self, rva: u32) -> Option<usize> {
+		if rva < self.optional_header.size_of_headers() {
+			return Some(rva as usize).filter(|off| *off < self.data.len());
+		}
+		self.sections.iter()
+			.find(|sec| {
+				let virt_sz = if sec.VirtualSize == 0 { sec.SizeOfRawData } else { sec.VirtualSize };
+				rva >= sec.VirtualAddress && rva - sec.VirtualAddress < virt_sz
+			})
+			.filter(|sec| rva - sec.VirtualAddress < sec.SizeOfRawData) // uninitialized data is not in the file
+			.and_then(|sec| sec.PointerToRawData.checked_add(rva - sec.VirtualAddress))
+			.map(|off| off as usize)
+			.filter(|off| *off < self.data.len())
+	}
+
+	/// Returns the raw data of the given section, as stored in the file.
+	#[must_use]
+	pub fn section_data(&self, section: &IMAGE_SECTION_HEADER) -> &'a [u8] {
+		let start = (section.PointerToRawData as usize).min(self.data.len());
+		let end = (start + section.SizeOfRawData as usize).min(self.data.len());
+		&self.data[start..end]
+	}
+
+	/// Returns the section headers.
+	#[must_use]
+	pub fn sections(&self) -> &[IMAGE_SECTION_HEADER] {
+		&self.sections
+	}
+
+	/// Returns the functions exported by the image.
+	///
+	/// If the image has no export directory, returns `None`.
+	#[must_use]
+	pub fn exports(&self) -> SysResult<Option<PeExports>> {
+		let dir = match self.data_directory(co::IMAGE_DIRECTORY_ENTRY::EXPORT) {
+			Some(dir) => dir,
+			None => return Ok(None),
+		};
+		let dir_off = self.rva_offset(dir.VirtualAddress)?;
+
+		let dll_name = self.rva_str(read_u32(self.data, dir_off + 12)?)?;
+		let ordinal_base = read_u32(self.data, dir_off + 16)?;
+		let num_funcs = read_u32(self.data, dir_off + 20)? as usize;
+		let num_names = read_u32(self.data, dir_off + 24)? as usize;
+		let funcs_rva = read_u32(self.data, dir_off + 28)?;
+		let names_rva = read_u32(self.data, dir_off + 32)?;
+		let ords_rva = read_u32(self.data, dir_off + 36)?;
+
+		let funcs_off = self.rva_offset(funcs_rva)?;
+		if funcs_off + num_funcs * 4 > self.data.len() {
+			return Err(co::ERROR::BAD_EXE_FORMAT);
+		}
+
+		let mut names = vec![None; num_funcs];
+		if num_names > 0 {
+			let names_off = self.rva_offset(names_rva)?;
+			let ords_off = self.rva_offset(ords_rva)?;
+			for idx in 0..num_names {
+				let func_idx = read_u16(self.data, ords_off + idx * 2)? as usize;
+				let name = self.rva_str(read_u32(self.data, names_off + idx * 4)?)?;
+				if let Some(slot) = names.get_mut(func_idx) {
+					*slot = Some(name);
+				}
+			}
+		}
+
+		let mut functions = Vec::<PeExport>::with_capacity(num_funcs);
+		for (idx, name) in names.into_iter().enumerate() {
+			let func_rva = read_u32(self.data, funcs_off + idx * 4)?;
+			if func_rva == 0 {
+				continue; // gap in the ordinals
+			}
+			let is_forwarder = func_rva >= dir.VirtualAddress
+				&& func_rva - dir.VirtualAddress < dir.Size;
+			functions.push(PeExport {
+				ordinal: ordinal_base.wrapping_add(idx as _),
+				name,
+				target: if is_forwarder {
+					PeExportTarget::Forwarder(self.rva_str(func_rva)?)
+				} else {
+					PeExportTarget::Rva(func_rva)
+				},
+			});
+		}
+
+		Ok(Some(PeExports { dll_name, functions }))
+	}
+
+	/// Returns the DLLs and functions imported by the image, in the order they
+	/// appear in the import directory.
+	#[must_use]
+	pub fn imports(&self) -> SysResult<Vec<PeImport>> {
+		let dir = match self.data_directory(co::IMAGE_DIRECTORY_ENTRY::IMPORT) {
+			Some(dir) => dir,
+			None => return Ok(Vec::default()),
+		};
+		let mut desc_off = self.rva_offset(dir.VirtualAddress)?;
+		let (thunk_sz, ordinal_flag) = if self.is_pe32_plus() {
+			(8, 1u64 << 63)
+		} else {
+			(4, 1u64 << 31)
+		};
+
+		let mut imports = Vec::<PeImport>::default();
+		loop {
+			let desc = self.data.get(desc_off..desc_off + 20) // IMAGE_IMPORT_DESCRIPTOR
+				.ok_or(co::ERROR::BAD_EXE_FORMAT)?;
+			if desc.iter().all(|b| *b == 0) {
+				break; // null terminator descriptor
+			}
+
+			let original_first_thunk = read_u32(desc, 0)?;
+			let first_thunk = read_u32(desc, 16)?;
+			let dll_name = self.rva_str(read_u32(desc, 12)?)?;
+
+			let mut thunk_off = self.rva_offset(
+				if original_first_thunk != 0 { original_first_thunk } else { first_thunk })?;
+			let mut functions = Vec::<PeImportFunc>::default();
+			loop {
+				let thunk = if thunk_sz == 8 {
+					read_u64(self.data, thunk_off)?
+				} else {
+					read_u32(self.data, thunk_off)? as u64
+				};
+				if thunk == 0 {
+					break;
+				}
+
+				functions.push(if thunk & ordinal_flag != 0 {
+					PeImportFunc::Ordinal(thunk as u16)
+				} else {
+					let by_name_off = self.rva_offset(thunk as u32)?; // IMAGE_IMPORT_BY_NAME
+					PeImportFunc::Name(
+						read_u16(self.data, by_name_off)?,
+						read_cstr(self.data, by_name_off + 2)?,
+					)
+				});
+				thunk_off += thunk_sz;
+			}
+
+			imports.push(PeImport { dll_name, functions });
+			desc_off += 20;
+		}
+		Ok(imports)
+	}
+
+	/// Returns all the resources of the image, by walking the type, name and
+	/// language levels of the resource directory tree.
+	#[must_use]
+	pub fn resources(&self) -> SysResult<Vec<PeResource<'a>>> {
+		let dir = match self.data_directory(co::IMAGE_DIRECTORY_ENTRY::RESOURCE) {
+			Some(dir) => dir,
+			None => return Ok(Vec::default()),
+		};
+		let rsrc_off = self.rva_offset(dir.VirtualAddress)?;
+
+		let mut resources = Vec::<PeResource>::default();
+		for (type_name, type_target) in self.res_dir_entries(rsrc_off, 0)? {
+			let type_off = match type_target {
+				ResTarget::Dir(off) => off,
+				ResTarget::Data(_) => return Err(co::ERROR::BAD_EXE_FORMAT),
+			};
+			let resource_type = match type_name {
+				ResName::Id(id) => RtStr::Rt(unsafe { co::RT::from_raw(id) }),
+				ResName::Str(s) => RtStr::from_str(&s),
+			};
+
+			for (res_name, name_target) in self.res_dir_entries(rsrc_off, type_off)? {
+				let name_off = match name_target {
+					ResTarget::Dir(off) => off,
+					ResTarget::Data(_) => return Err(co::ERROR::BAD_EXE_FORMAT),
+				};
+				let resource_id = match res_name {
+					ResName::Id(id) => IdStr::Id(id),
+					ResName::Str(s) => IdStr::from_str(&s),
+				};
+
+				for (lang_name, lang_target) in self.res_dir_entries(rsrc_off, name_off)? {
+					let (lang_id, entry_off) = match (lang_name, lang_target) {
+						(ResName::Id(id), ResTarget::Data(off)) => (id, rsrc_off + off),
+						_ => return Err(co::ERROR::BAD_EXE_FORMAT), // languages must point to data
+					};
+					let data_rva = read_u32(self.data, entry_off)?; // IMAGE_RESOURCE_DATA_ENTRY
+					let data_sz = read_u32(self.data, entry_off + 4)? as usize;
+					let data_off = self.rva_offset(data_rva)?;
+
+					resources.push(PeResource {
+						resource_type: resource_type.clone(),
+						resource_id: resource_id.clone(),
+						language: unsafe { LANGID::from_raw(lang_id) },
+						code_page: read_u32(self.data, entry_off + 8)?,
+						data: self.data.get(data_off..data_off + data_sz)
+							.ok_or(co::ERROR::BAD_EXE_FORMAT)?,
+					});
+				}
+			}
+		}
+		Ok(resources)
+	}
+
+	/// Returns the first resource with the given type and name and, if
+	/// specified, language.
+	///
+	/// String types and names are compared case-insensitively, like
+	/// [`HINSTANCE::FindResourceEx`](crate::prelude::kernel_Hinstance::FindResourceEx)
+	/// does.
+	#[must_use]
+	pub fn find_resource(&self,
+		resource_type: &RtStr,
+		resource_id: &IdStr,
+		language: Option<LANGID>,
+	) -> SysResult<Option<PeResource<'a>>>
+	{
+		Ok(
+			self.resources()?
+				.into_iter()
+				.find(|res| {
+					let type_eq = match (&res.resource_type, resource_type) {
+						(RtStr::Rt(a), RtStr::Rt(b)) => a == b,
+						(RtStr::Str(a), RtStr::Str(b)) => eq_ignore_case(a, b),
+						_ => false,
+					};
+					let id_eq = match (&res.resource_id, resource_id) {
+						(IdStr::Id(a), IdStr::Id(b)) => a == b,
+						(IdStr::Str(a), IdStr::Str(b)) => eq_ignore_case(a, b),
+						_ => false,
+					};
+					let lang_eq = match language {
+						Some(lang) => res.language == lang,
+						None => true,
+					};
+					type_eq && id_eq && lang_eq
+				}),
+		)
+	}
+
+	fn rva_offset(&self, rva: u32) -> SysResult<usize> {
+		self.rva_to_offset(rva).ok_or(co::ERROR::BAD_EXE_FORMAT)
+	}
+
+	fn rva_str(&self, rva: u32) -> SysResult<String> {
+		read_cstr(self.data, self.rva_offset(rva)?)
+	}
+
+	/// Returns the entries of an `IMAGE_RESOURCE_DIRECTORY`, each one with its
+	/// name and the offset of what it points to, relative to the resource
+	/// section.
+	fn res_dir_entries(&self,
+		rsrc_off: usize,
+		dir_off: usize,
+	) -> SysResult<Vec<(ResName, ResTarget)>>
+	{
+		let abs_off = rsrc_off + dir_off;
+		let num_entries = read_u16(self.data, abs_off + 12)? as usize // NumberOfNamedEntries
+			+ read_u16(self.data, abs_off + 14)? as usize; // NumberOfIdEntries
+
+		(0..num_entries)
+			.map(|idx| {
+				let entry_off = abs_off + 16 + idx * 8; // IMAGE_RESOURCE_DIRECTORY_ENTRY
+				let name = read_u32(self.data, entry_off)?;
+				let offset = read_u32(self.data, entry_off + 4)?;
+
+				let name = if name & 0x8000_0000 != 0 {
+					let str_off = rsrc_off + (name & 0x7fff_ffff) as usize;
+					let len = read_u16(self.data, str_off)? as usize;
+					let chars = self.data.get(str_off + 2..str_off + 2 + len * 2)
+						.ok_or(co::ERROR::BAD_EXE_FORMAT)?
+						.chunks_exact(2)
+						.map(|ch2| u16::from_le_bytes([ch2[0], ch2[1]]))
+						.collect::<Vec<_>>();
+					ResName::Str(String::from_utf16_lossy(&chars))
+				} else {
+					ResName::Id(name as _)
+				};
+
+				let target = if offset & 0x8000_0000 != 0 {
+					let sub_off = (offset & 0x7fff_ffff) as usize;
+					if sub_off <= dir_off {
+						return Err(co::ERROR::BAD_EXE_FORMAT); // prevent infinite loops
+					}
+					ResTarget::Dir(sub_off)
+				} else {
+					ResTarget::Data(offset as _)
+				};
+				Ok((name, target))
+			})
+			.collect()
+	}
+}
+
+//------------------------------------------------------------------------------
+
+enum ResName {
+	Id(u16),
+	Str(String),
+}
+
+enum ResTarget {
+	Dir(usize),
+	Data(usize),
+}
+
+fn eq_ignore_case(a: &WString, b: &WString) -> bool {
+	a.to_string().to_uppercase() == b.to_string().to_uppercase()
+}
+
+fn read_u16(data: &[u8], off: usize) -> SysResult<u16> {
+	data.get(off..off + 2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.ok_or(co::ERROR::BAD_EXE_FORMAT)
+}
+
+fn read_u32(data: &[u8], off: usize) -> SysResult<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		.ok_or(co::ERROR::BAD_EXE_FORMAT)
+}
+
+fn read_u64(data: &[u8], off: usize) -> SysResult<u64> {
+	Ok(MAKEQWORD(read_u32(data, off)?, read_u32(data, off + 4)?))
+}
+
+/// Reads a null-terminated ANSI string.
+fn read_cstr(data: &[u8], off: usize) -> SysResult<String> {
+	let bytes = data.get(off..).ok_or(co::ERROR::BAD_EXE_FORMAT)?;
+	let len = bytes.iter()
+		.position(|ch| *ch == 0x00)
+		.ok_or(co::ERROR::BAD_EXE_FORMAT)?;
+	Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+}
+
+/// Reads a plain struct, which must be entirely within the data.
+fn read_struct<T: Copy>(data: &[u8], off: usize) -> SysResult<T> {
+	data.get(off..off + std::mem::size_of::<T>())
+		.map(|b| unsafe { std::ptr::read_unaligned(b.as_ptr() as *const T) })
+		.ok_or(co::ERROR::BAD_EXE_FORMAT)
+}
+
+/// Reads a plain struct which may be truncated, zeroing the missing fields.
+fn read_struct_partial<T: Copy + Default>(data: &[u8]) -> T {
+	let mut obj = T::default();
+	let sz = data.len().min(std::mem::size_of::<T>());
+	unsafe {
+		std::ptr::copy_nonoverlapping(data.as_ptr(), &mut obj as *mut _ as *mut u8, sz);
+	}
+	obj
+}
@@ -0,0 +1,502 @@
+use crate::co;
+use crate::decl::*;
+
+/// Reads the keys and values of a
+/// [registry hive](https://learn.microsoft.com/en-us/windows/win32/sysinfo/registry-hives)
+/// file, like an offline `SYSTEM` or `NTUSER.DAT`, directly from its raw
+/// bytes, without loading it into the registry.
+///
+/// Files saved by [`HKEY::RegSaveKey`](crate::prelude::kernel_Hkey::RegSaveKey)
+/// are in this format, and they can be read without any privileges. Only the
+/// primary file is read, so changes still pending in the transaction logs of a
+/// [dirty](crate::RegistryHive::is_dirty) hive are not seen.
+///
+/// This is a pure Rust implementation, no system calls are made. Malformed
+/// data fails with [`co::ERROR::BADDB`](crate::co::ERROR::BADDB).
+///
+/// # Examples
+///
+/// Reading a value from an offline `SYSTEM` hive mapped in memory:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let f = w::FileMapped::open(
+///     "C:\\Temp\\SYSTEM",
+///     w::FileAccess::ExistingReadOnly,
+/// )?;
+/// let hive = w::RegistryHive::parse(f.as_slice())?;
+///
+/// let key = hive.root_key()
+///     .open_key("ControlSet001\\Control\\ComputerName\\ComputerName")?;
+///
+/// if let w::RegistryValue::Sz(name) = key.query_value(Some("ComputerName"))? {
+///     println!("Computer name: {}", name);
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+///
+/// Listing the keys and values at the root:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let raw_bytes: Vec<u8>; // initialized somewhere
+/// # let raw_bytes = Vec::<u8>::new();
+/// let hive = w::RegistryHive::parse(&raw_bytes)?;
+/// let root = hive.root_key();
+///
+/// for sub_key in root.enum_keys()? {
+///     let sub_key = sub_key?;
+///     println!("Key: {}", sub_key.name());
+/// }
+///
+/// for value_and_type in root.enum_values()? {
+///     let (value, reg_type) = value_and_type?;
+///     println!("Value: {}, {}", value, reg_type);
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct RegistryHive<'a> {
+	data: &'a [u8],
+	minor_version: u32,
+	root_off: u32,
+	root_nk: &'a [u8],
+}
+
+impl<'a> RegistryHive<'a> {
+	/// Parses the base block of the hive, and its root key. The other keys are
+	/// parsed only when requested.
+	#[must_use]
+	pub fn parse(data: &'a [u8]) -> SysResult<Self> {
+		if data.get(..4) != Some(b"regf")
+			|| data.get(HBINS_START..HBINS_START + 4) != Some(b"hbin")
+			|| read_u32(data, 0x14)? != 1 // major version
+		{
+			return Err(co::ERROR::BADDB);
+		}
+
+		let mut hive = Self {
+			data,
+			minor_version: read_u32(data, 0x18)?,
+			root_off: read_u32(data, 0x24)?,
+			root_nk: &[],
+		};
+		hive.root_nk = hive.key_cell(hive.root_off, None)?;
+		Ok(hive)
+	}
+
+	/// Returns the whole raw data of the hive.
+	#[must_use]
+	pub const fn as_slice(&self) -> &'a [u8] {
+		self.data
+	}
+
+	/// Returns the file name stored in the base block, which is usually a
+	/// partial path of the hive when it was saved.
+	#[must_use]
+	pub fn file_name(&self) -> String {
+		utf16_until_null(&self.data[0x30..0x70])
+	}
+
+	/// Tells whether the primary and secondary sequence numbers differ, which
+	/// means the hive was not cleanly written, and the most recent changes
+	/// are in its transaction logs.
+	#[must_use]
+	pub fn is_dirty(&self) -> bool {
+		self.data[0x04..0x08] != self.data[0x08..0x0c]
+	}
+
+	/// Returns the last time the hive was written.
+	#[must_use]
+	pub fn last_write_time(&self) -> FILETIME {
+		FILETIME::from_ticks(read_u64(self.data, 0x0c).unwrap_or_default())
+	}
+
+	/// Returns the root key of the hive.
+	#[must_use]
+	pub const fn root_key(&self) -> RegistryHiveKey<'a> {
+		RegistryHiveKey { hive: *self, off: self.root_off, nk: self.root_nk }
+	}
+
+	/// Returns the major and minor versions of the format.
+	#[must_use]
+	pub const fn version(&self) -> (u32, u32) {
+		(1, self.minor_version)
+	}
+
+	/// Returns the data of the cell at the given offset, relative to the
+	/// first hive bin.
+	fn cell(&self, off: u32) -> SysResult<&'a [u8]> {
+		let start = HBINS_START.checked_add(off as usize).ok_or(co::ERROR::BADDB)?;
+		let sz = (read_u32(self.data, start)? as i32).unsigned_abs() as usize; // allocated cells have negative sizes
+		start.checked_add(sz)
+			.filter(|_| sz >= 4)
+			.and_then(|end| self.data.get(start + 4..end))
+			.ok_or(co::ERROR::BADDB)
+	}
+
+	/// Returns the cell of a key node, validating its signature and name.
+	///
+	/// A sub key must point back to its parent, and cannot be the root key, so
+	/// the keys can't form a cycle.
+	fn key_cell(&self, off: u32, parent_off: Option<u32>) -> SysResult<&'a [u8]> {
+		let nk = self.cell(off)?;
+		let name_len = read_u16(nk, 0x48)? as usize;
+		if nk.get(..2) != Some(b"nk") || nk.len() < NK_NAME + name_len {
+			return Err(co::ERROR::BADDB);
+		}
+		if let Some(parent_off) = parent_off {
+			if off == self.root_off || read_u32(nk, 0x10)? != parent_off {
+				return Err(co::ERROR::BADDB);
+			}
+		}
+		Ok(nk)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A key of a [`RegistryHive`](crate::RegistryHive).
+///
+/// The methods mirror those of [`HKEY`](crate::HKEY): sub keys are enumerated
+/// with [`enum_keys`](crate::RegistryHiveKey::enum_keys) and opened with
+/// [`open_key`](crate::RegistryHiveKey::open_key); values are enumerated with
+/// [`enum_values`](crate::RegistryHiveKey::enum_values) and read with
+/// [`query_value`](crate::RegistryHiveKey::query_value). Names are compared
+/// case-insensitively, like the registry does.
+#[derive(Clone, Copy)]
+pub struct RegistryHiveKey<'a> {
+	hive: RegistryHive<'a>,
+	off: u32,
+	nk: &'a [u8],
+}
+
+impl<'a> RegistryHiveKey<'a> {
+	/// Returns the class name of the key, if any.
+	#[must_use]
+	pub fn class_name(&self) -> SysResult<Option<String>> {
+		let class_len = read_u16(self.nk, 0x4a)? as usize;
+		if class_len == 0 {
+			return Ok(None);
+		}
+		let class_cell = self.hive.cell(read_u32(self.nk, 0x30)?)?;
+		class_cell.get(..class_len)
+			.map(|raw| Some(utf16_until_null(raw)))
+			.ok_or(co::ERROR::BADDB)
+	}
+
+	/// Returns an iterator over the sub keys, like
+	/// [`HKEY::RegEnumKeyEx`](crate::prelude::kernel_Hkey::RegEnumKeyEx),
+	/// but yielding the keys themselves.
+	#[must_use]
+	pub fn enum_keys(&self,
+	) -> SysResult<impl Iterator<Item = SysResult<RegistryHiveKey<'a>>> + 'a>
+	{
+		let (hive, parent_off) = (self.hive, self.off);
+		Ok(self.sub_key_offsets()?
+			.into_iter()
+			.map(move |off| hive.key_cell(off, Some(parent_off))
+				.map(|nk| Self { hive, off, nk })))
+	}
+
+	/// Returns an iterator over the names and types of the values, like
+	/// [`HKEY::RegEnumValue`](crate::prelude::kernel_Hkey::RegEnumValue). The
+	/// default value, if present, has an empty name.
+	#[must_use]
+	pub fn enum_values(&self,
+	) -> SysResult<impl Iterator<Item = SysResult<(String, co::REG)>> + 'a>
+	{
+		let hive = self.hive;
+		Ok(self.value_offsets()?
+			.into_iter()
+			.map(move |off| {
+				let vk = hive.cell(off).and_then(value_cell)?;
+				Ok((value_name_of(vk), value_type(vk)))
+			}))
+	}
+
+	/// Returns the last time the key, or any of its values, was written.
+	#[must_use]
+	pub fn last_write_time(&self) -> FILETIME {
+		FILETIME::from_ticks(read_u64(self.nk, 0x04).unwrap_or_default())
+	}
+
+	/// Returns the name of the key.
+	#[must_use]
+	pub fn name(&self) -> String {
+		let name_len = read_u16(self.nk, 0x48).unwrap_or_default() as usize;
+		let raw = &self.nk[NK_NAME..NK_NAME + name_len];
+		if read_u16(self.nk, 0x02).unwrap_or_default() & KEY_COMP_NAME != 0 {
+			latin1(raw)
+		} else {
+			utf16_until_null(raw)
+		}
+	}
+
+	/// Returns the number of sub keys.
+	#[must_use]
+	pub fn num_sub_keys(&self) -> u32 {
+		read_u32(self.nk, 0x14).unwrap_or_default()
+	}
+
+	/// Returns the number of values.
+	#[must_use]
+	pub fn num_values(&self) -> u32 {
+		read_u32(self.nk, 0x24).unwrap_or_default()
+	}
+
+	/// Opens a sub key, like
+	/// [`HKEY::RegOpenKeyEx`](crate::prelude::kernel_Hkey::RegOpenKeyEx). The
+	/// path may have many levels, separated by backslashes.
+	///
+	/// Fails with
+	/// [`co::ERROR::FILE_NOT_FOUND`](crate::co::ERROR::FILE_NOT_FOUND) if the
+	/// key doesn't exist.
+	#[must_use]
+	pub fn open_key(&self, sub_key: &str) -> SysResult<RegistryHiveKey<'a>> {
+		let mut key = *self;
+		for level in sub_key.split('\\').filter(|level| !level.is_empty()) {
+			key = key.enum_keys()?
+				.find(|sub_key| match sub_key {
+					Ok(sub_key) => eq_ignore_case(&sub_key.name(), level),
+					Err(_) => true, // stop at the first error
+				})
+				.ok_or(co::ERROR::FILE_NOT_FOUND)??;
+		}
+		Ok(key)
+	}
+
+	/// Returns the value with the given name, like
+	/// [`HKEY::RegQueryValueEx`](crate::prelude::kernel_Hkey::RegQueryValueEx).
+	/// If `value_name` is `None`, the default value is returned.
+	///
+	/// Value types not supported by [`RegistryValue`](crate::RegistryValue),
+	/// and numbers whose data is too short, are returned as
+	/// [`RegistryValue::None`](crate::RegistryValue::None); their data can be
+	/// read with
+	/// [`query_value_raw`](crate::RegistryHiveKey::query_value_raw).
+	///
+	/// Fails with
+	/// [`co::ERROR::FILE_NOT_FOUND`](crate::co::ERROR::FILE_NOT_FOUND) if the
+	/// value doesn't exist.
+	#[must_use]
+	pub fn query_value(&self, value_name: Option<&str>) -> SysResult<RegistryValue> {
+		let (reg_type, data) = self.query_value_raw(value_name)?;
+		Ok(match reg_type {
+			co::REG::DWORD if data.len() >= 4 => RegistryValue::Dword(read_u32(&data, 0)?),
+			co::REG::QWORD if data.len() >= 8 => RegistryValue::Qword(read_u64(&data, 0)?),
+			co::REG::SZ => RegistryValue::Sz(utf16_until_null(&data)),
+			co::REG::EXPAND_SZ => RegistryValue::ExpandSz(utf16_until_null(&data)),
+			co::REG::MULTI_SZ => RegistryValue::MultiSz(
+				wchars_of(&data)
+					.split(|ch| *ch == 0x0000)
+					.take_while(|s| !s.is_empty())
+					.map(String::from_utf16_lossy)
+					.collect(),
+			),
+			co::REG::BINARY => RegistryValue::Binary(data),
+			_ => RegistryValue::None, // REG_NONE, other types, and truncated numbers
+		})
+	}
+
+	/// Returns the type and the raw data of the value with the given name. If
+	/// `value_name` is `None`, the default value is returned.
+	///
+	/// Fails with
+	/// [`co::ERROR::FILE_NOT_FOUND`](crate::co::ERROR::FILE_NOT_FOUND) if the
+	/// value doesn't exist.
+	#[must_use]
+	pub fn query_value_raw(&self,
+		value_name: Option<&str>,
+	) -> SysResult<(co::REG, Vec<u8>)>
+	{
+		let value_name = value_name.unwrap_or("");
+		for off in self.value_offsets()? {
+			let vk = value_cell(self.hive.cell(off)?)?;
+			if eq_ignore_case(&value_name_of(vk), value_name) {
+				return Ok((value_type(vk), self.value_data(vk)?));
+			}
+		}
+		Err(co::ERROR::FILE_NOT_FOUND)
+	}
+
+	/// Returns the security descriptor of the key, in self-relative format.
+	#[must_use]
+	pub fn security_descriptor(&self) -> SysResult<&'a [u8]> {
+		let sk = self.hive.cell(read_u32(self.nk, 0x2c)?)?;
+		if sk.get(..2) != Some(b"sk") {
+			return Err(co::ERROR::BADDB);
+		}
+		let sd_sz = read_u32(sk, 0x10)? as usize;
+		sk.get(0x14..0x14 + sd_sz).ok_or(co::ERROR::BADDB)
+	}
+
+	/// Returns the offsets of the key nodes of all sub keys, gathered from the
+	/// subkeys list, which may be split into many lists by an index root.
+	fn sub_key_offsets(&self) -> SysResult<Vec<u32>> {
+		let num_sub_keys = self.num_sub_keys() as usize;
+		if num_sub_keys == 0 {
+			return Ok(Vec::new());
+		} else if num_sub_keys > self.hive.data.len() / NK_NAME { // each one needs a key node
+			return Err(co::ERROR::BADDB);
+		}
+
+		let mut offsets = Vec::<u32>::with_capacity(num_sub_keys);
+		let list = self.hive.cell(read_u32(self.nk, 0x1c)?)?;
+		if list.get(..2) == Some(b"ri") {
+			for idx in 0..read_u16(list, 2)? as usize {
+				let sub_list = self.hive.cell(read_u32(list, 4 + idx * 4)?)?;
+				push_sub_key_offsets(sub_list, &mut offsets)?;
+				if offsets.len() > num_sub_keys {
+					return Err(co::ERROR::BADDB);
+				}
+			}
+		} else {
+			push_sub_key_offsets(list, &mut offsets)?;
+		}
+
+		if offsets.len() != num_sub_keys {
+			return Err(co::ERROR::BADDB);
+		}
+		Ok(offsets)
+	}
+
+	/// Returns the offsets of the value cells.
+	fn value_offsets(&self) -> SysResult<Vec<u32>> {
+		let num_values = self.num_values() as usize;
+		if num_values == 0 {
+			return Ok(Vec::new());
+		}
+		let list = self.hive.cell(read_u32(self.nk, 0x28)?)?;
+		(0..num_values)
+			.map(|idx| read_u32(list, idx * 4))
+			.collect()
+	}
+
+	/// Returns the data of a value, which can be stored in the value cell
+	/// itself, in a data cell, or split into segments of a big data cell.
+	fn value_data(&self, vk: &[u8]) -> SysResult<Vec<u8>> {
+		let raw_sz = read_u32(vk, 0x04)?;
+		if raw_sz & DATA_IN_OFFSET != 0 { // up to 4 bytes, stored in the offset field
+			let sz = (raw_sz & !DATA_IN_OFFSET) as usize;
+			return vk.get(0x08..0x08 + sz)
+				.filter(|_| sz <= 4)
+				.map(|data| data.to_vec())
+				.ok_or(co::ERROR::BADDB);
+		}
+
+		let sz = raw_sz as usize;
+		if sz == 0 {
+			return Ok(Vec::new());
+		}
+		let cell = self.hive.cell(read_u32(vk, 0x08)?)?;
+
+		if self.hive.minor_version >= 4 && sz > BIG_DATA_SEGMENT && cell.get(..2) == Some(b"db") {
+			let num_segments = read_u16(cell, 0x02)? as usize;
+			if num_segments > sz.div_ceil(BIG_DATA_SEGMENT) {
+				return Err(co::ERROR::BADDB);
+			}
+			let segments = self.hive.cell(read_u32(cell, 0x04)?)?;
+			let mut data = Vec::<u8>::new(); // sz is not trusted, grow as segments are read
+			for idx in 0..num_segments {
+				let segment = self.hive.cell(read_u32(segments, idx * 4)?)?;
+				let seg_sz = (sz - data.len()).min(BIG_DATA_SEGMENT);
+				data.extend_from_slice(segment.get(..seg_sz).ok_or(co::ERROR::BADDB)?);
+			}
+			if data.len() != sz {
+				return Err(co::ERROR::BADDB);
+			}
+			Ok(data)
+		} else {
+			cell.get(..sz)
+				.map(|data| data.to_vec())
+				.ok_or(co::ERROR::BADDB)
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+const HBINS_START: usize = 0x1000;
+const NK_NAME: usize = 0x4c;
+const VK_NAME: usize = 0x14;
+const KEY_COMP_NAME: u16 = 0x0020;
+const VALUE_COMP_NAME: u16 = 0x0001;
+const DATA_IN_OFFSET: u32 = 0x8000_0000;
+const BIG_DATA_SEGMENT: usize = 16344;
+
+/// Appends the offsets of a leaf subkeys list: `li`, `lf` or `lh`.
+fn push_sub_key_offsets(list: &[u8], offsets: &mut Vec<u32>) -> SysResult<()> {
+	let stride = match list.get(..2) {
+		Some(b"li") => 4, // offset only
+		Some(b"lf") | Some(b"lh") => 8, // offset and name hint or hash
+		_ => return Err(co::ERROR::BADDB),
+	};
+	for idx in 0..read_u16(list, 2)? as usize {
+		offsets.push(read_u32(list, 4 + idx * stride)?);
+	}
+	Ok(())
+}
+
+/// Validates the signature and the name of a value cell.
+fn value_cell(vk: &[u8]) -> SysResult<&[u8]> {
+	let name_len = read_u16(vk, 0x02)? as usize;
+	if vk.get(..2) != Some(b"vk") || vk.len() < VK_NAME + name_len {
+		return Err(co::ERROR::BADDB);
+	}
+	Ok(vk)
+}
+
+fn value_name_of(vk: &[u8]) -> String {
+	let name_len = read_u16(vk, 0x02).unwrap_or_default() as usize;
+	let raw = &vk[VK_NAME..VK_NAME + name_len];
+	if read_u16(vk, 0x10).unwrap_or_default() & VALUE_COMP_NAME != 0 {
+		latin1(raw)
+	} else {
+		utf16_until_null(raw)
+	}
+}
+
+fn value_type(vk: &[u8]) -> co::REG {
+	unsafe { co::REG::from_raw(read_u32(vk, 0x0c).unwrap_or_default()) }
+}
+
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+	a.to_uppercase() == b.to_uppercase()
+}
+
+/// Decodes a compressed name, which has one byte per character.
+fn latin1(raw: &[u8]) -> String {
+	raw.iter().map(|ch| *ch as char).collect()
+}
+
+fn wchars_of(raw: &[u8]) -> Vec<u16> {
+	raw.chunks_exact(2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.collect()
+}
+
+/// Decodes an UTF-16 string up to the first null, if any.
+fn utf16_until_null(raw: &[u8]) -> String {
+	let wchars = wchars_of(raw);
+	let len = wchars.iter().position(|ch| *ch == 0x0000).unwrap_or(wchars.len());
+	String::from_utf16_lossy(&wchars[..len])
+}
+
+fn read_u16(data: &[u8], off: usize) -> SysResult<u16> {
+	data.get(off..off + 2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.ok_or(co::ERROR::BADDB)
+}
+
+fn read_u32(data: &[u8], off: usize) -> SysResult<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		.ok_or(co::ERROR::BADDB)
+}
+
+fn read_u64(data: &[u8], off: usize) -> SysResult<u64> {
+	Ok(MAKEQWORD(read_u32(data, off)?, read_u32(data, off + 4)?))
+}
@@ -0,0 +1,314 @@
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+
+use crate::decl::*;
+use crate::prelude::*;
+
+/// Builds a `&'static` [`WStr`](crate::WStr) from a string literal. The UTF-16
+/// conversion is performed at compile time, so no allocation is ever made.
+///
+/// The literal cannot contain null characters, otherwise compilation fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let hwnd: w::HWND; // initialized somewhere
+/// # let hwnd = w::HWND::NULL;
+///
+/// hwnd.SetWindowText(w::w!("Hello"))?;
+/// # Ok::<_, co::ERROR>(())
+/// ```
+#[macro_export]
+macro_rules! w {
+	($s:literal) => {{
+		const __W_LEN: usize = $crate::WStr::__utf16_len($s) + 1; // plus terminating null
+		const __W_BUF: [u16; __W_LEN] = $crate::WStr::__encode_utf16::<__W_LEN>($s);
+		const __W_STR: &$crate::WStr = unsafe {
+			$crate::WStr::from_wchars_with_nul_unchecked(&__W_BUF)
+		};
+		__W_STR
+	}};
+}
+
+/// A borrowed, null-terminated
+/// [Unicode UTF-16](https://learn.microsoft.com/en-us/windows/win32/intl/unicode-in-the-windows-api)
+/// wide string slice, which is to [`WString`](crate::WString) what
+/// [`CStr`](std::ffi::CStr) is to [`CString`](std::ffi::CString).
+///
+/// A `WStr` can be created at compile time with the [`w!`](crate::w) macro,
+/// and a [`WString`](crate::WString) derefs to it.
+#[repr(transparent)]
+pub struct WStr {
+	inner: [u16], // always ends with a terminating null
+}
+
+impl std::fmt::Display for WStr {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		std::fmt::Display::fmt(&self.to_string_lossy(), f)
+	}
+}
+impl std::fmt::Debug for WStr {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		std::fmt::Debug::fmt(&self.to_string_lossy(), f)
+	}
+}
+
+impl std::cmp::PartialEq for WStr {
+	fn eq(&self, other: &Self) -> bool {
+		self.as_wchars() == other.as_wchars()
+	}
+}
+impl std::cmp::Eq for WStr {}
+
+impl std::cmp::PartialOrd for WStr {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl std::cmp::Ord for WStr {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.as_wchars().cmp(other.as_wchars())
+	}
+}
+
+impl std::hash::Hash for WStr {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.as_wchars().hash(state);
+	}
+}
+
+impl Default for &WStr {
+	fn default() -> Self {
+		WStr::EMPTY
+	}
+}
+
+impl AsRef<WStr> for WStr {
+	fn as_ref(&self) -> &WStr {
+		self
+	}
+}
+
+impl ToOwned for WStr {
+	type Owned = WString;
+
+	fn to_owned(&self) -> Self::Owned {
+		WString::from_wchars_slice(self.as_wchars())
+	}
+}
+
+impl WStr {
+	/// An empty string, which contains only the terminating null.
+	pub const EMPTY: &'static Self = unsafe {
+		Self::from_wchars_with_nul_unchecked(&[0x0000])
+	};
+
+	/// Wraps a slice which ends with a terminating null, and has no other null
+	/// before it. Otherwise, returns `None`.
+	#[must_use]
+	pub fn from_wchars_with_nul(src: &[u16]) -> Option<&Self> {
+		match src.iter().position(|ch| *ch == 0x0000) {
+			Some(idx) if idx == src.len() - 1 =>
+				Some(unsafe { Self::from_wchars_with_nul_unchecked(src) }),
+			_ => None,
+		}
+	}
+
+	/// Wraps a slice up to its first terminating null, which is included.
+	/// Returns `None` if the slice has no null.
+	#[must_use]
+	pub fn from_wchars_until_nul(src: &[u16]) -> Option<&Self> {
+		src.iter()
+			.position(|ch| *ch == 0x0000)
+			.map(|idx| unsafe { Self::from_wchars_with_nul_unchecked(&src[..=idx]) })
+	}
+
+	/// Wraps a slice without any checks.
+	///
+	/// # Safety
+	///
+	/// The slice must end with a terminating null, and must not have any other
+	/// null before it.
+	#[must_use]
+	pub const unsafe fn from_wchars_with_nul_unchecked(src: &[u16]) -> &Self {
+		unsafe { &*(src as *const [u16] as *const Self) }
+	}
+
+	/// Returns a
+	/// [`LPCWSTR`](https://learn.microsoft.com/en-us/windows/win32/learnwin32/working-with-strings)
+	/// pointer to the string, to be passed to native Win32 functions.
+	///
+	/// The pointer is never null.
+	#[must_use]
+	pub const fn as_ptr(&self) -> *const u16 {
+		self.inner.as_ptr()
+	}
+
+	/// Returns the string characters, without the terminating null.
+	#[must_use]
+	pub fn as_wchars(&self) -> &[u16] {
+		&self.inner[..self.inner.len() - 1]
+	}
+
+	/// Returns the string characters, including the terminating null.
+	#[must_use]
+	pub const fn as_wchars_with_nul(&self) -> &[u16] {
+		&self.inner
+	}
+
+	/// Returns `true` if the string has no characters.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.inner.len() == 1
+	}
+
+	/// Returns the number of [`u16`] characters, not counting the terminating
+	/// null.
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.inner.len() - 1
+	}
+
+	/// Converts into [`String`](std::string::String) by calling
+	/// [`String::from_utf16`](std::string::String::from_utf16).
+	#[must_use]
+	pub fn to_string_checked(&self,
+	) -> Result<String, std::string::FromUtf16Error>
+	{
+		String::from_utf16(self.as_wchars())
+	}
+
+	/// Converts into [`String`](std::string::String) by calling
+	/// [`String::from_utf16_lossy`](std::string::String::from_utf16_lossy),
+	/// which replaces invalid characters with
+	/// [`U+FFFD`](std::char::REPLACEMENT_CHARACTER).
+	#[must_use]
+	pub fn to_string_lossy(&self) -> String {
+		String::from_utf16_lossy(self.as_wchars())
+	}
+
+	#[doc(hidden)]
+	#[must_use]
+	pub const fn __utf16_len(s: &str) -> usize {
+		let bytes = s.as_bytes();
+		let mut idx = 0;
+		let mut num_wchars = 0;
+		while idx < bytes.len() {
+			let (ch, ch_len) = decode_utf8(bytes, idx);
+			num_wchars += if ch >= 0x1_0000 { 2 } else { 1 }; // surrogate pair
+			idx += ch_len;
+		}
+		num_wchars
+	}
+
+	#[doc(hidden)]
+	#[must_use]
+	pub const fn __encode_utf16<const N: usize>(s: &str) -> [u16; N] {
+		let bytes = s.as_bytes();
+		let mut buf = [0x0000; N];
+		let mut idx = 0;
+		let mut dest = 0;
+		while idx < bytes.len() {
+			let (ch, ch_len) = decode_utf8(bytes, idx);
+			if ch == 0 {
+				panic!("String literal cannot contain null characters.");
+			} else if ch >= 0x1_0000 {
+				let ch = ch - 0x1_0000;
+				buf[dest] = (0xd800 | (ch >> 10)) as _;
+				buf[dest + 1] = (0xdc00 | (ch & 0x3ff)) as _;
+				dest += 2;
+			} else {
+				buf[dest] = ch as _;
+				dest += 1;
+			}
+			idx += ch_len;
+		}
+		buf
+	}
+}
+
+/// Decodes the UTF-8 char at the given index, which is assumed valid because
+/// it comes from a `&str`. Returns the code point and its length in bytes.
+const fn decode_utf8(bytes: &[u8], idx: usize) -> (u32, usize) {
+	let b0 = bytes[idx] as u32;
+	if b0 < 0x80 {
+		(b0, 1)
+	} else if b0 < 0xe0 {
+		((b0 & 0x1f) << 6
+			| (bytes[idx + 1] as u32 & 0x3f), 2)
+	} else if b0 < 0xf0 {
+		((b0 & 0x0f) << 12
+			| (bytes[idx + 1] as u32 & 0x3f) << 6
+			| (bytes[idx + 2] as u32 & 0x3f), 3)
+	} else {
+		((b0 & 0x07) << 18
+			| (bytes[idx + 1] as u32 & 0x3f) << 12
+			| (bytes[idx + 2] as u32 & 0x3f) << 6
+			| (bytes[idx + 3] as u32 & 0x3f), 4)
+	}
+}
+
+//------------------------------------------------------------------------------
+
+impl std::ops::Deref for WString {
+	type Target = WStr;
+
+	/// Returns the string up to the first terminating null. An unallocated
+	/// buffer, or a buffer without any null, derefs to an empty string.
+	fn deref(&self) -> &Self::Target {
+		WStr::from_wchars_until_nul(self.as_slice())
+			.unwrap_or(WStr::EMPTY)
+	}
+}
+
+impl Borrow<WStr> for WString {
+	fn borrow(&self) -> &WStr {
+		self
+	}
+}
+
+impl AsRef<WStr> for WString {
+	fn as_ref(&self) -> &WStr {
+		self
+	}
+}
+
+impl From<&WStr> for WString {
+	fn from(s: &WStr) -> Self {
+		s.to_owned()
+	}
+}
+
+//------------------------------------------------------------------------------
+
+impl ToWStr for str {
+	fn to_wstr(&self) -> Cow<'_, WStr> {
+		Cow::Owned(WString::from_str(self))
+	}
+}
+
+impl ToWStr for String {
+	fn to_wstr(&self) -> Cow<'_, WStr> {
+		self.as_str().to_wstr()
+	}
+}
+
+impl ToWStr for WStr {
+	fn to_wstr(&self) -> Cow<'_, WStr> {
+		Cow::Borrowed(self)
+	}
+}
+
+impl ToWStr for WString {
+	fn to_wstr(&self) -> Cow<'_, WStr> {
+		Cow::Borrowed(self)
+	}
+}
+
+impl<T: ?Sized + ToWStr> ToWStr for &T {
+	fn to_wstr(&self) -> Cow<'_, WStr> {
+		(**self).to_wstr()
+	}
+}
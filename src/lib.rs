@@ -2,7 +2,7 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
 // Declarations of macros used throughout the library.
-// No macros are public.
+// The only public macro is w!, declared along with WStr.
 
 #[macro_use] mod macros;
 
@@ -90,6 +90,7 @@ pub mod guard {
 	#[cfg(feature = "gdi")] pub use super::gdi::guard::*;
 	#[cfg(feature = "kernel")] pub use super::kernel::guard::*;
 	#[cfg(feature = "ole")] pub use super::ole::guard::*;
+	#[cfg(feature = "oleaut")] pub use super::oleaut::guard::*;
 	#[cfg(feature = "shell")] pub use super::shell::guard::*;
 	#[cfg(feature = "user")] pub use super::user::guard::*;
 	#[cfg(feature = "uxtheme")] pub use super::uxtheme::guard::*;
@@ -131,6 +132,23 @@ pub mod msg {
 	//!
 	//! Messages are organized into modules according to their prefixes:
 	//! [`wm`] (window messages), [`lvm`] (list view messages), and so on.
+	//!
+	//! Messages which carry a text accept a [`WString`](crate::WString) or a
+	//! borrowed [`WStr`](crate::WStr), like one built with the
+	//! [`w!`](crate::w) macro, which avoids an allocation for each message:
+	//!
+	//! ```rust,ignore
+	//! use winsafe::{self as w, prelude::*, msg};
+	//!
+	//! let hlistbox: w::HWND; // initialized somewhere
+	//! # let hlistbox = w::HWND::NULL;
+	//!
+	//! hlistbox.SendMessage(
+	//!     msg::lb::AddString {
+	//!         text: w::w!("First item"),
+	//!     },
+	//! ).expect("Failed to add item.");
+	//! ```
 
 	pub use super::user::messages::WndMsg;
 
@@ -96,6 +96,24 @@ macro_rules! const_guid {
 				std::fmt::Display::fmt(&self.0, f)
 			}
 		}
+		impl std::fmt::LowerHex for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				std::fmt::LowerHex::fmt(&self.0, f)
+			}
+		}
+		impl std::fmt::UpperHex for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				std::fmt::UpperHex::fmt(&self.0, f)
+			}
+		}
+
+		impl std::str::FromStr for $name {
+			type Err = crate::co::ERROR;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				s.parse::<crate::kernel::decl::GUID>().map(Self)
+			}
+		}
 
 		impl $name {
 			/// Creates a new `GUID` from a representative hex string, which can
@@ -1,6 +1,11 @@
 #![allow(unused_macros)]
 
-/// Writes `pub(crate)` and `pub` values of the given constant type.
+/// Writes `pub(crate)` and `pub` values of the given constant type, along with
+/// the table of public value names.
+///
+/// The `in` form is used when the values are declared outside the constant
+/// type itself, usually in another feature; the table must then be listed in
+/// the declaration of the type.
 macro_rules! const_values {
 	(
 		$name:ident;
@@ -13,6 +18,32 @@ macro_rules! const_values {
 			$( #[$pubvaldoc:meta] )*
 			$pubvalname:ident $pubval:expr
 		)*
+	) => {
+		const_values! {
+			$name in __NAMES;
+			$(
+				$( #[$privvaldoc] )*
+				$privvalname $privval
+			)*
+			=>
+			$(
+				$( #[$pubvaldoc] )*
+				$pubvalname $pubval
+			)*
+		}
+	};
+
+	(
+		$name:ident in $table:ident;
+		$(
+			$( #[$privvaldoc:meta] )*
+			$privvalname:ident $privval:expr
+		)*
+		=>
+		$(
+			$( #[$pubvaldoc:meta] )*
+			$pubvalname:ident $pubval:expr
+		)*
 	) => {
 		impl $name {
 			$(
@@ -23,15 +54,22 @@ macro_rules! const_values {
 				$( #[$pubvaldoc] )*
 				pub const $pubvalname: Self = unsafe { Self::from_raw($pubval) };
 			)*
+
+			pub(crate) const $table: &'static [(&'static str, Self)] = &[
+				$( (stringify!($pubvalname), Self::$pubvalname), )*
+			];
 		}
 	};
 }
 
 /// Declares the type of a constant, along with private and public values. Won't
 /// include `Debug` and `Display` impls.
+///
+/// Name tables of values declared elsewhere, with the `in` form of
+/// `const_values!`, are listed after the underlying type.
 macro_rules! const_no_debug_display {
 	(
-		$name:ident : $ntype:ty;
+		$name:ident : $ntype:ty $( , $( #[$extcfg:meta] )* $ext:ident )*;
 		$( #[$doc:meta] )*
 	) => {
 		$( #[$doc] )*
@@ -41,7 +79,13 @@ macro_rules! const_no_debug_display {
 
 		impl_intunderlying!($name, $ntype);
 
-		impl crate::prelude::NativeConst for $name {}
+		impl crate::prelude::NativeConst for $name {
+			const TYPE_NAME: &'static str = stringify!($name);
+
+			fn name_tables() -> &'static [&'static [(&'static str, Self)]] {
+				&[Self::__NAMES, $( $( #[$extcfg] )* Self::$ext, )*]
+			}
+		}
 
 		impl std::fmt::LowerHex for $name {
 			fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -70,7 +114,7 @@ macro_rules! const_no_debug_display {
 /// values.
 macro_rules! const_ordinary {
 	(
-		$name:ident : $ntype:ty;
+		$name:ident : $ntype:ty $( , $( #[$extcfg:meta] )* $ext:ident )*;
 		$( #[$doc:meta] )*
 		=>
 		$(
@@ -84,7 +128,7 @@ macro_rules! const_ordinary {
 		)*
 	) => {
 		const_no_debug_display! {
-			$name: $ntype;
+			$name: $ntype $( , $( #[$extcfg] )* $ext )*;
 			$( #[$doc] )*
 		}
 
@@ -95,7 +139,9 @@ macro_rules! const_ordinary {
 		}
 		impl std::fmt::Debug for $name {
 			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-				if self.0 as usize > 0xffff {
+				if let Some(val_name) = crate::prelude::NativeConst::name(self) {
+					write!(f, "{}::{}", stringify!($name), val_name)
+				} else if self.0 as usize > 0xffff {
 					write!(f, "[{:#010x} {}] {}",
 						self.0, self.0, stringify!($name))
 				} else {
@@ -124,7 +170,7 @@ macro_rules! const_ordinary {
 /// public values.
 macro_rules! const_bitflag {
 	(
-		$name:ident : $ntype:ty;
+		$name:ident : $ntype:ty $( , $( #[$extcfg:meta] )* $ext:ident )*;
 		$( #[$doc:meta] )*
 		=>
 		$(
@@ -137,13 +183,16 @@ macro_rules! const_bitflag {
 			$pubvalname:ident $pubval:expr
 		)*
 	) => {
-		const_ordinary! {
-			$name: $ntype;
+		const_no_debug_display! {
+			$name: $ntype $( , $( #[$extcfg] )* $ext )*;
 			$( #[$doc] )*
 			///
 			/// This is a bitflag constant, which implements the
 			/// [`NativeBitflag`](crate::prelude::NativeBitflag) trait.
-			=>
+		}
+
+		const_values! {
+			$name;
 			$(
 				$( #[$privvaldoc] )*
 				$privvalname $privval
@@ -161,6 +210,59 @@ macro_rules! const_bitflag {
 			}
 		}
 
+		impl std::fmt::Display for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				use crate::prelude::{NativeBitflag, NativeConst};
+				if self.0 == 0 {
+					return match self.name() {
+						Some(val_name) => write!(f, "{}::{}", stringify!($name), val_name),
+						None => write!(f, "0x0"),
+					};
+				}
+				for (idx, flag) in self.iter_flags().enumerate() {
+					if idx > 0 {
+						write!(f, " | ")?;
+					}
+					match flag.name() {
+						Some(val_name) => write!(f, "{}::{}", stringify!($name), val_name)?,
+						None => write!(f, "{:#x}", flag.0)?,
+					}
+				}
+				Ok(())
+			}
+		}
+		impl std::fmt::Debug for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				std::fmt::Display::fmt(self, f)
+			}
+		}
+
+		impl std::str::FromStr for $name {
+			type Err = crate::co::ERROR;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				let mut val = Self(0);
+				for part in s.split('|').map(|part| part.trim()) {
+					let num = if let Some(hex) = part.strip_prefix("0x")
+						.or_else(|| part.strip_prefix("0X"))
+					{
+						// Parsed as unsigned, so signed types accept all their bits.
+						u64::from_str_radix(hex, 16).ok()
+							.filter(|num| num.checked_shr(<$ntype>::BITS).unwrap_or(0) == 0)
+							.map(|num| num as $ntype)
+					} else {
+						part.parse::<$ntype>().ok()
+					};
+					val.0 |= match num {
+						Some(num) => num,
+						None => <Self as crate::prelude::NativeConst>::from_name(part)
+							.ok_or(crate::co::ERROR::INVALID_DATA)?.0,
+					};
+				}
+				Ok(val)
+			}
+		}
+
 		// Bitflag operations.
 		impl std::ops::BitAnd for $name {
 			type Output = $name;
@@ -208,7 +310,7 @@ macro_rules! const_bitflag {
 /// [`WM`](crate::co::WM) constant type, along with private and public values.
 macro_rules! const_wm {
 	(
-		$name:ident;
+		$name:ident $( , $( #[$extcfg:meta] )* $ext:ident )*;
 		$( #[$doc:meta] )*
 		=>
 		$(
@@ -222,7 +324,7 @@ macro_rules! const_wm {
 		)*
 	) => {
 		const_ordinary! {
-			$name: u32;
+			$name: u32 $( , $( #[$extcfg] )* $ext )*;
 			$( #[$doc] )*
 			///
 			/// This is a window message, convertible to [`WM`](crate::co::WM).
@@ -3,6 +3,7 @@
 use crate::co;
 use crate::decl::*;
 use crate::kernel::ffi_types::*;
+use crate::ole::com_impl::*;
 use crate::ole::privs::*;
 use crate::prelude::*;
 use crate::vt::*;
@@ -11,8 +12,8 @@ use crate::vt::*;
 #[repr(C)]
 pub struct IMFAsyncCallbackVT {
 	pub IUnknownVT: IUnknownVT,
-	pub GetParameters: fn(COMPTR, *mut u32, *mut u32) -> HRES,
-	pub Invoke: fn(COMPTR, COMPTR) -> HRES,
+	pub GetParameters: extern "system" fn(COMPTR, *mut u32, *mut u32) -> HRES,
+	pub Invoke: extern "system" fn(COMPTR, COMPTR) -> HRES,
 }
 
 com_interface! { IMFAsyncCallback: "a27003cf-2354-4f2a-8d6a-ab7cff15437e";
@@ -27,6 +28,72 @@ com_interface! { IMFAsyncCallback: "a27003cf-2354-4f2a-8d6a-ab7cff15437e";
 
 impl mf_IMFAsyncCallback for IMFAsyncCallback {}
 
+impl IMFAsyncCallback {
+	/// Creates a new `IMFAsyncCallback` COM object, whose methods are
+	/// implemented by the given Rust object, which is dropped when the last
+	/// reference to the COM object is released.
+	///
+	/// Since Media Foundation calls the methods from its work queue threads,
+	/// the Rust object must be [`Send`](std::marker::Send) and
+	/// [`Sync`](std::marker::Sync).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// struct MyCallback;
+	///
+	/// impl w::prelude::mf_IMFAsyncCallbackImpl for MyCallback {
+	///     fn Invoke(&self, async_result: &w::IMFAsyncResult) -> w::HrResult<()> {
+	///         if async_result.GetStatus() != co::HRESULT::S_OK {
+	///             println!("Failed.");
+	///         } else {
+	///             println!("Done.");
+	///         }
+	///         Ok(())
+	///     }
+	/// }
+	///
+	/// let callback = w::IMFAsyncCallback::new_impl(MyCallback);
+	/// ```
+	#[must_use]
+	pub fn new_impl<T>(obj: T) -> Self
+		where T: mf_IMFAsyncCallbackImpl,
+	{
+		let vt = IMFAsyncCallbackVT {
+			IUnknownVT: ComImpl::<IMFAsyncCallbackVT, T>::iunknown_vt(),
+			GetParameters: get_parameters::<T>,
+			Invoke: invoke::<T>,
+		};
+		let ptr = ComImpl::new_ptr(vt, &[IUnknown::IID, IMFAsyncCallback::IID], obj);
+		unsafe { Self::from_ptr(ptr) }
+	}
+}
+
+extern "system" fn get_parameters<T>(p: COMPTR, flags: *mut u32, queue: *mut u32) -> HRES
+	where T: mf_IMFAsyncCallbackImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IMFAsyncCallbackVT, T>::obj(p) };
+		let (new_flags, new_queue) = obj.GetParameters()?;
+		unsafe {
+			*flags = new_flags.raw();
+			*queue = new_queue;
+		}
+		Ok(())
+	})
+}
+
+extern "system" fn invoke<T>(p: COMPTR, async_result: COMPTR) -> HRES
+	where T: mf_IMFAsyncCallbackImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IMFAsyncCallbackVT, T>::obj(p) };
+		obj.Invoke(&borrowed(async_result))
+	})
+}
+
 /// This trait is enabled with the `mf` feature, and provides methods for
 /// [`IMFAsyncCallback`](crate::IMFAsyncCallback).
 ///
@@ -67,3 +134,30 @@ pub trait mf_IMFAsyncCallback: ole_IUnknown {
 		)
 	}
 }
+
+/// This trait is enabled with the `mf` feature, and is implemented by Rust
+/// objects which back an [`IMFAsyncCallback`](crate::IMFAsyncCallback) COM
+/// object, created with
+/// [`IMFAsyncCallback::new_impl`](crate::IMFAsyncCallback::new_impl).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait mf_IMFAsyncCallbackImpl: Send + Sync + 'static {
+	/// [`IMFAsyncCallback::GetParameters`](https://learn.microsoft.com/en-us/windows/win32/api/mfobjects/nf-mfobjects-imfasynccallback-getparameters)
+	/// method.
+	///
+	/// Returns the flag and the ID of the work queue. The default
+	/// implementation returns
+	/// [`co::HRESULT::E_NOTIMPL`](crate::co::HRESULT::E_NOTIMPL), so the
+	/// default values are used.
+	fn GetParameters(&self) -> HrResult<(co::MFASYNC, u32)> {
+		Err(co::HRESULT::E_NOTIMPL)
+	}
+
+	/// [`IMFAsyncCallback::Invoke`](https://learn.microsoft.com/en-us/windows/win32/api/mfobjects/nf-mfobjects-imfasynccallback-invoke)
+	/// method.
+	fn Invoke(&self, async_result: &IMFAsyncResult) -> HrResult<()>;
+}
@@ -31,7 +31,7 @@ pub mod decl {
 }
 
 pub mod traits {
-	pub use super::imfasynccallback::mf_IMFAsyncCallback;
+	pub use super::imfasynccallback::{mf_IMFAsyncCallback, mf_IMFAsyncCallbackImpl};
 	pub use super::imfasyncresult::mf_IMFAsyncResult;
 	pub use super::imfattributes::mf_IMFAttributes;
 	pub use super::imfclock::mf_IMFClock;
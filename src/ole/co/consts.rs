@@ -448,6 +448,17 @@ const_ordinary! { STGMOVE: u32;
 	SHALLOWCOPY 2
 }
 
+const_ordinary! { STGTY: u32;
+	/// [`STGTY`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/ne-objidl-stgty)
+	/// enumeration (`u32`).
+	=>
+	=>
+	STORAGE 1
+	STREAM 2
+	LOCKBYTES 3
+	PROPERTY 4
+}
+
 const_ordinary! { STREAM_SEEK: u32;
 	/// [`STREAM_SEEK`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/ne-objidl-stream_seek)
 	/// enumeration (`u32`).
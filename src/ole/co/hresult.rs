@@ -1,7 +1,10 @@
 use crate::co;
 use crate::prelude::*;
 
-const_no_debug_display! { HRESULT: u32;
+const_no_debug_display! { HRESULT: u32,
+	#[cfg(feature = "dshow")] DSHOW_NAMES,
+	#[cfg(feature = "dxgi")] DXGI_NAMES,
+	#[cfg(feature = "mf")] MF_NAMES;
 	/// A native
 	/// [COM error code](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-erref/0642cb2f-2075-4469-918c-4441e69c548a)
 	/// (`u32`), which can be considered a superset of
@@ -31,7 +34,8 @@ const_no_debug_display! { HRESULT: u32;
 	/// ```
 	///
 	/// The numbers inside the brackets are the system error code, in
-	/// hexadecimal and decimal formats for convenience.
+	/// hexadecimal and decimal formats for convenience. The `Debug` output is
+	/// also prefixed by the symbolic name, like `HRESULT::E_INVALIDARG`.
 	///
 	/// An [`ERROR`](crate::co::ERROR) can be seamlessly converted into an
 	/// `HRESULT`. This operation is equivalent to the
@@ -66,6 +70,9 @@ impl std::fmt::Display for HRESULT {
 }
 impl std::fmt::Debug for HRESULT {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if let Some(val_name) = crate::prelude::NativeConst::name(self) {
+			write!(f, "HRESULT::{} ", val_name)?;
+		}
 		std::fmt::Display::fmt(self, f)
 	}
 }
@@ -90,7 +97,119 @@ impl co::ERROR {
 	}
 }
 
+impl From<co::ERROR> for HRESULT {
+	/// Equivalent to [`ERROR::to_hresult`](crate::co::ERROR::to_hresult).
+	fn from(err: co::ERROR) -> Self {
+		err.to_hresult()
+	}
+}
+
+impl TryFrom<HRESULT> for co::ERROR {
+	type Error = HRESULT;
+
+	/// Equivalent to [`HRESULT::to_error`](crate::co::HRESULT::to_error),
+	/// giving back the original `HRESULT` if it doesn't wrap an `ERROR`.
+	fn try_from(hr: HRESULT) -> Result<Self, Self::Error> {
+		hr.to_error().ok_or(hr)
+	}
+}
+
+impl From<HRESULT> for std::io::Error {
+	/// If the `HRESULT` wraps an [`ERROR`](crate::co::ERROR), converts into an
+	/// [`std::io::Error`](std::io::Error) with the error code as its
+	/// [`raw_os_error`](std::io::Error::raw_os_error). Otherwise, the `HRESULT`
+	/// itself is wrapped, and can be retrieved back with
+	/// [`TryFrom`](std::convert::TryFrom).
+	fn from(hr: HRESULT) -> Self {
+		match hr.to_error() {
+			Some(err) => err.into(),
+			None => Self::new(hr.kind(), hr),
+		}
+	}
+}
+
+impl TryFrom<std::io::Error> for HRESULT {
+	type Error = std::io::Error;
+
+	/// Retrieves the `HRESULT` from an [`std::io::Error`](std::io::Error)
+	/// created from a raw OS error, or wrapping an `HRESULT` or an
+	/// [`ERROR`](crate::co::ERROR), possibly within a
+	/// [`ContextError`](crate::ContextError). Otherwise, the original error is
+	/// given back.
+	fn try_from(err: std::io::Error) -> Result<Self, Self::Error> {
+		if let Some(inner) = err.get_ref() {
+			if let Some(inner) = inner.downcast_ref::<Self>() {
+				return Ok(*inner);
+			} else if let Some(inner) = inner.downcast_ref::<crate::ContextError<Self>>() {
+				return Ok(*inner.error());
+			}
+		}
+		co::ERROR::try_from(err).map(|err| err.to_hresult())
+	}
+}
+
+impl From<crate::ContextError<HRESULT>> for std::io::Error {
+	/// Wraps the error into an [`std::io::Error`](std::io::Error), whose
+	/// [`kind`](std::io::Error::kind) is the one of the original error.
+	fn from(err: crate::ContextError<HRESULT>) -> Self {
+		Self::new(err.error().kind(), err)
+	}
+}
+
 impl HRESULT {
+	/// Unwraps the [`ERROR`](crate::co::ERROR) wrapped by
+	/// [`HRESULT_FROM_WIN32`](https://learn.microsoft.com/en-us/windows/win32/api/winerror/nf-winerror-hresult_from_win32),
+	/// which is the inverse of
+	/// [`ERROR::to_hresult`](crate::co::ERROR::to_hresult).
+	///
+	/// Returns `None` if the `HRESULT` has a facility other than
+	/// [`co::FACILITY::WIN32`](crate::co::FACILITY::WIN32), with the
+	/// exception of [`S_OK`](crate::co::HRESULT::S_OK), which is converted to
+	/// [`co::ERROR::SUCCESS`](crate::co::ERROR::SUCCESS).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let hr = co::ERROR::FILE_NOT_FOUND.to_hresult();
+	/// assert_eq!(hr.to_error(), Some(co::ERROR::FILE_NOT_FOUND));
+	/// ```
+	#[must_use]
+	pub const fn to_error(self) -> Option<co::ERROR> {
+		if self.0 == 0 {
+			Some(co::ERROR::SUCCESS)
+		} else if (self.0 & 0xffff_0000) == (0x8000_0000 | co::FACILITY::WIN32.raw() << 16) {
+			Some(unsafe { co::ERROR::from_raw(self.0 & 0xffff) })
+		} else {
+			None
+		}
+	}
+
+	/// Classifies the `HRESULT` into an
+	/// [`std::io::ErrorKind`](std::io::ErrorKind).
+	///
+	/// If the `HRESULT` wraps an [`ERROR`](crate::co::ERROR), returns
+	/// [`ERROR::kind`](crate::co::ERROR::kind). Otherwise, a few common COM
+	/// errors are classified, and the others return
+	/// [`ErrorKind::Other`](std::io::ErrorKind::Other).
+	#[must_use]
+	pub const fn kind(self) -> std::io::ErrorKind {
+		use std::io::ErrorKind;
+		if let Some(err) = self.to_error() {
+			return err.kind();
+		}
+		match self {
+			Self::E_NOTIMPL
+				| Self::E_NOINTERFACE => ErrorKind::Unsupported,
+			Self::E_POINTER => ErrorKind::InvalidInput,
+			Self::E_ABORT => ErrorKind::Interrupted,
+			Self::E_PENDING => ErrorKind::WouldBlock,
+			Self::E_BOUNDS => ErrorKind::InvalidInput,
+			_ => ErrorKind::Other,
+		}
+	}
+
 	/// [`HRESULT_CODE`](https://learn.microsoft.com/en-us/windows/win32/api/winerror/nf-winerror-hresult_code)
 	/// macro.
 	pub const fn code(self) -> u16 {
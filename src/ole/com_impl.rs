@@ -0,0 +1,135 @@
+#![allow(non_snake_case)]
+
+use std::mem::ManuallyDrop;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::co;
+use crate::decl::*;
+use crate::kernel::ffi_types::*;
+use crate::prelude::*;
+use crate::vt::*;
+
+/// Heap block backing a COM object implemented in Rust. The COM pointer given
+/// to the outside world points to the `vt_ptr` field, so it's laid out as any
+/// other COM object.
+///
+/// The `V` virtual table must begin with an [`IUnknownVT`], built with
+/// [`ComImpl::iunknown_vt`], and the `iids` must hold the whole inheritance
+/// chain of the interface, so
+/// [`QueryInterface`](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void))
+/// succeeds for any of its ancestors.
+#[repr(C)]
+pub(crate) struct ComImpl<V, T> {
+	vt_ptr: *const V,
+	vt: V,
+	ref_count: AtomicU32,
+	iids: &'static [co::IID],
+	obj: T,
+}
+
+impl<V, T> ComImpl<V, T>
+	where V: 'static,
+		T: 'static,
+{
+	/// Allocates the object with a reference count of 1, and returns the COM
+	/// pointer, which can be wrapped by the interface type.
+	#[must_use]
+	pub(crate) fn new_ptr(vt: V, iids: &'static [co::IID], obj: T) -> COMPTR {
+		let mut boxed = Box::new(Self {
+			vt_ptr: std::ptr::null(),
+			vt,
+			ref_count: AtomicU32::new(1),
+			iids,
+			obj,
+		});
+		boxed.vt_ptr = &boxed.vt; // the heap block won't move
+		Box::into_raw(boxed) as _
+	}
+
+	/// Returns the Rust object behind the COM pointer.
+	///
+	/// # Safety
+	///
+	/// The pointer must have been returned by
+	/// [`new_ptr`](crate::ole::com_impl::ComImpl::new_ptr) with the same `V`
+	/// and `T`, and it must be alive.
+	#[must_use]
+	pub(crate) unsafe fn obj<'a>(p: COMPTR) -> &'a T {
+		unsafe { &(*(p as *const Self)).obj }
+	}
+
+	/// Returns the virtual table of `IUnknown`, which must be the first member
+	/// of `V`.
+	#[must_use]
+	pub(crate) fn iunknown_vt() -> IUnknownVT {
+		IUnknownVT {
+			QueryInterface: Self::QueryInterface,
+			AddRef: Self::AddRef,
+			Release: Self::Release,
+		}
+	}
+
+	extern "system" fn QueryInterface(p: COMPTR, riid: PCVOID, ppv: *mut COMPTR) -> HRES {
+		if riid.is_null() || ppv.is_null() {
+			return co::HRESULT::E_POINTER.raw();
+		}
+		let this = unsafe { &*(p as *const Self) };
+		let riid = unsafe { &*(riid as *const co::IID) };
+		if this.iids.iter().any(|iid| iid == riid) {
+			Self::AddRef(p);
+			unsafe { *ppv = p; }
+			co::HRESULT::S_OK.raw()
+		} else {
+			unsafe { *ppv = std::ptr::null_mut(); }
+			co::HRESULT::E_NOINTERFACE.raw()
+		}
+	}
+
+	extern "system" fn AddRef(p: COMPTR) -> u32 {
+		let this = unsafe { &*(p as *const Self) };
+		this.ref_count.fetch_add(1, Ordering::Relaxed) + 1
+	}
+
+	extern "system" fn Release(p: COMPTR) -> u32 {
+		let this = unsafe { &*(p as *const Self) };
+		let count = this.ref_count.fetch_sub(1, Ordering::Release) - 1;
+		if count == 0 {
+			std::sync::atomic::fence(Ordering::Acquire);
+			catch_noret(|| drop(unsafe { Box::from_raw(p as *mut Self) }));
+		}
+		count
+	}
+}
+
+/// Wraps a COM pointer received as argument by a COM method implemented in
+/// Rust. The pointer is not owned, so it won't be released.
+#[must_use]
+pub(crate) fn borrowed<I>(p: COMPTR) -> ManuallyDrop<I>
+	where I: ole_IUnknown,
+{
+	ManuallyDrop::new(unsafe { I::from_ptr(p) })
+}
+
+/// Runs the closure of a COM method implemented in Rust, converting its result
+/// into an `HRESULT`. A panic can't unwind across the FFI boundary, so it's
+/// caught and reported as
+/// [`co::HRESULT::E_UNEXPECTED`](crate::co::HRESULT::E_UNEXPECTED).
+#[must_use]
+pub(crate) fn catch_hres<F>(func: F) -> HRES
+	where F: FnOnce() -> HrResult<()>,
+{
+	match catch_unwind(AssertUnwindSafe(func)) {
+		Ok(Ok(_)) => co::HRESULT::S_OK.raw(),
+		Ok(Err(hr)) => hr.raw(),
+		Err(_) => co::HRESULT::E_UNEXPECTED.raw(),
+	}
+}
+
+/// Runs the closure of a COM method implemented in Rust which returns nothing.
+/// A panic can't unwind across the FFI boundary, so it's caught and discarded.
+pub(crate) fn catch_noret<F>(func: F)
+	where F: FnOnce(),
+{
+	let _ = catch_unwind(AssertUnwindSafe(func));
+}
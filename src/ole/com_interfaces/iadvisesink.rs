@@ -1,7 +1,9 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
 use crate::co;
+use crate::decl::*;
 use crate::kernel::ffi_types::*;
+use crate::ole::com_impl::*;
 use crate::ole::privs::*;
 use crate::prelude::*;
 use crate::vt::*;
@@ -10,11 +12,11 @@ use crate::vt::*;
 #[repr(C)]
 pub struct IAdviseSinkVT {
 	pub IUnknownVT: IUnknownVT,
-	pub OnDataChange: fn(COMPTR, PVOID, PVOID),
-	pub OnViewChange: fn(COMPTR, u32, i32),
-	pub OnRename: fn(COMPTR, COMPTR),
-	pub OnSave: fn(COMPTR),
-	pub OnClose: fn(COMPTR),
+	pub OnDataChange: extern "system" fn(COMPTR, PVOID, PVOID),
+	pub OnViewChange: extern "system" fn(COMPTR, u32, i32),
+	pub OnRename: extern "system" fn(COMPTR, COMPTR),
+	pub OnSave: extern "system" fn(COMPTR),
+	pub OnClose: extern "system" fn(COMPTR),
 }
 
 com_interface! { IAdviseSink: "0000010f-0000-0000-c000-000000000046";
@@ -28,6 +30,77 @@ com_interface! { IAdviseSink: "0000010f-0000-0000-c000-000000000046";
 
 impl ole_IAdviseSink for IAdviseSink {}
 
+impl IAdviseSink {
+	/// Creates a new `IAdviseSink` COM object, whose methods are implemented by
+	/// the given Rust object, which is dropped when the last reference to the
+	/// COM object is released.
+	#[must_use]
+	pub fn new_impl<T>(obj: T) -> Self
+		where T: ole_IAdviseSinkImpl,
+	{
+		let vt = IAdviseSinkVT {
+			IUnknownVT: ComImpl::<IAdviseSinkVT, T>::iunknown_vt(),
+			OnDataChange: on_data_change::<T>,
+			OnViewChange: on_view_change::<T>,
+			OnRename: on_rename::<T>,
+			OnSave: on_save::<T>,
+			OnClose: on_close::<T>,
+		};
+		let ptr = ComImpl::new_ptr(vt, &[IUnknown::IID, IAdviseSink::IID], obj);
+		unsafe { Self::from_ptr(ptr) }
+	}
+}
+
+extern "system" fn on_data_change<T>(p: COMPTR, format_etc: PVOID, stg_medium: PVOID)
+	where T: ole_IAdviseSinkImpl,
+{
+	if format_etc.is_null() || stg_medium.is_null() {
+		return; // nothing to notify, and no way to report the error
+	}
+	catch_noret(|| {
+		let obj = unsafe { ComImpl::<IAdviseSinkVT, T>::obj(p) };
+		let format_etc = unsafe { &*(format_etc as *const FORMATETC) };
+		let stg_medium = unsafe { &*(stg_medium as *const STGMEDIUM) };
+		obj.OnDataChange(format_etc, stg_medium);
+	})
+}
+
+extern "system" fn on_view_change<T>(p: COMPTR, aspect: u32, index: i32)
+	where T: ole_IAdviseSinkImpl,
+{
+	catch_noret(|| {
+		let obj = unsafe { ComImpl::<IAdviseSinkVT, T>::obj(p) };
+		obj.OnViewChange(unsafe { co::DVASPECT::from_raw(aspect) }, index);
+	})
+}
+
+extern "system" fn on_rename<T>(p: COMPTR, mk: COMPTR)
+	where T: ole_IAdviseSinkImpl,
+{
+	catch_noret(|| {
+		let obj = unsafe { ComImpl::<IAdviseSinkVT, T>::obj(p) };
+		obj.OnRename(&borrowed(mk));
+	})
+}
+
+extern "system" fn on_save<T>(p: COMPTR)
+	where T: ole_IAdviseSinkImpl,
+{
+	catch_noret(|| {
+		let obj = unsafe { ComImpl::<IAdviseSinkVT, T>::obj(p) };
+		obj.OnSave();
+	})
+}
+
+extern "system" fn on_close<T>(p: COMPTR)
+	where T: ole_IAdviseSinkImpl,
+{
+	catch_noret(|| {
+		let obj = unsafe { ComImpl::<IAdviseSinkVT, T>::obj(p) };
+		obj.OnClose();
+	})
+}
+
 /// This trait is enabled with the `ole` feature, and provides methods for
 /// [`IAdviseSink`](crate::IAdviseSink).
 ///
@@ -65,3 +138,47 @@ pub trait ole_IAdviseSink: ole_IUnknown {
 		}
 	}
 }
+
+/// This trait is enabled with the `ole` feature, and is implemented by Rust
+/// objects which back an [`IAdviseSink`](crate::IAdviseSink) COM object,
+/// created with [`IAdviseSink::new_impl`](crate::IAdviseSink::new_impl).
+///
+/// All methods have default implementations, which do nothing.
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait ole_IAdviseSinkImpl: 'static {
+	/// [`IAdviseSink::OnClose`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iadvisesink-onclose)
+	/// method.
+	fn OnClose(&self) {}
+
+	/// [`IAdviseSink::OnDataChange`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iadvisesink-ondatachange)
+	/// method.
+	///
+	/// The `stg_medium` is owned by the caller.
+	fn OnDataChange(&self,
+		format_etc: &FORMATETC,
+		stg_medium: &STGMEDIUM,
+	) {
+		let _ = (format_etc, stg_medium);
+	}
+
+	/// [`IAdviseSink::OnRename`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iadvisesink-onrename)
+	/// method.
+	fn OnRename(&self, mk: &IMoniker) {
+		let _ = mk;
+	}
+
+	/// [`IAdviseSink::OnSave`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iadvisesink-onsave)
+	/// method.
+	fn OnSave(&self) {}
+
+	/// [`IAdviseSink::OnViewChange`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iadvisesink-onviewchange)
+	/// method.
+	fn OnViewChange(&self, aspect: co::DVASPECT, index: i32) {
+		let _ = (aspect, index);
+	}
+}
@@ -3,6 +3,7 @@
 use crate::co;
 use crate::decl::*;
 use crate::kernel::ffi_types::*;
+use crate::ole::com_impl::*;
 use crate::ole::privs::*;
 use crate::prelude::*;
 use crate::vt::*;
@@ -11,10 +12,10 @@ use crate::vt::*;
 #[repr(C)]
 pub struct IDropTargetVT {
 	pub IUnknownVT: IUnknownVT,
-	pub DragEnter: fn(COMPTR, COMPTR, u32, u64, *mut u32) -> HRES,
-	pub DragOver: fn(COMPTR, u32, u64, *mut u32) -> HRES,
-	pub DragLeave: fn(COMPTR) -> HRES,
-	pub Drop: fn(COMPTR, COMPTR, u32, u64, *mut u32) -> HRES,
+	pub DragEnter: extern "system" fn(COMPTR, COMPTR, u32, u64, *mut u32) -> HRES,
+	pub DragOver: extern "system" fn(COMPTR, u32, u64, *mut u32) -> HRES,
+	pub DragLeave: extern "system" fn(COMPTR) -> HRES,
+	pub Drop: extern "system" fn(COMPTR, COMPTR, u32, u64, *mut u32) -> HRES,
 }
 
 com_interface! { IDropTarget: "00000122-0000-0000-c000-000000000046";
@@ -28,6 +29,124 @@ com_interface! { IDropTarget: "00000122-0000-0000-c000-000000000046";
 
 impl ole_IDropTarget for IDropTarget {}
 
+impl IDropTarget {
+	/// Creates a new `IDropTarget` COM object, whose methods are implemented by
+	/// the given Rust object, which is dropped when the last reference to the
+	/// COM object is released.
+	///
+	/// The returned object can be passed to
+	/// [`HWND::RegisterDragDrop`](crate::prelude::ole_Hwnd::RegisterDragDrop).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// struct MyDropTarget;
+	///
+	/// impl w::prelude::ole_IDropTargetImpl for MyDropTarget {
+	///     fn DragEnter(&self,
+	///         _data_obj: &w::IDataObject,
+	///         _key_state: co::MK,
+	///         _pt: w::POINT,
+	///         _effect: co::DROPEFFECT,
+	///     ) -> w::HrResult<co::DROPEFFECT>
+	///     {
+	///         Ok(co::DROPEFFECT::COPY)
+	///     }
+	/// }
+	///
+	/// let hwnd: w::HWND; // initialized somewhere
+	/// # let hwnd = w::HWND::NULL;
+	///
+	/// let drop_target = w::IDropTarget::new_impl(MyDropTarget);
+	/// hwnd.RegisterDragDrop(&drop_target)?;
+	/// # Ok::<_, co::HRESULT>(())
+	/// ```
+	#[must_use]
+	pub fn new_impl<T>(obj: T) -> Self
+		where T: ole_IDropTargetImpl,
+	{
+		let vt = IDropTargetVT {
+			IUnknownVT: ComImpl::<IDropTargetVT, T>::iunknown_vt(),
+			DragEnter: drag_enter::<T>,
+			DragOver: drag_over::<T>,
+			DragLeave: drag_leave::<T>,
+			Drop: drop_impl::<T>,
+		};
+		let ptr = ComImpl::new_ptr(vt, &[IUnknown::IID, IDropTarget::IID], obj);
+		unsafe { Self::from_ptr(ptr) }
+	}
+}
+
+extern "system" fn drag_enter<T>(
+	p: COMPTR,
+	data_obj: COMPTR,
+	key_state: u32,
+	pt: u64,
+	effect: *mut u32,
+) -> HRES
+	where T: ole_IDropTargetImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IDropTargetVT, T>::obj(p) };
+		let new_effect = obj.DragEnter(
+			&borrowed(data_obj),
+			unsafe { co::MK::from_raw(key_state as _) },
+			POINT::new(LODWORD(pt) as _, HIDWORD(pt) as _),
+			unsafe { co::DROPEFFECT::from_raw(*effect) },
+		)?;
+		unsafe { *effect = new_effect.raw(); }
+		Ok(())
+	})
+}
+
+extern "system" fn drag_over<T>(p: COMPTR, key_state: u32, pt: u64, effect: *mut u32) -> HRES
+	where T: ole_IDropTargetImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IDropTargetVT, T>::obj(p) };
+		let new_effect = obj.DragOver(
+			unsafe { co::MK::from_raw(key_state as _) },
+			POINT::new(LODWORD(pt) as _, HIDWORD(pt) as _),
+			unsafe { co::DROPEFFECT::from_raw(*effect) },
+		)?;
+		unsafe { *effect = new_effect.raw(); }
+		Ok(())
+	})
+}
+
+extern "system" fn drag_leave<T>(p: COMPTR) -> HRES
+	where T: ole_IDropTargetImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IDropTargetVT, T>::obj(p) };
+		obj.DragLeave()
+	})
+}
+
+extern "system" fn drop_impl<T>(
+	p: COMPTR,
+	data_obj: COMPTR,
+	key_state: u32,
+	pt: u64,
+	effect: *mut u32,
+) -> HRES
+	where T: ole_IDropTargetImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IDropTargetVT, T>::obj(p) };
+		let new_effect = obj.Drop(
+			&borrowed(data_obj),
+			unsafe { co::MK::from_raw(key_state as _) },
+			POINT::new(LODWORD(pt) as _, HIDWORD(pt) as _),
+			unsafe { co::DROPEFFECT::from_raw(*effect) },
+		)?;
+		unsafe { *effect = new_effect.raw(); }
+		Ok(())
+	})
+}
+
 /// This trait is enabled with the `ole` feature, and provides methods for
 /// [`IDropTarget`](crate::IDropTarget).
 ///
@@ -109,3 +228,66 @@ pub trait ole_IDropTarget: ole_IUnknown {
 		).map(|_| effect_buf)
 	}
 }
+
+/// This trait is enabled with the `ole` feature, and is implemented by Rust
+/// objects which back an [`IDropTarget`](crate::IDropTarget) COM object,
+/// created with [`IDropTarget::new_impl`](crate::IDropTarget::new_impl).
+///
+/// All methods have default implementations, which refuse the drop.
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait ole_IDropTargetImpl: 'static {
+	/// [`IDropTarget::DragEnter`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-dragenter)
+	/// method.
+	///
+	/// Returns the effect of the drop, if it happened.
+	fn DragEnter(&self,
+		data_obj: &IDataObject,
+		key_state: co::MK,
+		pt: POINT,
+		effect: co::DROPEFFECT,
+	) -> HrResult<co::DROPEFFECT>
+	{
+		let _ = (data_obj, key_state, pt, effect);
+		Ok(co::DROPEFFECT::NONE)
+	}
+
+	/// [`IDropTarget::DragLeave`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-dragleave)
+	/// method.
+	fn DragLeave(&self) -> HrResult<()> {
+		Ok(())
+	}
+
+	/// [`IDropTarget::DragOver`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-dragover)
+	/// method.
+	///
+	/// Returns the effect of the drop, if it happened.
+	fn DragOver(&self,
+		key_state: co::MK,
+		pt: POINT,
+		effect: co::DROPEFFECT,
+	) -> HrResult<co::DROPEFFECT>
+	{
+		let _ = (key_state, pt, effect);
+		Ok(co::DROPEFFECT::NONE)
+	}
+
+	/// [`IDropTarget::Drop`](https://learn.microsoft.com/en-us/windows/win32/api/oleidl/nf-oleidl-idroptarget-drop)
+	/// method.
+	///
+	/// Returns the effect of the drop.
+	fn Drop(&self,
+		data_obj: &IDataObject,
+		key_state: co::MK,
+		pt: POINT,
+		effect: co::DROPEFFECT,
+	) -> HrResult<co::DROPEFFECT>
+	{
+		let _ = (data_obj, key_state, pt, effect);
+		Ok(co::DROPEFFECT::NONE)
+	}
+}
@@ -11,8 +11,8 @@ use crate::vt::*;
 #[repr(C)]
 pub struct ISequentialStreamVT {
 	pub IUnknownVT: IUnknownVT,
-	pub Read: fn(COMPTR, PVOID, u32, *mut u32) -> HRES,
-	pub Write: fn(COMPTR, PCVOID, u32, *mut u32) -> HRES,
+	pub Read: extern "system" fn(COMPTR, PVOID, u32, *mut u32) -> HRES,
+	pub Write: extern "system" fn(COMPTR, PCVOID, u32, *mut u32) -> HRES,
 }
 
 com_interface! { ISequentialStream: "0c733a30-2a1c-11ce-ade5-00aa0044773d";
@@ -26,6 +26,38 @@ com_interface! { ISequentialStream: "0c733a30-2a1c-11ce-ade5-00aa0044773d";
 
 impl ole_ISequentialStream for ISequentialStream {}
 
+impl std::io::Read for ISequentialStream {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		std::io::Read::read(&mut &*self, buf)
+	}
+}
+impl std::io::Read for &ISequentialStream {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // Read takes an u32 length
+		Ok(ole_ISequentialStream::Read(*self, &mut buf[..len])? as _)
+	}
+}
+
+impl std::io::Write for ISequentialStream {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		std::io::Write::write(&mut &*self, buf)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		std::io::Write::flush(&mut &*self)
+	}
+}
+impl std::io::Write for &ISequentialStream {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // Write takes an u32 length
+		Ok(ole_ISequentialStream::Write(*self, &buf[..len])? as _)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
 /// This trait is enabled with the `ole` feature, and provides methods for
 /// [`ISequentialStream`](crate::ISequentialStream).
 ///
@@ -62,7 +94,7 @@ pub trait ole_ISequentialStream: ole_IUnknown {
 		let mut num_written = u32::default();
 		ok_to_hrresult(
 			unsafe {
-				(vt::<ISequentialStreamVT>(self).Read)(
+				(vt::<ISequentialStreamVT>(self).Write)(
 					self.ptr(),
 					vec_ptr(data) as _,
 					data.len() as _,
@@ -1,8 +1,12 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use std::cell::RefCell;
+use std::io::{Read, Seek, SeekFrom, Write};
+
 use crate::co;
 use crate::decl::*;
 use crate::kernel::ffi_types::*;
+use crate::ole::com_impl::*;
 use crate::ole::privs::*;
 use crate::prelude::*;
 use crate::vt::*;
@@ -11,15 +15,15 @@ use crate::vt::*;
 #[repr(C)]
 pub struct IStreamVT {
 	pub ISequentialStreamVT: ISequentialStreamVT,
-	pub Seek: fn(COMPTR, i64, u32, *mut u64) -> HRES,
-	pub SetSize: fn(COMPTR, u64) -> HRES,
-	pub CopyTo: fn(COMPTR, COMPTR, u64, *mut u64, *mut u64) -> HRES,
-	pub Commit: fn(COMPTR, u32)-> HRES,
-	pub Revert: fn(COMPTR) -> HRES,
-	pub LockRegion: fn(COMPTR, u64, u64, u32) -> HRES,
-	pub UnlockRegion: fn(COMPTR, u64, u64, u32) -> HRES,
-	pub Stat: fn(COMPTR, PVOID, u32) -> HRES,
-	pub Clone: fn(COMPTR, *mut COMPTR) -> HRES,
+	pub Seek: extern "system" fn(COMPTR, i64, u32, *mut u64) -> HRES,
+	pub SetSize: extern "system" fn(COMPTR, u64) -> HRES,
+	pub CopyTo: extern "system" fn(COMPTR, COMPTR, u64, *mut u64, *mut u64) -> HRES,
+	pub Commit: extern "system" fn(COMPTR, u32)-> HRES,
+	pub Revert: extern "system" fn(COMPTR) -> HRES,
+	pub LockRegion: extern "system" fn(COMPTR, u64, u64, u32) -> HRES,
+	pub UnlockRegion: extern "system" fn(COMPTR, u64, u64, u32) -> HRES,
+	pub Stat: extern "system" fn(COMPTR, PVOID, u32) -> HRES,
+	pub Clone: extern "system" fn(COMPTR, *mut COMPTR) -> HRES,
 }
 
 com_interface! { IStream: "0000000c-0000-0000-c000-000000000046";
@@ -43,11 +47,331 @@ com_interface! { IStream: "0000000c-0000-0000-c000-000000000046";
 	/// let stream = w::SHCreateMemStream(&raw_data)?;
 	/// # Ok::<_, winsafe::co::HRESULT>(())
 	/// ```
+	///
+	/// `IStream` and `&IStream` implement [`Read`](std::io::Read),
+	/// [`Write`](std::io::Write) and [`Seek`](std::io::Seek), so a stream can
+	/// be used with the standard library and other crates:
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let stream: w::IStream; // initialized somewhere
+	/// # let stream = unsafe { w::IStream::null() };
+	///
+	/// let mut contents = Vec::<u8>::new();
+	/// std::io::copy(&mut &stream, &mut contents)?;
+	/// # Ok::<_, std::io::Error>(())
+	/// ```
 }
 
 impl ole_ISequentialStream for IStream {}
 impl ole_IStream for IStream {}
 
+impl Read for IStream {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		Read::read(&mut &*self, buf)
+	}
+}
+impl Read for &IStream {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // Read takes an u32 length
+		Ok(ole_ISequentialStream::Read(*self, &mut buf[..len])? as _)
+	}
+}
+
+impl Write for IStream {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		Write::write(&mut &*self, buf)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Write::flush(&mut &*self)
+	}
+}
+impl Write for &IStream {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(u32::MAX as _); // Write takes an u32 length
+		Ok(ole_ISequentialStream::Write(*self, &buf[..len])? as _)
+	}
+
+	/// Calls [`IStream::Commit`](crate::prelude::ole_IStream::Commit), ignoring
+	/// streams which don't implement it.
+	fn flush(&mut self) -> std::io::Result<()> {
+		match self.Commit(co::STGC::DEFAULT) {
+			Ok(_) | Err(co::HRESULT::E_NOTIMPL) => Ok(()),
+			Err(e) => Err(e.into()),
+		}
+	}
+}
+
+impl Seek for IStream {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		Seek::seek(&mut &*self, pos)
+	}
+}
+impl Seek for &IStream {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		let (off, origin) = match pos {
+			SeekFrom::Start(off) => (
+				i64::try_from(off).map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidInput))?,
+				co::STREAM_SEEK::SET,
+			),
+			SeekFrom::Current(off) => (off, co::STREAM_SEEK::CUR),
+			SeekFrom::End(off) => (off, co::STREAM_SEEK::END),
+		};
+		Ok(ole_IStream::Seek(*self, off, origin)?)
+	}
+}
+
+impl IStream {
+	/// Creates a new `IStream` COM object backed by a Rust object which
+	/// implements [`Read`](std::io::Read), [`Write`](std::io::Write) and
+	/// [`Seek`](std::io::Seek), like a [`Cursor`](std::io::Cursor) or a
+	/// [`File`](crate::File). The Rust object is dropped when the last
+	/// reference to the COM object is released.
+	///
+	/// This allows passing in-memory buffers and other Rust streams to COM
+	/// methods which take an `IStream`. Since the size can't be changed
+	/// through these traits,
+	/// [`SetSize`](crate::prelude::ole_IStream::SetSize) and
+	/// [`Clone`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istream-clone)
+	/// fail with [`co::HRESULT::E_NOTIMPL`](crate::co::HRESULT::E_NOTIMPL),
+	/// and region locking isn't supported.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let buf = std::io::Cursor::new(Vec::<u8>::new());
+	/// let stream = w::IStream::new_impl(buf);
+	///
+	/// stream.Write(b"abc")?;
+	/// # Ok::<_, winsafe::co::HRESULT>(())
+	/// ```
+	#[must_use]
+	pub fn new_impl<S>(stream: S) -> Self
+		where S: Read + Write + Seek + 'static,
+	{
+		let vt = IStreamVT {
+			ISequentialStreamVT: ISequentialStreamVT {
+				IUnknownVT: ComImpl::<IStreamVT, RefCell<S>>::iunknown_vt(),
+				Read: read::<S>,
+				Write: write::<S>,
+			},
+			Seek: seek::<S>,
+			SetSize: set_size,
+			CopyTo: copy_to::<S>,
+			Commit: commit::<S>,
+			Revert: revert,
+			LockRegion: lock_region,
+			UnlockRegion: lock_region,
+			Stat: stat::<S>,
+			Clone: clone_impl,
+		};
+		let ptr = ComImpl::new_ptr(
+			vt,
+			&[IUnknown::IID, ISequentialStream::IID, IStream::IID],
+			RefCell::new(stream),
+		);
+		unsafe { Self::from_ptr(ptr) }
+	}
+}
+
+/// Converts an I/O error of a Rust stream into the `HRESULT` returned by the
+/// `IStream` implementation.
+fn io_to_hrresult(err: std::io::Error, fallback: co::HRESULT) -> co::HRESULT {
+	co::HRESULT::try_from(err).unwrap_or(fallback)
+}
+
+/// Returns the Rust stream behind the COM pointer.
+fn stream_of<'a, S>(p: COMPTR) -> &'a RefCell<S>
+	where S: Read + Write + Seek + 'static,
+{
+	unsafe { ComImpl::<IStreamVT, RefCell<S>>::obj(p) }
+}
+
+/// Reads until the buffer is full or the end of the stream is reached, because
+/// COM clients take a short read as the end of the stream.
+fn read_full<S>(stream: &mut S, buf: &mut [u8]) -> HrResult<usize>
+	where S: Read,
+{
+	let mut total = 0;
+	while total < buf.len() {
+		match stream.read(&mut buf[total..]) {
+			Ok(0) => break,
+			Ok(n) => total += n,
+			Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {},
+			Err(e) => return Err(io_to_hrresult(e, co::HRESULT::STG_E_READFAULT)),
+		}
+	}
+	Ok(total)
+}
+
+extern "system" fn read<S>(p: COMPTR, pv: PVOID, cb: u32, pcb_read: *mut u32) -> HRES
+	where S: Read + Write + Seek + 'static,
+{
+	catch_hres(|| {
+		if pv.is_null() && cb > 0 {
+			return Err(co::HRESULT::STG_E_INVALIDPOINTER);
+		}
+		let buf = if cb == 0 {
+			&mut [][..]
+		} else {
+			unsafe { std::slice::from_raw_parts_mut(pv as *mut u8, cb as _) }
+		};
+		let num_read = read_full(&mut *stream_of::<S>(p).borrow_mut(), buf)?;
+		if let Some(pcb_read) = unsafe { pcb_read.as_mut() } {
+			*pcb_read = num_read as _;
+		}
+		Ok(())
+	})
+}
+
+extern "system" fn write<S>(p: COMPTR, pv: PCVOID, cb: u32, pcb_written: *mut u32) -> HRES
+	where S: Read + Write + Seek + 'static,
+{
+	catch_hres(|| {
+		if pv.is_null() && cb > 0 {
+			return Err(co::HRESULT::STG_E_INVALIDPOINTER);
+		}
+		let buf = if cb == 0 {
+			&[][..]
+		} else {
+			unsafe { std::slice::from_raw_parts(pv as *const u8, cb as _) }
+		};
+		let res = stream_of::<S>(p).borrow_mut().write_all(buf);
+		if let Some(pcb_written) = unsafe { pcb_written.as_mut() } {
+			*pcb_written = if res.is_ok() { cb } else { 0 };
+		}
+		res.map_err(|e| io_to_hrresult(e, co::HRESULT::STG_E_WRITEFAULT))
+	})
+}
+
+extern "system" fn seek<S>(p: COMPTR, displacement: i64, origin: u32, new_pos: *mut u64) -> HRES
+	where S: Read + Write + Seek + 'static,
+{
+	catch_hres(|| {
+		let pos = match unsafe { co::STREAM_SEEK::from_raw(origin) } {
+			co::STREAM_SEEK::SET => SeekFrom::Start(displacement as _), // displacement is unsigned here
+			co::STREAM_SEEK::CUR => SeekFrom::Current(displacement),
+			co::STREAM_SEEK::END => SeekFrom::End(displacement),
+			_ => return Err(co::HRESULT::STG_E_INVALIDFUNCTION),
+		};
+		let off = stream_of::<S>(p).borrow_mut().seek(pos)
+			.map_err(|e| io_to_hrresult(e, co::HRESULT::STG_E_INVALIDFUNCTION))?;
+		if let Some(new_pos) = unsafe { new_pos.as_mut() } {
+			*new_pos = off;
+		}
+		Ok(())
+	})
+}
+
+extern "system" fn copy_to<S>(
+	p: COMPTR,
+	dest: COMPTR,
+	num_bytes: u64,
+	pcb_read: *mut u64,
+	pcb_written: *mut u64,
+) -> HRES
+	where S: Read + Write + Seek + 'static,
+{
+	catch_hres(|| {
+		let dest = borrowed::<IStream>(dest);
+		let mut stream = stream_of::<S>(p).borrow_mut();
+		let mut buf = vec![0u8; 0x1_0000];
+		let (mut total_read, mut total_written) = (0u64, 0u64);
+
+		let res = (|| {
+			while total_read < num_bytes {
+				let chunk_len = (num_bytes - total_read).min(buf.len() as _) as usize;
+				let num_read = read_full(&mut *stream, &mut buf[..chunk_len])?;
+				if num_read == 0 {
+					break;
+				}
+				total_read += num_read as u64;
+				total_written += dest.Write(&buf[..num_read])? as u64;
+			}
+			Ok(())
+		})();
+
+		if let Some(pcb_read) = unsafe { pcb_read.as_mut() } {
+			*pcb_read = total_read;
+		}
+		if let Some(pcb_written) = unsafe { pcb_written.as_mut() } {
+			*pcb_written = total_written;
+		}
+		res
+	})
+}
+
+extern "system" fn commit<S>(p: COMPTR, _flags: u32) -> HRES
+	where S: Read + Write + Seek + 'static,
+{
+	catch_hres(|| {
+		stream_of::<S>(p).borrow_mut().flush()
+			.map_err(|e| io_to_hrresult(e, co::HRESULT::STG_E_WRITEFAULT))
+	})
+}
+
+extern "system" fn set_size(_p: COMPTR, _new_size: u64) -> HRES {
+	co::HRESULT::E_NOTIMPL.raw()
+}
+
+extern "system" fn revert(_p: COMPTR) -> HRES {
+	co::HRESULT::S_OK.raw() // not transacted, nothing to revert
+}
+
+extern "system" fn lock_region(_p: COMPTR, _offset: u64, _cb: u64, _lock_type: u32) -> HRES {
+	co::HRESULT::STG_E_INVALIDFUNCTION.raw() // also used for UnlockRegion
+}
+
+extern "system" fn clone_impl(_p: COMPTR, _ppstm: *mut COMPTR) -> HRES {
+	co::HRESULT::E_NOTIMPL.raw()
+}
+
+/// Prefix of the
+/// [`STATSTG`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/ns-objidl-statstg)
+/// struct, with the fields filled by the Rust stream.
+#[repr(C)]
+struct StatStg {
+	pwcsName: *mut u16,
+	r#type: u32,
+	cbSize: u64,
+	mtime: FILETIME,
+	ctime: FILETIME,
+	atime: FILETIME,
+	grfMode: u32,
+	grfLocksSupported: u32,
+	clsid: GUID,
+	grfStateBits: u32,
+	reserved: u32,
+}
+
+extern "system" fn stat<S>(p: COMPTR, pstatstg: PVOID, _flags: u32) -> HRES
+	where S: Read + Write + Seek + 'static,
+{
+	catch_hres(|| {
+		if pstatstg.is_null() {
+			return Err(co::HRESULT::STG_E_INVALIDPOINTER);
+		}
+		let mut stream = stream_of::<S>(p).borrow_mut();
+		let size = (|| {
+			let cur = stream.stream_position()?;
+			let end = stream.seek(SeekFrom::End(0))?;
+			stream.seek(SeekFrom::Start(cur))?;
+			Ok(end)
+		})().map_err(|e| io_to_hrresult(e, co::HRESULT::STG_E_INVALIDFUNCTION))?;
+
+		let statstg = unsafe { &mut *(pstatstg as *mut StatStg) };
+		*statstg = unsafe { std::mem::zeroed() };
+		statstg.r#type = 2; // STGTY_STREAM
+		statstg.cbSize = size;
+		statstg.grfMode = 0x0000_0002; // STGM_READWRITE
+		Ok(())
+	})
+}
+
 /// [`IStream`](crate::IStream) methods from `ole` feature.
 pub trait ole_IStream: ole_ISequentialStream {
 	/// [`IStream::Commit`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istream-commit)
@@ -8,9 +8,9 @@ use crate::ole::privs::*;
 /// [`IUnknown`](crate::IUnknown) virtual table, base to all COM virtual tables.
 #[repr(C)]
 pub struct IUnknownVT {
-	pub QueryInterface: fn(COMPTR, PCVOID, *mut COMPTR) -> HRES,
-	pub AddRef: fn(COMPTR) -> u32,
-	pub Release: fn(COMPTR) -> u32,
+	pub QueryInterface: extern "system" fn(COMPTR, PCVOID, *mut COMPTR) -> HRES,
+	pub AddRef: extern "system" fn(COMPTR) -> u32,
+	pub Release: extern "system" fn(COMPTR) -> u32,
 }
 
 com_interface! { IUnknown: "00000000-0000-0000-c000-000000000046";
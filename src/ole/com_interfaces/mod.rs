@@ -27,10 +27,10 @@ pub mod decl {
 }
 
 pub mod traits {
-	pub use super::iadvisesink::ole_IAdviseSink;
+	pub use super::iadvisesink::{ole_IAdviseSink, ole_IAdviseSinkImpl};
 	pub use super::ibindctx::ole_IBindCtx;
 	pub use super::idataobject::ole_IDataObject;
-	pub use super::idroptarget::ole_IDropTarget;
+	pub use super::idroptarget::{ole_IDropTarget, ole_IDropTargetImpl};
 	pub use super::imoniker::ole_IMoniker;
 	pub use super::ipersist::ole_IPersist;
 	pub use super::ipersiststream::ole_IPersistStream;
@@ -253,3 +253,72 @@ impl SNB {
 		vec
 	}
 }
+
+/// [`STGMEDIUM`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/ns-objidl-ustgmedium-r1)
+/// struct.
+///
+/// The storage medium is owned by whoever provided it, so it's not released
+/// by this struct.
+#[repr(C)]
+pub struct STGMEDIUM {
+	tymed: co::TYMED,
+	data: *mut std::ffi::c_void, // union with the handles, pointers and interfaces
+	pUnkForRelease: COMPTR,
+}
+
+impl_default!(STGMEDIUM);
+
+impl STGMEDIUM {
+	/// Returns the `tymed` field, which tells which union field is valid.
+	#[must_use]
+	pub const fn tymed(&self) -> co::TYMED {
+		self.tymed
+	}
+
+	/// Returns the `hBitmap` union field, if `tymed` is
+	/// [`co::TYMED::GDI`](crate::co::TYMED::GDI).
+	#[must_use]
+	pub fn hBitmap(&self) -> Option<&HBITMAP> {
+		(self.tymed == co::TYMED::GDI)
+			.then(|| unsafe { &*(&self.data as *const _ as *const HBITMAP) })
+	}
+
+	/// Returns the `hGlobal` union field, if `tymed` is
+	/// [`co::TYMED::HGLOBAL`](crate::co::TYMED::HGLOBAL).
+	#[must_use]
+	pub fn hGlobal(&self) -> Option<&HGLOBAL> {
+		(self.tymed == co::TYMED::HGLOBAL)
+			.then(|| unsafe { &*(&self.data as *const _ as *const HGLOBAL) })
+	}
+
+	/// Returns the `lpszFileName` union field, if `tymed` is
+	/// [`co::TYMED::FILE`](crate::co::TYMED::FILE).
+	#[must_use]
+	pub fn lpszFileName(&self) -> Option<String> {
+		(self.tymed == co::TYMED::FILE && !self.data.is_null())
+			.then(|| unsafe { WString::from_wchars_nullt(self.data as _) }.to_string())
+	}
+
+	/// Returns the `pstg` union field, if `tymed` is
+	/// [`co::TYMED::ISTORAGE`](crate::co::TYMED::ISTORAGE).
+	#[must_use]
+	pub fn pstg(&self) -> Option<&IStorage> {
+		(self.tymed == co::TYMED::ISTORAGE && !self.data.is_null())
+			.then(|| unsafe { &*(&self.data as *const _ as *const IStorage) })
+	}
+
+	/// Returns the `pstm` union field, if `tymed` is
+	/// [`co::TYMED::ISTREAM`](crate::co::TYMED::ISTREAM).
+	#[must_use]
+	pub fn pstm(&self) -> Option<&IStream> {
+		(self.tymed == co::TYMED::ISTREAM && !self.data.is_null())
+			.then(|| unsafe { &*(&self.data as *const _ as *const IStream) })
+	}
+
+	/// Returns the `pUnkForRelease` field.
+	#[must_use]
+	pub fn pUnkForRelease(&self) -> Option<&IUnknown> {
+		(!self.pUnkForRelease.is_null())
+			.then(|| unsafe { &*(&self.pUnkForRelease as *const _ as *const IUnknown) })
+	}
+}
@@ -0,0 +1,685 @@
+//! Reading and writing of the
+//! [compound file binary format](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-cfb/53989ce4-7b05-4f8d-829b-d08d6148375b),
+//! versions 3 and 4.
+
+use std::cmp::Ordering;
+
+use crate::co;
+use crate::decl::*;
+
+const SIGNATURE: [u8; 8] = [0xd0, 0xcf, 0x11, 0xe0, 0xa1, 0xb1, 0x1a, 0xe1];
+const HEADER_DIFAT_LEN: usize = 109;
+const DIR_ENTRY_SIZE: usize = 128;
+const MINI_SECTOR_SIZE: usize = 64;
+const MINI_STREAM_CUTOFF: usize = 4096;
+const MAX_NAME_LEN: usize = 31; // in UTF-16 code units, without the terminating null
+
+const MAXREGSECT: u32 = 0xffff_fffa;
+const DIFSECT: u32 = 0xffff_fffc;
+const FATSECT: u32 = 0xffff_fffd;
+const ENDOFCHAIN: u32 = 0xffff_fffe;
+const FREESECT: u32 = 0xffff_ffff;
+const NOSTREAM: u32 = 0xffff_ffff;
+
+const TYPE_UNUSED: u8 = 0;
+const TYPE_STORAGE: u8 = 1;
+const TYPE_STREAM: u8 = 2;
+const TYPE_ROOT: u8 = 5;
+
+/// Name of the root entry, as written by `StgCreateDocfile`.
+pub(in crate::ole::utilities) const ROOT_NAME: &str = "Root Entry";
+
+/// A storage or a stream of a compound file.
+pub(in crate::ole::utilities) struct CfbNode {
+	pub(in crate::ole::utilities) name: String,
+	pub(in crate::ole::utilities) kind: co::STGTY,
+	pub(in crate::ole::utilities) clsid: GUID,
+	pub(in crate::ole::utilities) state_bits: u32,
+	pub(in crate::ole::utilities) created: FILETIME,
+	pub(in crate::ole::utilities) modified: FILETIME,
+	/// Contents of a stream.
+	pub(in crate::ole::utilities) data: Vec<u8>,
+	/// Indexes of the elements of a storage, sorted in directory order.
+	pub(in crate::ole::utilities) children: Vec<usize>,
+	/// False after the element is destroyed; its index is never reused.
+	pub(in crate::ole::utilities) alive: bool,
+}
+
+impl CfbNode {
+	pub(in crate::ole::utilities) fn new(
+		name: &str,
+		kind: co::STGTY,
+		time: FILETIME,
+	) -> Self
+	{
+		Self {
+			name: name.to_owned(),
+			kind,
+			clsid: GUID::default(),
+			state_bits: 0,
+			created: time,
+			modified: time,
+			data: Vec::new(),
+			children: Vec::new(),
+			alive: true,
+		}
+	}
+}
+
+/// The whole contents of a compound file, kept in memory. The root storage is
+/// always the first node.
+pub(in crate::ole::utilities) struct CfbDoc {
+	pub(in crate::ole::utilities) major_version: u16,
+	pub(in crate::ole::utilities) nodes: Vec<CfbNode>,
+}
+
+impl CfbDoc {
+	#[must_use]
+	pub(in crate::ole::utilities) fn new(major_version: u16) -> Self {
+		Self {
+			major_version,
+			nodes: vec![CfbNode::new(ROOT_NAME, co::STGTY::STORAGE, FILETIME::default())],
+		}
+	}
+
+	/// Parses the contents of a compound file.
+	#[must_use]
+	pub(in crate::ole::utilities) fn parse(data: &[u8]) -> HrResult<Self> {
+		if data.len() < 512 || data[..8] != SIGNATURE {
+			return Err(co::HRESULT::STG_E_INVALIDHEADER);
+		}
+		let major_version = le_u16(data, 0x1a);
+		let sector_size = match (major_version, le_u16(data, 0x1e)) {
+			(3, 9) => 512,
+			(4, 12) => 4096,
+			_ => return Err(co::HRESULT::STG_E_INVALIDHEADER),
+		};
+		if le_u16(data, 0x1c) != 0xfffe || le_u16(data, 0x20) != 6 { // byte order, mini sector shift
+			return Err(co::HRESULT::STG_E_INVALIDHEADER);
+		}
+
+		let padded; // some writers don't pad the last sector
+		let rem = data.len() % sector_size;
+		let data = if rem != 0 {
+			padded = [data, &vec![0; sector_size - rem]].concat();
+			&padded[..]
+		} else {
+			data
+		};
+		let sectors = Sectors { data, sector_size };
+
+		let num_fat = le_u32(data, 0x2c) as usize;
+		if num_fat > sectors.count() {
+			return Err(co::HRESULT::STG_E_DOCFILECORRUPT);
+		}
+		let first_dir = le_u32(data, 0x30);
+		let mini_cutoff = le_u32(data, 0x38) as u64;
+		let first_minifat = le_u32(data, 0x3c);
+		let first_difat = le_u32(data, 0x44);
+
+		// DIFAT: the first sectors of the FAT are listed in the header, the
+		// remaining ones in a chain of DIFAT sectors.
+		let mut fat_sects = (0..HEADER_DIFAT_LEN.min(num_fat))
+			.map(|idx| le_u32(data, 0x4c + idx * 4))
+			.collect::<Vec<_>>();
+		let mut difat_sect = first_difat;
+		let mut steps = 0;
+		while fat_sects.len() < num_fat && difat_sect <= MAXREGSECT {
+			steps += 1;
+			if steps > sectors.count() {
+				return Err(co::HRESULT::STG_E_DOCFILECORRUPT); // circular chain
+			}
+			let sect = sectors.get(difat_sect)?;
+			let per_sector = sector_size / 4 - 1; // last one points to the next DIFAT sector
+			fat_sects.extend((0..per_sector).map(|idx| le_u32(sect, idx * 4)));
+			difat_sect = le_u32(sect, per_sector * 4);
+		}
+		if fat_sects.len() < num_fat {
+			return Err(co::HRESULT::STG_E_DOCFILECORRUPT);
+		}
+		fat_sects.truncate(num_fat);
+
+		let mut is_fat_sect = vec![false; sectors.count()];
+		for fat_sect in fat_sects.iter() {
+			match is_fat_sect.get_mut(*fat_sect as usize) {
+				Some(seen) if !*seen => *seen = true,
+				_ => return Err(co::HRESULT::STG_E_DOCFILECORRUPT), // out of range or repeated
+			}
+		}
+
+		let mut fat = Vec::with_capacity(num_fat * sector_size / 4); // num_fat is now within the file size
+		for fat_sect in fat_sects.iter() {
+			let sect = sectors.get(*fat_sect)?;
+			fat.extend((0..sector_size / 4).map(|idx| le_u32(sect, idx * 4)));
+		}
+
+		let dir = sectors.read_chain(&fat, first_dir, None)?;
+		let minifat = sectors.read_chain(&fat, first_minifat, None)?
+			.chunks_exact(4)
+			.map(|b| le_u32(b, 0))
+			.collect::<Vec<_>>();
+
+		let entries = dir.chunks_exact(DIR_ENTRY_SIZE)
+			.collect::<Vec<_>>();
+		let root = entries.first()
+			.map(|b| DirEntry::parse(b, major_version))
+			.filter(|e| e.kind == TYPE_ROOT)
+			.ok_or(co::HRESULT::STG_E_DOCFILECORRUPT)?;
+		let mini_stream = if root.size == 0 {
+			Vec::new()
+		} else {
+			sectors.read_chain(&fat, root.start, Some(root.size))?
+		};
+
+		let mut root_node = CfbNode::new(ROOT_NAME, co::STGTY::STORAGE, FILETIME::default());
+		root_node.clsid = root.clsid;
+		root_node.state_bits = root.state_bits;
+		root_node.created = FILETIME::from_ticks(root.created);
+		root_node.modified = FILETIME::from_ticks(root.modified);
+		let mut nodes = vec![root_node];
+
+		// Each storage keeps its elements in a tree of siblings, which is
+		// flattened into the children list.
+		let mut visited = vec![false; entries.len()];
+		visited[0] = true;
+		let mut pending_storages = vec![(0, root.child)];
+
+		while let Some((parent, tree_root)) = pending_storages.pop() {
+			let mut pending_siblings = vec![tree_root];
+
+			while let Some(id) = pending_siblings.pop() {
+				if id == NOSTREAM {
+					continue;
+				}
+				let id = id as usize;
+				if id >= entries.len() || visited[id] {
+					return Err(co::HRESULT::STG_E_DOCFILECORRUPT); // out of range or circular
+				}
+				visited[id] = true;
+
+				let entry = DirEntry::parse(entries[id], major_version);
+				pending_siblings.push(entry.left);
+				pending_siblings.push(entry.right);
+
+				let mut node = CfbNode::new(&entry.name, co::STGTY::STORAGE,
+					FILETIME::default());
+				node.clsid = entry.clsid;
+				node.state_bits = entry.state_bits;
+				match entry.kind {
+					TYPE_STORAGE => {
+						node.created = FILETIME::from_ticks(entry.created);
+						node.modified = FILETIME::from_ticks(entry.modified);
+						pending_storages.push((nodes.len(), entry.child));
+					},
+					TYPE_STREAM => {
+						node.kind = co::STGTY::STREAM;
+						node.data = if entry.size == 0 {
+							Vec::new()
+						} else if entry.size < mini_cutoff {
+							read_mini_chain(&minifat, &mini_stream, entry.start, entry.size)?
+						} else {
+							sectors.read_chain(&fat, entry.start, Some(entry.size))?
+						};
+					},
+					_ => return Err(co::HRESULT::STG_E_DOCFILECORRUPT),
+				}
+
+				let idx = nodes.len();
+				nodes[parent].children.push(idx);
+				nodes.push(node);
+			}
+		}
+
+		let mut doc = Self { major_version, nodes };
+		for idx in 0..doc.nodes.len() {
+			doc.sort_children(idx);
+		}
+		Ok(doc)
+	}
+
+	/// Sorts the elements of a storage in directory order.
+	pub(in crate::ole::utilities) fn sort_children(&mut self, parent: usize) {
+		let mut children = std::mem::take(&mut self.nodes[parent].children);
+		children.sort_by(|a, b| cmp_names(&self.nodes[*a].name, &self.nodes[*b].name));
+		self.nodes[parent].children = children;
+	}
+
+	/// Serializes the live elements into the contents of a compound file.
+	#[must_use]
+	pub(in crate::ole::utilities) fn serialize(&self) -> HrResult<Vec<u8>> {
+		let sector_size = if self.major_version == 4 { 4096 } else { 512 };
+		let sectors_of = |num_bytes: usize| div_round_up(num_bytes, sector_size);
+
+		// Directory entries are numbered in pre-order, the root being zero.
+		let mut order = Vec::<usize>::with_capacity(self.nodes.len());
+		let mut pending = vec![0];
+		while let Some(idx) = pending.pop() {
+			order.push(idx);
+			pending.extend(self.nodes[idx].children.iter().rev());
+		}
+		let mut dir_ids = vec![NOSTREAM; self.nodes.len()];
+		for (id, idx) in order.iter().enumerate() {
+			dir_ids[*idx] = id as _;
+		}
+
+		let mut entries = order.iter()
+			.map(|idx| DirEntry::from_node(&self.nodes[*idx]))
+			.collect::<Vec<_>>();
+		entries[0].kind = TYPE_ROOT;
+		entries[0].created = 0; // must be zero for the root
+
+		for (id, idx) in order.iter().enumerate() {
+			let node = &self.nodes[*idx];
+			if node.kind == co::STGTY::STORAGE && !node.children.is_empty() {
+				let sorted = node.children.iter() // children are kept sorted
+					.map(|idx| dir_ids[*idx])
+					.collect::<Vec<_>>();
+				let height = (usize::BITS - 1 - sorted.len().leading_zeros()) as usize;
+				entries[id].child = build_tree(&sorted, 0, height, &mut entries);
+			}
+		}
+
+		// Streams smaller than the cutoff are stored in the mini stream, which
+		// is kept in the chain of the root entry.
+		let mut mini_stream = Vec::<u8>::new();
+		let mut minifat = Vec::<u32>::new();
+		let mut regular_streams = Vec::<usize>::new(); // directory IDs
+		for (id, idx) in order.iter().enumerate() {
+			let node = &self.nodes[*idx];
+			if node.kind != co::STGTY::STREAM || node.data.is_empty() {
+				continue;
+			}
+			if self.major_version == 3 && node.data.len() as u64 > u32::MAX as u64 {
+				return Err(co::HRESULT::STG_E_DOCFILETOOLARGE);
+			}
+			if node.data.len() < MINI_STREAM_CUTOFF {
+				let first = minifat.len();
+				let count = div_round_up(node.data.len(), MINI_SECTOR_SIZE);
+				minifat.extend((first + 1..first + count).map(|next| next as u32));
+				minifat.push(ENDOFCHAIN);
+				mini_stream.extend_from_slice(&node.data);
+				mini_stream.resize(minifat.len() * MINI_SECTOR_SIZE, 0);
+				entries[id].start = first as _;
+			} else {
+				regular_streams.push(id);
+			}
+		}
+
+		let dir_sects = sectors_of(entries.len() * DIR_ENTRY_SIZE);
+		let minifat_sects = sectors_of(minifat.len() * 4);
+		let mini_stream_sects = sectors_of(mini_stream.len());
+		let data_sects = dir_sects + minifat_sects + mini_stream_sects
+			+ regular_streams.iter()
+				.map(|id| sectors_of(self.nodes[order[*id]].data.len()))
+				.sum::<usize>();
+
+		// The FAT must also map its own sectors and the DIFAT ones.
+		let per_sector = sector_size / 4;
+		let (mut num_fat, mut num_difat) = (0, 0);
+		loop {
+			let new_fat = div_round_up(data_sects + num_fat + num_difat, per_sector);
+			let new_difat = if new_fat <= HEADER_DIFAT_LEN {
+				0
+			} else {
+				div_round_up(new_fat - HEADER_DIFAT_LEN, per_sector - 1)
+			};
+			if (new_fat, new_difat) == (num_fat, num_difat) {
+				break;
+			}
+			(num_fat, num_difat) = (new_fat, new_difat);
+		}
+		if data_sects + num_fat + num_difat > MAXREGSECT as usize {
+			return Err(co::HRESULT::STG_E_DOCFILETOOLARGE);
+		}
+
+		// Sectors are laid out as: FAT, DIFAT, directory, mini FAT, mini stream
+		// and regular streams, each chain being contiguous.
+		let mut fat = vec![FREESECT; num_fat * per_sector];
+		fat[..num_fat].fill(FATSECT);
+		fat[num_fat..num_fat + num_difat].fill(DIFSECT);
+		let mut next_sect = num_fat + num_difat;
+		let first_dir = alloc_chain(&mut fat, &mut next_sect, dir_sects);
+		let first_minifat = alloc_chain(&mut fat, &mut next_sect, minifat_sects);
+		entries[0].start = alloc_chain(&mut fat, &mut next_sect, mini_stream_sects);
+		entries[0].size = mini_stream.len() as _;
+		for id in regular_streams.iter() {
+			let num_sects = sectors_of(self.nodes[order[*id]].data.len());
+			entries[*id].start = alloc_chain(&mut fat, &mut next_sect, num_sects);
+		}
+
+		let mut buf = Vec::<u8>::with_capacity((next_sect + 1) * sector_size);
+		buf.extend_from_slice(&SIGNATURE);
+		buf.extend_from_slice(&[0; 16]); // header CLSID
+		buf.extend_from_slice(&0x003eu16.to_le_bytes()); // minor version
+		buf.extend_from_slice(&self.major_version.to_le_bytes());
+		buf.extend_from_slice(&0xfffeu16.to_le_bytes()); // byte order
+		buf.extend_from_slice(&(if self.major_version == 4 { 12u16 } else { 9 }).to_le_bytes());
+		buf.extend_from_slice(&6u16.to_le_bytes()); // mini sector shift
+		buf.extend_from_slice(&[0; 6]); // reserved
+		buf.extend_from_slice(&(if self.major_version == 4 { dir_sects as u32 } else { 0 }).to_le_bytes());
+		buf.extend_from_slice(&(num_fat as u32).to_le_bytes());
+		buf.extend_from_slice(&first_dir.to_le_bytes());
+		buf.extend_from_slice(&0u32.to_le_bytes()); // transaction signature
+		buf.extend_from_slice(&(MINI_STREAM_CUTOFF as u32).to_le_bytes());
+		buf.extend_from_slice(&first_minifat.to_le_bytes());
+		buf.extend_from_slice(&(minifat_sects as u32).to_le_bytes());
+		buf.extend_from_slice(&(if num_difat == 0 { ENDOFCHAIN } else { num_fat as u32 }).to_le_bytes());
+		buf.extend_from_slice(&(num_difat as u32).to_le_bytes());
+		for idx in 0..HEADER_DIFAT_LEN {
+			let fat_sect = if idx < num_fat { idx as u32 } else { FREESECT };
+			buf.extend_from_slice(&fat_sect.to_le_bytes());
+		}
+		buf.resize(sector_size, 0); // version 4 header takes a whole sector
+
+		for sect in fat.iter() {
+			buf.extend_from_slice(&sect.to_le_bytes());
+		}
+
+		let extra_fat_sects = (HEADER_DIFAT_LEN..num_fat).collect::<Vec<_>>();
+		for (idx, chunk) in extra_fat_sects.chunks(per_sector - 1).enumerate() {
+			for fat_sect in chunk.iter() {
+				buf.extend_from_slice(&(*fat_sect as u32).to_le_bytes());
+			}
+			for _ in chunk.len()..per_sector - 1 {
+				buf.extend_from_slice(&FREESECT.to_le_bytes());
+			}
+			let next_difat = if idx + 1 < num_difat { (num_fat + idx + 1) as u32 } else { ENDOFCHAIN };
+			buf.extend_from_slice(&next_difat.to_le_bytes());
+		}
+
+		for entry in entries.iter() {
+			entry.serialize(&mut buf);
+		}
+		for _ in entries.len()..dir_sects * sector_size / DIR_ENTRY_SIZE {
+			DirEntry::unused().serialize(&mut buf);
+		}
+
+		for sect in minifat.iter() {
+			buf.extend_from_slice(&sect.to_le_bytes());
+		}
+		pad_to_sector(&mut buf, sector_size, 0xff); // free mini sectors
+
+		buf.extend_from_slice(&mini_stream);
+		pad_to_sector(&mut buf, sector_size, 0);
+
+		for id in regular_streams.iter() {
+			buf.extend_from_slice(&self.nodes[order[*id]].data);
+			pad_to_sector(&mut buf, sector_size, 0);
+		}
+
+		Ok(buf)
+	}
+}
+
+/// Fails with
+/// [`co::HRESULT::STG_E_INVALIDNAME`](crate::co::HRESULT::STG_E_INVALIDNAME)
+/// if the name can't be stored in a directory entry.
+pub(in crate::ole::utilities) fn validate_name(name: &str) -> HrResult<()> {
+	if name.is_empty()
+		|| name.encode_utf16().count() > MAX_NAME_LEN
+		|| name.contains(['/', '\\', ':', '!'])
+	{
+		Err(co::HRESULT::STG_E_INVALIDNAME)
+	} else {
+		Ok(())
+	}
+}
+
+/// Compares two element names in directory order: shorter names come first,
+/// then names are compared by their uppercase UTF-16 code units.
+#[must_use]
+pub(in crate::ole::utilities) fn cmp_names(a: &str, b: &str) -> Ordering {
+	let (a, b) = (upper_units(a), upper_units(b));
+	a.len().cmp(&b.len())
+		.then_with(|| a.cmp(&b))
+}
+
+/// Converts each character to uppercase, as long as it maps to a single
+/// character of the same UTF-16 length, like the simple case mapping used by
+/// the format.
+fn upper_units(name: &str) -> Vec<u16> {
+	let mut units = Vec::with_capacity(name.len());
+	for ch in name.chars() {
+		let mut upper = ch.to_uppercase();
+		let ch = match (upper.next(), upper.next()) {
+			(Some(up), None) if up.len_utf16() == ch.len_utf16() => up,
+			_ => ch,
+		};
+		let mut buf = [0; 2];
+		units.extend_from_slice(ch.encode_utf16(&mut buf));
+	}
+	units
+}
+
+/// Raw `StructuredStorageDirectoryEntry`.
+struct DirEntry {
+	name: String,
+	kind: u8,
+	black: bool,
+	left: u32,
+	right: u32,
+	child: u32,
+	clsid: GUID,
+	state_bits: u32,
+	created: u64,
+	modified: u64,
+	start: u32,
+	size: u64,
+}
+
+impl DirEntry {
+	fn unused() -> Self {
+		Self {
+			name: String::new(),
+			kind: TYPE_UNUSED,
+			black: false,
+			left: NOSTREAM,
+			right: NOSTREAM,
+			child: NOSTREAM,
+			clsid: GUID::default(),
+			state_bits: 0,
+			created: 0,
+			modified: 0,
+			start: 0,
+			size: 0,
+		}
+	}
+
+	/// Creates the entry of an element, without the tree links and the chain.
+	fn from_node(node: &CfbNode) -> Self {
+		let is_stream = node.kind == co::STGTY::STREAM;
+		Self {
+			name: node.name.clone(),
+			kind: if is_stream { TYPE_STREAM } else { TYPE_STORAGE },
+			black: true,
+			clsid: node.clsid,
+			state_bits: node.state_bits,
+			created: if is_stream { 0 } else { node.created.ticks() }, // must be zero for streams
+			modified: if is_stream { 0 } else { node.modified.ticks() },
+			start: if is_stream { ENDOFCHAIN } else { 0 },
+			size: if is_stream { node.data.len() as _ } else { 0 },
+			..Self::unused()
+		}
+	}
+
+	fn parse(b: &[u8], major_version: u16) -> Self {
+		let name_len = ((le_u16(b, 0x40) as usize / 2).saturating_sub(1)).min(MAX_NAME_LEN);
+		let name = (0..name_len)
+			.map(|idx| le_u16(b, idx * 2))
+			.collect::<Vec<_>>();
+		let size = u64::from_le_bytes(b[0x78..0x80].try_into().unwrap());
+
+		Self {
+			name: String::from_utf16_lossy(&name),
+			kind: b[0x42],
+			black: b[0x43] != 0,
+			left: le_u32(b, 0x44),
+			right: le_u32(b, 0x48),
+			child: le_u32(b, 0x4c),
+			clsid: unsafe { std::ptr::read_unaligned(b[0x50..].as_ptr() as *const GUID) },
+			state_bits: le_u32(b, 0x60),
+			created: u64::from_le_bytes(b[0x64..0x6c].try_into().unwrap()),
+			modified: u64::from_le_bytes(b[0x6c..0x74].try_into().unwrap()),
+			start: le_u32(b, 0x74),
+			size: if major_version == 3 { size & 0xffff_ffff } else { size }, // high part may be garbage
+		}
+	}
+
+	fn serialize(&self, buf: &mut Vec<u8>) {
+		let name = self.name.encode_utf16().collect::<Vec<_>>();
+		for idx in 0..32 {
+			buf.extend_from_slice(&name.get(idx).copied().unwrap_or(0).to_le_bytes());
+		}
+		let name_len = if name.is_empty() { 0 } else { (name.len() + 1) * 2 }; // includes the null
+		buf.extend_from_slice(&(name_len as u16).to_le_bytes());
+		buf.push(self.kind);
+		buf.push(self.black as _);
+		buf.extend_from_slice(&self.left.to_le_bytes());
+		buf.extend_from_slice(&self.right.to_le_bytes());
+		buf.extend_from_slice(&self.child.to_le_bytes());
+		buf.extend_from_slice(unsafe {
+			std::slice::from_raw_parts(&self.clsid as *const _ as *const u8, 16)
+		});
+		buf.extend_from_slice(&self.state_bits.to_le_bytes());
+		buf.extend_from_slice(&self.created.to_le_bytes());
+		buf.extend_from_slice(&self.modified.to_le_bytes());
+		buf.extend_from_slice(&self.start.to_le_bytes());
+		buf.extend_from_slice(&self.size.to_le_bytes());
+	}
+}
+
+/// Builds a balanced red-black tree from sorted directory IDs, returning the
+/// ID of its root. All levels but the deepest one are full, so the nodes of
+/// the deepest level are red and all others are black.
+fn build_tree(
+	sorted: &[u32],
+	depth: usize,
+	height: usize,
+	entries: &mut [DirEntry],
+) -> u32
+{
+	if sorted.is_empty() {
+		return NOSTREAM;
+	}
+	let mid = sorted.len() / 2;
+	let id = sorted[mid];
+	let left = build_tree(&sorted[..mid], depth + 1, height, entries);
+	let right = build_tree(&sorted[mid + 1..], depth + 1, height, entries);
+
+	let entry = &mut entries[id as usize];
+	entry.left = left;
+	entry.right = right;
+	entry.black = depth < height || height == 0;
+	id
+}
+
+/// Marks a contiguous chain of sectors in the FAT, returning its first sector.
+fn alloc_chain(fat: &mut [u32], next_sect: &mut usize, num_sects: usize) -> u32 {
+	if num_sects == 0 {
+		return ENDOFCHAIN;
+	}
+	let first = *next_sect;
+	let chain = &mut fat[first..first + num_sects];
+	for (idx, sect) in chain.iter_mut().enumerate() {
+		*sect = (first + idx + 1) as _;
+	}
+	chain[num_sects - 1] = ENDOFCHAIN;
+	*next_sect += num_sects;
+	first as _
+}
+
+fn div_round_up(num: usize, den: usize) -> usize {
+	let rem = num % den;
+	num / den + (rem != 0) as usize
+}
+
+fn pad_to_sector(buf: &mut Vec<u8>, sector_size: usize, byte: u8) {
+	let rem = buf.len() % sector_size;
+	if rem != 0 {
+		buf.resize(buf.len() + sector_size - rem, byte);
+	}
+}
+
+/// Reads the mini sectors of a small stream from the mini stream.
+fn read_mini_chain(
+	minifat: &[u32],
+	mini_stream: &[u8],
+	start: u32,
+	size: u64,
+) -> HrResult<Vec<u8>>
+{
+	let mut buf = Vec::<u8>::new();
+	let mut cur = start;
+	while (buf.len() as u64) < size {
+		if buf.len() / MINI_SECTOR_SIZE >= mini_stream.len() / MINI_SECTOR_SIZE {
+			return Err(co::HRESULT::STG_E_DOCFILECORRUPT); // circular chain
+		}
+		let off = cur as usize * MINI_SECTOR_SIZE;
+		let mini_sect = mini_stream.get(off..off + MINI_SECTOR_SIZE)
+			.ok_or(co::HRESULT::STG_E_DOCFILECORRUPT)?;
+		buf.extend_from_slice(mini_sect);
+		cur = *minifat.get(cur as usize)
+			.ok_or(co::HRESULT::STG_E_DOCFILECORRUPT)?;
+	}
+	buf.truncate(size as _);
+	Ok(buf)
+}
+
+/// The regular sectors of a file, the header being sector -1.
+struct Sectors<'a> {
+	data: &'a [u8],
+	sector_size: usize,
+}
+
+impl<'a> Sectors<'a> {
+	fn count(&self) -> usize {
+		self.data.len() / self.sector_size - 1
+	}
+
+	fn get(&self, sect: u32) -> HrResult<&'a [u8]> {
+		let sect = sect as usize;
+		if sect >= self.count() {
+			return Err(co::HRESULT::STG_E_DOCFILECORRUPT);
+		}
+		let off = (sect + 1) * self.sector_size;
+		Ok(&self.data[off..off + self.sector_size])
+	}
+
+	/// Reads a chain of sectors. If a size is given, the chain is truncated to
+	/// it, failing if it's shorter.
+	fn read_chain(&self,
+		fat: &[u32],
+		start: u32,
+		size: Option<u64>,
+	) -> HrResult<Vec<u8>>
+	{
+		let mut buf = Vec::<u8>::new();
+		let mut cur = start;
+		while cur != ENDOFCHAIN {
+			if size.is_some_and(|size| buf.len() as u64 >= size) {
+				break;
+			}
+			if buf.len() / self.sector_size >= self.count() {
+				return Err(co::HRESULT::STG_E_DOCFILECORRUPT); // circular chain
+			}
+			buf.extend_from_slice(self.get(cur)?);
+			cur = *fat.get(cur as usize)
+				.ok_or(co::HRESULT::STG_E_DOCFILECORRUPT)?;
+		}
+		if let Some(size) = size {
+			if (buf.len() as u64) < size {
+				return Err(co::HRESULT::STG_E_DOCFILECORRUPT);
+			}
+			buf.truncate(size as _);
+		}
+		Ok(buf)
+	}
+}
+
+fn le_u16(data: &[u8], off: usize) -> u16 {
+	u16::from_le_bytes([data[off], data[off + 1]])
+}
+
+fn le_u32(data: &[u8], off: usize) -> u32 {
+	u32::from_le_bytes([data[off], data[off + 1], data[off + 2], data[off + 3]])
+}
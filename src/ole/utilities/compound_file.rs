@@ -0,0 +1,494 @@
+use std::cell::RefCell;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::rc::Rc;
+
+use crate::co;
+use crate::decl::*;
+use crate::ole::utilities::cfb::*;
+
+/// Information about an element of a [`CompoundFile`](crate::CompoundFile),
+/// like the
+/// [`STATSTG`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/ns-objidl-statstg)
+/// struct.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CompoundEntry {
+	/// Name of the element. The root storage is named `Root Entry`.
+	pub name: String,
+	/// Either [`co::STGTY::STORAGE`](crate::co::STGTY::STORAGE) or
+	/// [`co::STGTY::STREAM`](crate::co::STGTY::STREAM).
+	pub kind: co::STGTY,
+	/// Size of a stream, in bytes. Zero for storages.
+	pub size: u64,
+	/// Class ID of a storage.
+	pub clsid: GUID,
+	/// State bits of the element, whose meaning is defined by the application.
+	pub state_bits: u32,
+	/// Creation time of a storage. Zero for streams.
+	pub created: FILETIME,
+	/// Last modification time of a storage. Zero for streams.
+	pub modified: FILETIME,
+}
+
+impl CompoundEntry {
+	fn from_node(node: &CfbNode) -> Self {
+		Self {
+			name: node.name.clone(),
+			kind: node.kind,
+			size: node.data.len() as _,
+			clsid: node.clsid,
+			state_bits: node.state_bits,
+			created: node.created,
+			modified: node.modified,
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+type SharedDoc = Rc<RefCell<CfbDoc>>;
+
+/// A
+/// [compound file](https://learn.microsoft.com/en-us/windows/win32/stg/compound-files),
+/// also known as structured storage, which is the container format of `.msi`,
+/// `.doc`, `.xls` and `Thumbs.db` files, among others.
+///
+/// This is a pure Rust implementation of the
+/// [MS-CFB](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-cfb/53989ce4-7b05-4f8d-829b-d08d6148375b)
+/// format, versions 3 and 4, which doesn't need COM. The API is shaped like
+/// [`IStorage`](crate::IStorage) and [`IStream`](crate::IStream): the
+/// [`root`](crate::CompoundFile::root) storage holds storages and streams,
+/// which are [`CompoundStorage`](crate::CompoundStorage) and
+/// [`CompoundStream`](crate::CompoundStream) objects. Errors are reported with
+/// the same `STG_E_*` codes returned by the system implementation.
+///
+/// The whole file is loaded in memory; changes are written back only when
+/// [`serialize_to_file`](crate::CompoundFile::serialize_to_file) or
+/// [`serialize_to_bytes`](crate::CompoundFile::serialize_to_bytes) is called.
+/// The serialized files can be opened by
+/// [`StgOpenStorage`](https://learn.microsoft.com/en-us/windows/win32/api/coml2api/nf-coml2api-stgopenstorage),
+/// and files created by
+/// [`StgCreateDocfile`](https://learn.microsoft.com/en-us/windows/win32/api/coml2api/nf-coml2api-stgcreatedocfile)
+/// can be read.
+///
+/// # Examples
+///
+/// Listing the streams of an `.msi` file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let cf = w::CompoundFile::parse_from_file("C:\\Temp\\setup.msi")?;
+///
+/// for entry in cf.root().elements()?.iter() {
+///     if entry.kind == co::STGTY::STREAM {
+///         println!("{:?}: {} bytes", entry.name, entry.size);
+///     }
+/// }
+/// # Ok::<_, winsafe::co::HRESULT>(())
+/// ```
+///
+/// Creating a new file:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+/// use std::io::Write;
+///
+/// let cf = w::CompoundFile::new();
+/// let storage = cf.root().create_storage("Data")?;
+/// let mut stream = storage.create_stream("Contents")?;
+/// stream.write_all(b"Hello")?;
+///
+/// cf.serialize_to_file("C:\\Temp\\data.stg")?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct CompoundFile {
+	doc: SharedDoc,
+}
+
+impl Default for CompoundFile {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl CompoundFile {
+	/// Creates a new, empty compound file, in version 3, with 512-byte sectors,
+	/// which is the default of `StgCreateDocfile`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self { doc: Rc::new(RefCell::new(CfbDoc::new(3))) }
+	}
+
+	/// Creates a new, empty compound file, in version 4, with 4096-byte
+	/// sectors, which allows streams larger than 4 GB.
+	#[must_use]
+	pub fn new_v4() -> Self {
+		Self { doc: Rc::new(RefCell::new(CfbDoc::new(4))) }
+	}
+
+	/// Parses the contents of a compound file.
+	///
+	/// Fails with
+	/// [`co::HRESULT::STG_E_INVALIDHEADER`](crate::co::HRESULT::STG_E_INVALIDHEADER)
+	/// if the data isn't a compound file, or with
+	/// [`co::HRESULT::STG_E_DOCFILECORRUPT`](crate::co::HRESULT::STG_E_DOCFILECORRUPT)
+	/// if its structures are damaged.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> HrResult<Self> {
+		Ok(Self { doc: Rc::new(RefCell::new(CfbDoc::parse(data)?)) })
+	}
+
+	/// Parses a compound file directly from the disk. The file will be
+	/// [mapped in memory](crate::FileMapped) during reading.
+	#[must_use]
+	pub fn parse_from_file(file_path: &str) -> HrResult<Self> {
+		let fin = FileMapped::open(file_path, FileAccess::ExistingReadOnly)
+			.map_err(|e| e.to_hresult())?;
+		Self::parse(fin.as_slice())
+	}
+
+	/// Returns the major version of the format, either 3 or 4.
+	#[must_use]
+	pub fn major_version(&self) -> u16 {
+		self.doc.borrow().major_version
+	}
+
+	/// Returns the root storage, which holds all the other elements.
+	#[must_use]
+	pub fn root(&self) -> CompoundStorage {
+		CompoundStorage { doc: self.doc.clone(), idx: 0 }
+	}
+
+	/// Serializes all the storages and streams to the contents of a compound
+	/// file.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> HrResult<Vec<u8>> {
+		self.doc.borrow().serialize()
+	}
+
+	/// Serializes all the storages and streams directly to a file.
+	pub fn serialize_to_file(&self, file_path: &str) -> HrResult<()> {
+		let data = self.serialize_to_bytes()?;
+		let fout = File::open(file_path, FileAccess::OpenOrCreateRW)
+			.map_err(|e| e.to_hresult())?;
+		fout.erase_and_write(&data)
+			.map_err(|e| e.to_hresult())
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A storage within a [`CompoundFile`](crate::CompoundFile), which holds other
+/// storages and streams, like an [`IStorage`](crate::IStorage).
+///
+/// Element names are case-insensitive, up to 31 characters long, and can't
+/// contain the `/`, `\`, `:` and `!` characters.
+///
+/// If the storage is destroyed, its methods fail with
+/// [`co::HRESULT::STG_E_REVERTED`](crate::co::HRESULT::STG_E_REVERTED).
+pub struct CompoundStorage {
+	doc: SharedDoc,
+	idx: usize,
+}
+
+impl CompoundStorage {
+	/// Creates a new storage within this one, like
+	/// [`IStorage::CreateStorage`](crate::prelude::ole_IStorage::CreateStorage).
+	///
+	/// Fails with
+	/// [`co::HRESULT::STG_E_FILEALREADYEXISTS`](crate::co::HRESULT::STG_E_FILEALREADYEXISTS)
+	/// if an element with the same name already exists.
+	#[must_use]
+	pub fn create_storage(&self, name: &str) -> HrResult<CompoundStorage> {
+		let now = FILETIME::try_from(std::time::SystemTime::now())
+			.unwrap_or_default();
+		let idx = self.create_element(CfbNode::new(name, co::STGTY::STORAGE, now))?;
+		Ok(CompoundStorage { doc: self.doc.clone(), idx })
+	}
+
+	/// Creates a new, empty stream within this storage, like
+	/// [`IStorage::CreateStream`](crate::prelude::ole_IStorage::CreateStream).
+	///
+	/// Fails with
+	/// [`co::HRESULT::STG_E_FILEALREADYEXISTS`](crate::co::HRESULT::STG_E_FILEALREADYEXISTS)
+	/// if an element with the same name already exists.
+	#[must_use]
+	pub fn create_stream(&self, name: &str) -> HrResult<CompoundStream> {
+		let idx = self.create_element(
+			CfbNode::new(name, co::STGTY::STREAM, FILETIME::default()))?;
+		Ok(CompoundStream { doc: self.doc.clone(), idx, pos: 0 })
+	}
+
+	/// Destroys an element of this storage, like
+	/// [`IStorage::DestroyElement`](crate::prelude::ole_IStorage::DestroyElement).
+	/// If it's a storage, all its elements are also destroyed.
+	pub fn destroy_element(&self, name: &str) -> HrResult<()> {
+		let mut doc = self.doc.borrow_mut();
+		let child = find_child(&doc, self.idx, name)?;
+		doc.nodes[self.idx].children.retain(|idx| *idx != child);
+
+		let mut pending = vec![child];
+		while let Some(idx) = pending.pop() {
+			let node = &mut doc.nodes[idx];
+			node.alive = false;
+			node.data = Vec::new();
+			pending.append(&mut node.children);
+		}
+		Ok(())
+	}
+
+	/// Returns information about all the elements of this storage, sorted in
+	/// directory order, like
+	/// [`IStorage::EnumElements`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istorage-enumelements).
+	#[must_use]
+	pub fn elements(&self) -> HrResult<Vec<CompoundEntry>> {
+		let doc = self.doc.borrow();
+		Ok(
+			live_node(&doc, self.idx)?
+				.children.iter()
+				.map(|idx| CompoundEntry::from_node(&doc.nodes[*idx]))
+				.collect(),
+		)
+	}
+
+	/// Opens an existing storage within this one, like
+	/// [`IStorage::OpenStorage`](crate::prelude::ole_IStorage::OpenStorage).
+	///
+	/// Fails with
+	/// [`co::HRESULT::STG_E_FILENOTFOUND`](crate::co::HRESULT::STG_E_FILENOTFOUND)
+	/// if there's no storage with the given name.
+	#[must_use]
+	pub fn open_storage(&self, name: &str) -> HrResult<CompoundStorage> {
+		let idx = self.open_element(name, co::STGTY::STORAGE)?;
+		Ok(CompoundStorage { doc: self.doc.clone(), idx })
+	}
+
+	/// Opens an existing stream within this storage, like
+	/// [`IStorage::OpenStream`](crate::prelude::ole_IStorage::OpenStream).
+	///
+	/// Fails with
+	/// [`co::HRESULT::STG_E_FILENOTFOUND`](crate::co::HRESULT::STG_E_FILENOTFOUND)
+	/// if there's no stream with the given name.
+	#[must_use]
+	pub fn open_stream(&self, name: &str) -> HrResult<CompoundStream> {
+		let idx = self.open_element(name, co::STGTY::STREAM)?;
+		Ok(CompoundStream { doc: self.doc.clone(), idx, pos: 0 })
+	}
+
+	/// Renames an element of this storage, like
+	/// [`IStorage::RenameElement`](crate::prelude::ole_IStorage::RenameElement).
+	///
+	/// Fails with
+	/// [`co::HRESULT::STG_E_FILEALREADYEXISTS`](crate::co::HRESULT::STG_E_FILEALREADYEXISTS)
+	/// if another element already has the new name.
+	pub fn rename_element(&self, old_name: &str, new_name: &str) -> HrResult<()> {
+		validate_name(new_name)?;
+		let mut doc = self.doc.borrow_mut();
+		let child = find_child(&doc, self.idx, old_name)?;
+		if let Ok(existing) = find_child(&doc, self.idx, new_name) {
+			if existing != child {
+				return Err(co::HRESULT::STG_E_FILEALREADYEXISTS);
+			}
+		}
+		doc.nodes[child].name = new_name.to_owned();
+		doc.sort_children(self.idx);
+		Ok(())
+	}
+
+	/// Sets the class ID of this storage, like
+	/// [`IStorage::SetClass`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istorage-setclass).
+	pub fn set_class(&self, clsid: &GUID) -> HrResult<()> {
+		let mut doc = self.doc.borrow_mut();
+		live_node_mut(&mut doc, self.idx)?.clsid = *clsid;
+		Ok(())
+	}
+
+	/// Sets the creation and modification times of a storage within this one,
+	/// like
+	/// [`IStorage::SetElementTimes`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istorage-setelementtimes).
+	/// Times which are `None` are left unchanged.
+	///
+	/// Streams have no times in the format, so they fail with
+	/// [`co::HRESULT::STG_E_INVALIDFUNCTION`](crate::co::HRESULT::STG_E_INVALIDFUNCTION).
+	pub fn set_element_times(&self,
+		name: &str,
+		created: Option<&FILETIME>,
+		modified: Option<&FILETIME>,
+	) -> HrResult<()>
+	{
+		let mut doc = self.doc.borrow_mut();
+		let child = find_child(&doc, self.idx, name)?;
+		let node = &mut doc.nodes[child];
+		if node.kind != co::STGTY::STORAGE {
+			return Err(co::HRESULT::STG_E_INVALIDFUNCTION);
+		}
+		if let Some(created) = created {
+			node.created = *created;
+		}
+		if let Some(modified) = modified {
+			node.modified = *modified;
+		}
+		Ok(())
+	}
+
+	/// Sets the bits of the state of this storage which are present in the
+	/// mask, like
+	/// [`IStorage::SetStateBits`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istorage-setstatebits).
+	pub fn set_state_bits(&self, state_bits: u32, mask: u32) -> HrResult<()> {
+		let mut doc = self.doc.borrow_mut();
+		let node = live_node_mut(&mut doc, self.idx)?;
+		node.state_bits = (node.state_bits & !mask) | (state_bits & mask);
+		Ok(())
+	}
+
+	/// Returns information about this storage, like
+	/// [`IStorage::Stat`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istorage-stat).
+	#[must_use]
+	pub fn stat(&self) -> HrResult<CompoundEntry> {
+		let doc = self.doc.borrow();
+		Ok(CompoundEntry::from_node(live_node(&doc, self.idx)?))
+	}
+
+	fn create_element(&self, node: CfbNode) -> HrResult<usize> {
+		validate_name(&node.name)?;
+		let mut doc = self.doc.borrow_mut();
+		match find_child(&doc, self.idx, &node.name) {
+			Ok(_) => return Err(co::HRESULT::STG_E_FILEALREADYEXISTS),
+			Err(co::HRESULT::STG_E_FILENOTFOUND) => {},
+			Err(e) => return Err(e),
+		}
+
+		let idx = doc.nodes.len();
+		doc.nodes.push(node);
+		doc.nodes[self.idx].children.push(idx);
+		doc.sort_children(self.idx);
+		Ok(idx)
+	}
+
+	fn open_element(&self, name: &str, kind: co::STGTY) -> HrResult<usize> {
+		let doc = self.doc.borrow();
+		let idx = find_child(&doc, self.idx, name)?;
+		if doc.nodes[idx].kind == kind {
+			Ok(idx)
+		} else {
+			Err(co::HRESULT::STG_E_FILENOTFOUND)
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A stream within a [`CompoundFile`](crate::CompoundFile), like an
+/// [`IStream`](crate::IStream).
+///
+/// The contents are accessed through the [`Read`](std::io::Read),
+/// [`Write`](std::io::Write) and [`Seek`](std::io::Seek) traits. Each object
+/// has its own seek pointer, starting at zero. Writing past the end extends
+/// the stream.
+///
+/// If the stream is destroyed, its methods fail with
+/// [`co::HRESULT::STG_E_REVERTED`](crate::co::HRESULT::STG_E_REVERTED).
+pub struct CompoundStream {
+	doc: SharedDoc,
+	idx: usize,
+	pos: u64,
+}
+
+impl Read for CompoundStream {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let doc = self.doc.borrow();
+		let data = &live_node(&doc, self.idx)?.data;
+		let start = self.pos.min(data.len() as _) as usize;
+		let num_read = buf.len().min(data.len() - start);
+		buf[..num_read].copy_from_slice(&data[start..start + num_read]);
+		self.pos += num_read as u64;
+		Ok(num_read)
+	}
+}
+
+impl Write for CompoundStream {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		let mut doc = self.doc.borrow_mut();
+		let data = &mut live_node_mut(&mut doc, self.idx)?.data;
+		let start = usize::try_from(self.pos)
+			.map_err(|_| co::HRESULT::STG_E_MEDIUMFULL)?;
+		let end = start.checked_add(buf.len())
+			.ok_or(co::HRESULT::STG_E_MEDIUMFULL)?;
+		if end > data.len() {
+			data.resize(end, 0);
+		}
+		data[start..end].copy_from_slice(buf);
+		self.pos = end as _;
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(()) // changes are kept in memory until the file is serialized
+	}
+}
+
+impl Seek for CompoundStream {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		let (base, off) = match pos {
+			SeekFrom::Start(off) => {
+				self.pos = off;
+				return Ok(off);
+			},
+			SeekFrom::Current(off) => (self.pos, off),
+			SeekFrom::End(off) => (self.size()?, off),
+		};
+		self.pos = base.checked_add_signed(off)
+			.ok_or(co::HRESULT::STG_E_INVALIDFUNCTION)?;
+		Ok(self.pos)
+	}
+}
+
+impl CompoundStream {
+	/// Changes the size of the stream, like
+	/// [`IStream::SetSize`](crate::prelude::ole_IStream::SetSize). If it grows,
+	/// the new bytes are zeroed. The seek pointer isn't changed.
+	pub fn set_size(&self, new_size: u64) -> HrResult<()> {
+		let new_size = usize::try_from(new_size)
+			.map_err(|_| co::HRESULT::STG_E_MEDIUMFULL)?;
+		let mut doc = self.doc.borrow_mut();
+		live_node_mut(&mut doc, self.idx)?.data.resize(new_size, 0);
+		Ok(())
+	}
+
+	/// Returns the size of the stream, in bytes.
+	#[must_use]
+	pub fn size(&self) -> HrResult<u64> {
+		let doc = self.doc.borrow();
+		Ok(live_node(&doc, self.idx)?.data.len() as _)
+	}
+
+	/// Returns information about this stream, like
+	/// [`IStream::Stat`](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-istream-stat).
+	#[must_use]
+	pub fn stat(&self) -> HrResult<CompoundEntry> {
+		let doc = self.doc.borrow();
+		Ok(CompoundEntry::from_node(live_node(&doc, self.idx)?))
+	}
+}
+
+//------------------------------------------------------------------------------
+
+fn live_node(doc: &CfbDoc, idx: usize) -> HrResult<&CfbNode> {
+	let node = &doc.nodes[idx];
+	if node.alive { Ok(node) } else { Err(co::HRESULT::STG_E_REVERTED) }
+}
+
+fn live_node_mut(doc: &mut CfbDoc, idx: usize) -> HrResult<&mut CfbNode> {
+	let node = &mut doc.nodes[idx];
+	if node.alive { Ok(node) } else { Err(co::HRESULT::STG_E_REVERTED) }
+}
+
+/// Returns the index of the element with the given name, in a case-insensitive
+/// comparison.
+fn find_child(doc: &CfbDoc, parent: usize, name: &str) -> HrResult<usize> {
+	live_node(doc, parent)?
+		.children.iter()
+		.copied()
+		.find(|idx| cmp_names(&doc.nodes[*idx].name, name).is_eq())
+		.ok_or(co::HRESULT::STG_E_FILENOTFOUND)
+}
@@ -0,0 +1,5 @@
+mod cfb;
+mod compound_file;
+
+pub use compound_file::{CompoundEntry, CompoundFile, CompoundStorage,
+	CompoundStream};
@@ -1,3 +1,92 @@
+const_bitflag! { DISPATCH: u16;
+	/// [`IDispatch::Invoke`](crate::prelude::oleaut_IDispatch::Invoke) `flags`
+	/// (`u16`).
+	=>
+	=>
+	/// The member is invoked as a method.
+	METHOD 0x1
+	/// The member is retrieved as a property or data member.
+	PROPERTYGET 0x2
+	/// The member is changed as a property or data member.
+	PROPERTYPUT 0x4
+	/// The member is changed by a reference assignment, rather than a value
+	/// assignment.
+	PROPERTYPUTREF 0x8
+}
+
+const_ordinary! { DISPID: i32;
+	/// Predefined
+	/// [`DISPID`](https://learn.microsoft.com/en-us/windows/win32/com/dispatch-identifier-constants)
+	/// constants (`i32`).
+	=>
+	=>
+	/// The default member of the object.
+	VALUE 0
+	/// Returned by
+	/// [`IDispatch::GetIDsOfNames`](crate::prelude::oleaut_IDispatch::GetIDsOfNames)
+	/// when a name is unknown.
+	UNKNOWN -1
+	/// The named argument holding the new value of a property, when calling
+	/// [`IDispatch::Invoke`](crate::prelude::oleaut_IDispatch::Invoke) with
+	/// [`co::DISPATCH::PROPERTYPUT`](crate::co::DISPATCH::PROPERTYPUT).
+	PROPERTYPUT -3
+	/// The `_NewEnum` method of collections, which returns an enumerator.
+	NEWENUM -4
+	/// The `Evaluate` method, implicitly invoked when the script uses square
+	/// brackets.
+	EVALUATE -5
+	/// The constructor of the object.
+	CONSTRUCTOR -6
+	/// The destructor of the object.
+	DESTRUCTOR -7
+	/// The `Collect` property, for collections.
+	COLLECT -8
+}
+
+const_bitflag! { PVCHF: u32;
+	/// [`PROPVARIANT::PropVariantChangeType`](crate::PROPVARIANT::PropVariantChangeType)
+	/// `flags` (`u32`).
+	=>
+	=>
+	DEFAULT 0x0000_0000
+	/// Don't coerce an object with a default property into a value.
+	NOVALUEPROP 0x0000_0001
+	/// Converts a `bool` into the strings `"True"` and `"False"`, instead of
+	/// `"-1"` and `"0"`.
+	ALPHABOOL 0x0000_0002
+	/// Uses the system default locale settings, ignoring the user overrides.
+	NOUSEROVERRIDE 0x0000_0004
+	/// Converts a `bool` into localized strings.
+	LOCALBOOL 0x0000_0008
+	/// Don't convert a string into a number when the string is hexadecimal.
+	NOHEXSTRING 0x0000_0010
+}
+
+const_bitflag! { VARIANT: u16;
+	/// [`VARIANT::VariantChangeType`](crate::VARIANT::VariantChangeType)
+	/// `flags` (`u16`).
+	=>
+	=>
+	/// Don't coerce an object with a default property into a value.
+	NOVALUEPROP 0x01
+	/// Converts a `bool` into the strings `"True"` and `"False"`, instead of
+	/// `"-1"` and `"0"`.
+	ALPHABOOL 0x02
+	/// Uses the system default locale settings, ignoring the user overrides.
+	NOUSEROVERRIDE 0x04
+	/// Uses the Hijri calendar for date conversions.
+	CALENDAR_HIJRI 0x08
+	/// Converts a `bool` into localized strings.
+	LOCALBOOL 0x10
+	/// Uses the Thai calendar for date conversions.
+	CALENDAR_THAI 0x20
+	/// Uses the Gregorian calendar for date conversions.
+	CALENDAR_GREGORIAN 0x40
+	/// Uses the National Language Support (NLS) functions for string
+	/// conversions.
+	USE_NLS 0x80
+}
+
 const_bitflag! { VT: u16;
 	/// [`VARENUM`](https://learn.microsoft.com/en-us/windows/win32/api/wtypes/ne-wtypes-varenum)
 	/// enumeration (`u16`).
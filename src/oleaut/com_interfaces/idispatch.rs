@@ -1,5 +1,7 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use std::mem::ManuallyDrop;
+
 use crate::co;
 use crate::decl::*;
 use crate::kernel::{ffi_types::*, privs::*};
@@ -90,4 +92,106 @@ pub trait oleaut_IDispatch: ole_IUnknown {
 			},
 		).map(|_| queried)
 	}
+
+	/// [`IDispatch::Invoke`](https://learn.microsoft.com/en-us/windows/win32/api/oaidl/nf-oaidl-idispatch-invoke)
+	/// method.
+	///
+	/// The positional `args` are passed in their natural order – they're
+	/// reversed internally, as `Invoke` expects. The `named_args` are pairs of
+	/// argument DISPID and value, and they come after the positional ones. To
+	/// set a property, pass its new value as a named argument with
+	/// [`co::DISPID::PROPERTYPUT`](crate::co::DISPID::PROPERTYPUT).
+	///
+	/// If the member raises an exception, the returned
+	/// [`DispatchError`](crate::DispatchError) carries the information of the
+	/// [`EXCEPINFO`](crate::EXCEPINFO) struct.
+	///
+	/// For a higher-level API, which looks up and caches the DISPIDs, see
+	/// [`DispatchObject`](crate::DispatchObject).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let obj: w::IDispatch; // initialized somewhere
+	/// # let obj = unsafe { w::IDispatch::null() };
+	///
+	/// let ids = obj.GetIDsOfNames(&["Visible"], w::LCID::USER_DEFAULT)?;
+	/// obj.Invoke(
+	///     ids[0],
+	///     w::LCID::USER_DEFAULT,
+	///     co::DISPATCH::PROPERTYPUT,
+	///     &[],
+	///     &[(co::DISPID::PROPERTYPUT.raw(), &w::VARIANT::new_bool(true))],
+	/// )?;
+	/// # Ok::<_, w::DispatchError>(())
+	/// ```
+	fn Invoke(&self,
+		disp_id: i32,
+		lcid: LCID,
+		flags: co::DISPATCH,
+		args: &[VARIANT],
+		named_args: &[(i32, &VARIANT)],
+	) -> Result<VARIANT, DispatchError>
+	{
+		// Shallow copies, the values are still owned by the caller.
+		let rgvarg = named_args.iter()
+			.map(|(_, var)| *var)
+			.chain(args.iter().rev())
+			.map(|var| ManuallyDrop::new(unsafe { std::ptr::read(var) }))
+			.collect::<Vec<_>>();
+		let named_ids = named_args.iter()
+			.map(|(id, _)| *id)
+			.collect::<Vec<_>>();
+
+		let params = DISPPARAMS {
+			rgvarg: vec_ptr(&rgvarg) as _,
+			rgdispidNamedArgs: vec_ptr(&named_ids) as _,
+			cArgs: rgvarg.len() as _,
+			cNamedArgs: named_ids.len() as _,
+		};
+
+		let is_put = flags.has(co::DISPATCH::PROPERTYPUT)
+			|| flags.has(co::DISPATCH::PROPERTYPUTREF);
+		let mut result = VARIANT::default();
+		let mut excep_info = EXCEPINFO::default();
+		let mut arg_err = u32::MAX;
+
+		match ok_to_hrresult(
+			unsafe {
+				(vt::<IDispatchVT>(self).Invoke)(
+					self.ptr(),
+					disp_id,
+					&co::IID::default() as *const _ as _,
+					lcid.into(),
+					flags.raw(),
+					&params as *const _ as _,
+					if is_put { std::ptr::null_mut() } else { &mut result as *mut _ as _ },
+					&mut excep_info as *mut _ as _,
+					&mut arg_err,
+				)
+			},
+		) {
+			Ok(_) => Ok(result),
+			Err(co::HRESULT::DISP_E_EXCEPTION) => {
+				excep_info.deferred_fill_in();
+				Err(DispatchError::from_excep_info(&excep_info))
+			},
+			Err(hr) => {
+				let mut err = DispatchError::from(hr);
+				if matches!(hr, co::HRESULT::DISP_E_TYPEMISMATCH | co::HRESULT::DISP_E_PARAMNOTFOUND)
+					&& (arg_err as usize) < rgvarg.len()
+				{
+					let idx = arg_err as usize; // index within rgvarg
+					err.arg_err = Some(if idx < named_args.len() {
+						args.len() + idx
+					} else {
+						args.len() - 1 - (idx - named_args.len())
+					});
+				}
+				Err(err)
+			},
+		}
+	}
 }
@@ -0,0 +1,317 @@
+use std::mem::ManuallyDrop;
+
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// A high-level representation of the value held by a
+/// [`VARIANT`](crate::VARIANT) or a [`PROPVARIANT`](crate::PROPVARIANT),
+/// which can be converted from and to both with
+/// [`TryFrom`](std::convert::TryFrom).
+///
+/// Some values can be held by only one of the types: converting them to the
+/// other one fails with
+/// [`co::HRESULT::DISP_E_BADVARTYPE`](crate::co::HRESULT::DISP_E_BADVARTYPE).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let pv = w::PROPVARIANT::try_from(&w::VariantValue::Lpwstr("abc".to_owned()))?;
+///
+/// if let w::VariantValue::Lpwstr(s) = w::VariantValue::try_from(&pv)? {
+///     println!("{}", s);
+/// }
+/// # Ok::<_, winsafe::co::HRESULT>(())
+/// ```
+#[derive(Clone)]
+pub enum VariantValue {
+	/// No value, [`VT::EMPTY`](crate::co::VT::EMPTY).
+	Empty,
+	/// SQL style null, [`VT::NULL`](crate::co::VT::NULL).
+	Null,
+	/// [`VT::BOOL`](crate::co::VT::BOOL).
+	Bool(bool),
+	/// [`VT::I1`](crate::co::VT::I1).
+	I8(i8),
+	/// [`VT::I2`](crate::co::VT::I2).
+	I16(i16),
+	/// [`VT::I4`](crate::co::VT::I4), also read from
+	/// [`VT::INT`](crate::co::VT::INT).
+	I32(i32),
+	/// [`VT::I8`](crate::co::VT::I8).
+	I64(i64),
+	/// [`VT::UI1`](crate::co::VT::UI1).
+	U8(u8),
+	/// [`VT::UI2`](crate::co::VT::UI2).
+	U16(u16),
+	/// [`VT::UI4`](crate::co::VT::UI4), also read from
+	/// [`VT::UINT`](crate::co::VT::UINT).
+	U32(u32),
+	/// [`VT::UI8`](crate::co::VT::UI8).
+	U64(u64),
+	/// [`VT::R4`](crate::co::VT::R4).
+	F32(f32),
+	/// [`VT::R8`](crate::co::VT::R8).
+	F64(f64),
+	/// [`VT::CY`](crate::co::VT::CY).
+	Cy(CY),
+	/// Raw OLE automation date, [`VT::DATE`](crate::co::VT::DATE). Can be
+	/// converted with
+	/// [`SYSTEMTIME::from_ole_date`](crate::SYSTEMTIME::from_ole_date).
+	Date(f64),
+	/// [`VT::DECIMAL`](crate::co::VT::DECIMAL).
+	Decimal(DECIMAL),
+	/// Error code, [`VT::ERROR`](crate::co::VT::ERROR).
+	Error(co::HRESULT),
+	/// [`VT::BSTR`](crate::co::VT::BSTR).
+	Bstr(String),
+	/// [`VT::LPWSTR`](crate::co::VT::LPWSTR), only in
+	/// [`PROPVARIANT`](crate::PROPVARIANT).
+	Lpwstr(String),
+	/// [`VT::FILETIME`](crate::co::VT::FILETIME), only in
+	/// [`PROPVARIANT`](crate::PROPVARIANT).
+	FileTime(FILETIME),
+	/// [`VT::CLSID`](crate::co::VT::CLSID), only in
+	/// [`PROPVARIANT`](crate::PROPVARIANT).
+	Clsid(GUID),
+	/// [`VT::UNKNOWN`](crate::co::VT::UNKNOWN), only in
+	/// [`VARIANT`](crate::VARIANT).
+	Unknown(IUnknown),
+	/// [`VT::DISPATCH`](crate::co::VT::DISPATCH), only in
+	/// [`VARIANT`](crate::VARIANT).
+	Dispatch(IDispatch),
+}
+
+impl std::fmt::Debug for VariantValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Empty => write!(f, "Empty"),
+			Self::Null => write!(f, "Null"),
+			Self::Bool(v) => write!(f, "Bool({:?})", v),
+			Self::I8(v) => write!(f, "I8({:?})", v),
+			Self::I16(v) => write!(f, "I16({:?})", v),
+			Self::I32(v) => write!(f, "I32({:?})", v),
+			Self::I64(v) => write!(f, "I64({:?})", v),
+			Self::U8(v) => write!(f, "U8({:?})", v),
+			Self::U16(v) => write!(f, "U16({:?})", v),
+			Self::U32(v) => write!(f, "U32({:?})", v),
+			Self::U64(v) => write!(f, "U64({:?})", v),
+			Self::F32(v) => write!(f, "F32({:?})", v),
+			Self::F64(v) => write!(f, "F64({:?})", v),
+			Self::Cy(v) => write!(f, "Cy({})", v),
+			Self::Date(v) => write!(f, "Date({:?})", v),
+			Self::Decimal(v) => write!(f, "Decimal({:?})", v),
+			Self::Error(v) => write!(f, "Error({:?})", v),
+			Self::Bstr(v) => write!(f, "Bstr({:?})", v),
+			Self::Lpwstr(v) => write!(f, "Lpwstr({:?})", v),
+			Self::FileTime(v) => write!(f, "FileTime({})", v.ticks()),
+			Self::Clsid(v) => write!(f, "Clsid({})", v),
+			Self::Unknown(v) => write!(f, "Unknown({:p})", v.ptr()),
+			Self::Dispatch(v) => write!(f, "Dispatch({:p})", v.ptr()),
+		}
+	}
+}
+
+impl TryFrom<&VARIANT> for VariantValue {
+	type Error = co::HRESULT;
+
+	/// Reads the value held by the `VARIANT`. If it holds a
+	/// [`co::VT::BYREF`](crate::co::VT::BYREF) reference, the referenced value
+	/// is read.
+	///
+	/// Fails with
+	/// [`co::HRESULT::DISP_E_BADVARTYPE`](crate::co::HRESULT::DISP_E_BADVARTYPE)
+	/// if the type is not supported.
+	fn try_from(var: &VARIANT) -> HrResult<Self> {
+		if let Some(val) = common_value(var) {
+			return Ok(val);
+		}
+
+		match var.vt() {
+			co::VT::DECIMAL => Ok(Self::Decimal(var.decimal().unwrap())),
+			co::VT::UNKNOWN => Ok(Self::Unknown(var.iunknown().unwrap())),
+			co::VT::DISPATCH => Ok(Self::Dispatch(var.idispatch().unwrap())),
+			_ => match var.byref() {
+				Some((vt, ptr)) => byref_value(vt, ptr),
+				None => Err(co::HRESULT::DISP_E_BADVARTYPE),
+			},
+		}
+	}
+}
+
+impl TryFrom<&VariantValue> for VARIANT {
+	type Error = co::HRESULT;
+
+	/// Fails with
+	/// [`co::HRESULT::DISP_E_BADVARTYPE`](crate::co::HRESULT::DISP_E_BADVARTYPE)
+	/// if the value can't be held by a `VARIANT`.
+	fn try_from(val: &VariantValue) -> Result<Self, Self::Error> {
+		if let Some(var) = common_new(val) {
+			return var;
+		}
+
+		match val {
+			VariantValue::Decimal(v) => Ok(Self::new_decimal(v)),
+			VariantValue::Unknown(v) => Ok(Self::new_iunknown::<IUnknown>(v)),
+			VariantValue::Dispatch(v) => Ok(Self::new_idispatch(v)),
+			_ => Err(co::HRESULT::DISP_E_BADVARTYPE),
+		}
+	}
+}
+
+impl TryFrom<&PROPVARIANT> for VariantValue {
+	type Error = co::HRESULT;
+
+	/// Reads the value held by the `PROPVARIANT`.
+	///
+	/// Fails with
+	/// [`co::HRESULT::DISP_E_BADVARTYPE`](crate::co::HRESULT::DISP_E_BADVARTYPE)
+	/// if the type is not supported.
+	fn try_from(pv: &PROPVARIANT) -> HrResult<Self> {
+		if let Some(val) = common_value(pv) {
+			return Ok(val);
+		}
+
+		match pv.vt() {
+			co::VT::DECIMAL => Ok(Self::Decimal(pv.decimal().unwrap())),
+			co::VT::LPWSTR => Ok(Self::Lpwstr(pv.lpwstr().unwrap())),
+			co::VT::FILETIME => Ok(Self::FileTime(pv.filetime().unwrap())),
+			co::VT::CLSID => Ok(Self::Clsid(pv.clsid().unwrap())),
+			_ => Err(co::HRESULT::DISP_E_BADVARTYPE),
+		}
+	}
+}
+
+impl TryFrom<&VariantValue> for PROPVARIANT {
+	type Error = co::HRESULT;
+
+	/// Fails with
+	/// [`co::HRESULT::DISP_E_BADVARTYPE`](crate::co::HRESULT::DISP_E_BADVARTYPE)
+	/// if the value can't be held by a `PROPVARIANT`.
+	fn try_from(val: &VariantValue) -> Result<Self, Self::Error> {
+		if let Some(pv) = common_new(val) {
+			return pv;
+		}
+
+		match val {
+			VariantValue::Decimal(v) => Ok(Self::new_decimal(v)),
+			VariantValue::Lpwstr(v) => Self::new_lpwstr(v),
+			VariantValue::FileTime(v) => Ok(Self::new_filetime(v)),
+			VariantValue::Clsid(v) => Self::new_clsid(v),
+			_ => Err(co::HRESULT::DISP_E_BADVARTYPE),
+		}
+	}
+}
+
+impl VariantValue {
+	/// Returns the [`co::VT`](crate::co::VT) which holds this value.
+	#[must_use]
+	pub const fn vt(&self) -> co::VT {
+		match self {
+			Self::Empty => co::VT::EMPTY,
+			Self::Null => co::VT::NULL,
+			Self::Bool(_) => co::VT::BOOL,
+			Self::I8(_) => co::VT::I1,
+			Self::I16(_) => co::VT::I2,
+			Self::I32(_) => co::VT::I4,
+			Self::I64(_) => co::VT::I8,
+			Self::U8(_) => co::VT::UI1,
+			Self::U16(_) => co::VT::UI2,
+			Self::U32(_) => co::VT::UI4,
+			Self::U64(_) => co::VT::UI8,
+			Self::F32(_) => co::VT::R4,
+			Self::F64(_) => co::VT::R8,
+			Self::Cy(_) => co::VT::CY,
+			Self::Date(_) => co::VT::DATE,
+			Self::Decimal(_) => co::VT::DECIMAL,
+			Self::Error(_) => co::VT::ERROR,
+			Self::Bstr(_) => co::VT::BSTR,
+			Self::Lpwstr(_) => co::VT::LPWSTR,
+			Self::FileTime(_) => co::VT::FILETIME,
+			Self::Clsid(_) => co::VT::CLSID,
+			Self::Unknown(_) => co::VT::UNKNOWN,
+			Self::Dispatch(_) => co::VT::DISPATCH,
+		}
+	}
+}
+
+/// Reads the values which are stored in the same way by `VARIANT` and
+/// `PROPVARIANT`.
+fn common_value(var: &impl oleaut_Variant) -> Option<VariantValue> {
+	Some(match var.vt() {
+		co::VT::EMPTY => VariantValue::Empty,
+		co::VT::NULL => VariantValue::Null,
+		co::VT::BOOL => VariantValue::Bool(var.bool()?),
+		co::VT::I1 => VariantValue::I8(var.i8()?),
+		co::VT::I2 => VariantValue::I16(var.i16()?),
+		co::VT::I4 | co::VT::INT => VariantValue::I32(var.i32()?),
+		co::VT::I8 => VariantValue::I64(var.i64()?),
+		co::VT::UI1 => VariantValue::U8(var.u8()?),
+		co::VT::UI2 => VariantValue::U16(var.u16()?),
+		co::VT::UI4 | co::VT::UINT => VariantValue::U32(var.u32()?),
+		co::VT::UI8 => VariantValue::U64(var.u64()?),
+		co::VT::R4 => VariantValue::F32(var.f32()?),
+		co::VT::R8 => VariantValue::F64(var.f64()?),
+		co::VT::CY => VariantValue::Cy(var.cy()?),
+		co::VT::DATE => VariantValue::Date(var.date()?),
+		co::VT::ERROR => VariantValue::Error(var.error()?),
+		co::VT::BSTR => VariantValue::Bstr(var.bstr()?),
+		_ => return None,
+	})
+}
+
+/// Creates the values which are stored in the same way by `VARIANT` and
+/// `PROPVARIANT`.
+fn common_new<T: oleaut_Variant>(val: &VariantValue) -> Option<HrResult<T>> {
+	Some(Ok(match val {
+		VariantValue::Empty => T::default(),
+		VariantValue::Null => unsafe { T::from_raw(co::VT::NULL, &[]) },
+		VariantValue::Bool(v) => T::new_bool(*v),
+		VariantValue::I8(v) => T::new_i8(*v),
+		VariantValue::I16(v) => T::new_i16(*v),
+		VariantValue::I32(v) => T::new_i32(*v),
+		VariantValue::I64(v) => T::new_i64(*v),
+		VariantValue::U8(v) => T::new_u8(*v),
+		VariantValue::U16(v) => T::new_u16(*v),
+		VariantValue::U32(v) => T::new_u32(*v),
+		VariantValue::U64(v) => T::new_u64(*v),
+		VariantValue::F32(v) => T::new_f32(*v),
+		VariantValue::F64(v) => T::new_f64(*v),
+		VariantValue::Cy(v) => T::new_cy(*v),
+		VariantValue::Date(v) => T::new_date(*v),
+		VariantValue::Error(v) => T::new_error(*v),
+		VariantValue::Bstr(v) => return Some(T::new_bstr(v)),
+		_ => return None,
+	}))
+}
+
+/// Reads the value referenced by a `VARIANT` holding `VT::BYREF`.
+fn byref_value(
+	vt: co::VT,
+	ptr: *mut std::ffi::c_void,
+) -> HrResult<VariantValue>
+{
+	if ptr.is_null() {
+		return Err(co::HRESULT::E_POINTER);
+	}
+
+	let sz = match vt {
+		co::VT::VARIANT => return VariantValue::try_from(unsafe { &*(ptr as *const VARIANT) }),
+		co::VT::DECIMAL => return Ok(VariantValue::Decimal(
+			unsafe { std::ptr::read_unaligned(ptr as *const DECIMAL) },
+		)),
+		co::VT::I1 | co::VT::UI1 => 1,
+		co::VT::I2 | co::VT::UI2 | co::VT::BOOL => 2,
+		co::VT::I4 | co::VT::UI4 | co::VT::INT | co::VT::UINT
+			| co::VT::R4 | co::VT::ERROR => 4,
+		co::VT::I8 | co::VT::UI8 | co::VT::R8 | co::VT::CY | co::VT::DATE
+			| co::VT::BSTR | co::VT::UNKNOWN | co::VT::DISPATCH => 8,
+		_ => return Err(co::HRESULT::DISP_E_BADVARTYPE),
+	};
+
+	let data = unsafe { std::slice::from_raw_parts(ptr as *const u8, sz) };
+	let var = ManuallyDrop::new(unsafe { VARIANT::from_raw(vt, data) }); // the referenced value is not owned
+	VariantValue::try_from(&*var)
+}
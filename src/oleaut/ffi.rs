@@ -9,16 +9,29 @@ extern_sys! { "ole32";
 extern_sys! { "oleaut32";
 	OleLoadPicture(COMPTR, i32, BOOL, PCVOID, *mut COMPTR) -> HRES
 	OleLoadPicturePath(PCSTR, COMPTR, u32, u32, PCVOID, *mut COMPTR) -> HRES
+	SafeArrayAccessData(PVOID, *mut PVOID) -> HRES
+	SafeArrayCopy(PVOID, *mut PVOID) -> HRES
+	SafeArrayCreate(u16, u32, PCVOID) -> PVOID
+	SafeArrayCreateVector(u16, i32, u32) -> PVOID
+	SafeArrayDestroy(PVOID) -> HRES
+	SafeArrayGetDim(PVOID) -> u32
+	SafeArrayGetElemsize(PVOID) -> u32
+	SafeArrayGetLBound(PVOID, u32, *mut i32) -> HRES
+	SafeArrayGetUBound(PVOID, u32, *mut i32) -> HRES
+	SafeArrayGetVartype(PVOID, *mut u16) -> HRES
+	SafeArrayUnaccessData(PVOID) -> HRES
 	SysAllocString(PCSTR) -> PSTR
 	SysFreeString(PSTR)
 	SysReAllocString(PSTR, PCSTR) -> PSTR
 	SysStringLen(PSTR) -> u32
 	SystemTimeToVariantTime(PVOID, *mut f64) -> i32
+	VariantChangeType(PVOID, PCVOID, u16, u16) -> HRES
 	VariantClear(PVOID) -> HRES
 	VariantInit(PVOID)
 	VariantTimeToSystemTime(f64, PVOID)  -> i32
 }
 
 extern_sys! { "propsys";
+	PropVariantChangeType(PVOID, PCVOID, u32, u16) -> HRES
 	PSGetNameFromPropertyKey(PCVOID, *mut PSTR) -> HRES
 }
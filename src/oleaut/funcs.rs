@@ -94,7 +94,8 @@ pub fn PSGetNameFromPropertyKey(prop_key: &PROPERTYKEY) -> HrResult<String> {
 /// [`VariantTimeToSystemTime`](crate::VariantTimeToSystemTime).
 ///
 /// Note that this function resolves the time to one second; milliseconds are
-/// ignored.
+/// ignored. To keep the milliseconds, use
+/// [`SYSTEMTIME::to_ole_date`](crate::SYSTEMTIME::to_ole_date).
 #[must_use]
 pub fn SystemTimeToVariantTime(st: &SYSTEMTIME) -> SysResult<f64> {
 	let mut double = f64::default();
@@ -109,6 +110,10 @@ pub fn SystemTimeToVariantTime(st: &SYSTEMTIME) -> SysResult<f64> {
 /// [`VariantTimeToSystemTime`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-varianttimetosystemtime)
 /// function. The inverse operation is performed by
 /// [`SystemTimeToVariantTime`](SystemTimeToVariantTime).
+///
+/// Note that this function rounds the time to one second. To keep the
+/// milliseconds, use
+/// [`SYSTEMTIME::from_ole_date`](crate::SYSTEMTIME::from_ole_date).
 #[must_use]
 pub fn VariantTimeToSystemTime(
 	var_time: f64,
@@ -0,0 +1,65 @@
+use crate::decl::*;
+use crate::oleaut::ffi;
+use crate::prelude::*;
+
+/// RAII implementation for [`SafeArray`](crate::SafeArray) data access which
+/// automatically calls
+/// [`SafeArrayUnaccessData`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearrayunaccessdata)
+/// when the object goes out of scope.
+pub struct SafeArrayUnaccessDataGuard<'a, T>
+	where T: SafeArrayElement,
+{
+	arr: &'a mut SafeArray<T>,
+	pdata: *mut T,
+	len: usize,
+}
+
+impl<'a, T> Drop for SafeArrayUnaccessDataGuard<'a, T>
+	where T: SafeArrayElement,
+{
+	fn drop(&mut self) {
+		unsafe { ffi::SafeArrayUnaccessData(self.arr.as_ptr()); } // ignore errors
+	}
+}
+
+impl<'a, T> SafeArrayUnaccessDataGuard<'a, T>
+	where T: SafeArrayElement,
+{
+	/// Constructs the guard.
+	///
+	/// # Safety
+	///
+	/// Be sure the array must be unlocked with
+	/// [`SafeArrayUnaccessData`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearrayunaccessdata)
+	/// at the end of scope, the pointer is valid, and the number of elements is
+	/// correct.
+	#[must_use]
+	pub const unsafe fn new(
+		arr: &'a mut SafeArray<T>,
+		pdata: *mut T,
+		len: usize,
+	) -> Self
+	{
+		Self { arr, pdata, len }
+	}
+
+	/// Returns a slice over the elements of the array.
+	#[must_use]
+	pub const fn as_slice(&self) -> &[T] {
+		if self.len == 0 {
+			&[]
+		} else {
+			unsafe { std::slice::from_raw_parts(self.pdata, self.len) }
+		}
+	}
+
+	/// Returns a mutable slice over the elements of the array.
+	#[must_use]
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		if self.len == 0 {
+			&mut []
+		} else {
+			unsafe { std::slice::from_raw_parts_mut(self.pdata, self.len) }
+		}
+	}
+}
@@ -1,18 +1,23 @@
 #![cfg(feature = "oleaut")]
 
 mod com_interfaces;
+mod enums;
 mod funcs;
 mod structs;
+mod utilities;
 
 pub(in crate::oleaut) mod ffi;
 pub(in crate::oleaut) mod iterators;
 pub(crate) mod privs;
 pub mod co;
+pub mod guard;
 
 pub mod decl {
 	pub use super::com_interfaces::decl::*;
+	pub use super::enums::*;
 	pub use super::funcs::*;
 	pub use super::structs::decl::*;
+	pub use super::utilities::*;
 }
 
 pub mod traits {
@@ -52,7 +52,8 @@ impl BSTR {
 	#[must_use]
 	pub fn SysAllocString(s: &str) -> HrResult<Self> {
 		let str_obj = WString::from_str(s);
-		let ptr = unsafe { ffi::SysAllocString(str_obj.as_ptr()) };
+		let wstr: &WStr = &str_obj; // never null, even if empty
+		let ptr = unsafe { ffi::SysAllocString(wstr.as_ptr()) };
 		if ptr.is_null() {
 			Err(co::HRESULT::E_OUTOFMEMORY)
 		} else {
@@ -66,7 +67,8 @@ impl BSTR {
 	/// The underlying pointer is automatically updated.
 	pub fn SysReAllocString(&mut self, s: &str) -> HrResult<()> {
 		let str_obj = WString::from_str(s);
-		let ptr = unsafe { ffi::SysReAllocString(self.0, str_obj.as_ptr()) };
+		let wstr: &WStr = &str_obj; // never null, even if empty
+		let ptr = unsafe { ffi::SysReAllocString(self.0, wstr.as_ptr()) };
 		if ptr.is_null() {
 			Err(co::HRESULT::E_OUTOFMEMORY)
 		} else {
@@ -1,6 +1,7 @@
 mod bstr;
 mod others;
 mod propvariant;
+mod safe_array;
 mod variant;
 mod variant_traits;
 
@@ -8,9 +9,11 @@ pub mod decl {
 	pub use super::bstr::BSTR;
 	pub use super::others::*;
 	pub use super::propvariant::PROPVARIANT;
+	pub use super::safe_array::SafeArray;
 	pub use super::variant::VARIANT;
 }
 
 pub mod traits {
+	pub use super::safe_array::SafeArrayElement;
 	pub use super::variant_traits::*;
 }
@@ -1,6 +1,120 @@
+#![allow(non_camel_case_types, non_snake_case)]
+
+use crate::co;
 use crate::decl::*;
 use crate::oleaut::privs::*;
 
+/// [`CY`](https://learn.microsoft.com/en-us/windows/win32/api/wtypes/ns-wtypes-cy-r1)
+/// struct.
+///
+/// A currency value, stored as an integer scaled by 10,000, so it has 4 digits
+/// after the decimal point.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct CY {
+	pub int64: i64,
+}
+
+impl std::fmt::Display for CY {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let sign = if self.int64 < 0 { "-" } else { "" };
+		let abs = self.int64.unsigned_abs();
+		write!(f, "{}{}.{:04}", sign, abs / 10_000, abs % 10_000)
+	}
+}
+
+impl CY {
+	/// Creates a new `CY` from the integer and the 4-digit fractional parts,
+	/// so that `CY::new(12, 3400)` is `12.34`.
+	#[must_use]
+	pub const fn new(units: i64, ten_thousandths: i64) -> Self {
+		Self { int64: units * 10_000 + ten_thousandths }
+	}
+}
+
+/// [`DECIMAL`](https://learn.microsoft.com/en-us/windows/win32/api/wtypes/ns-wtypes-decimal-r1)
+/// struct.
+///
+/// A 96-bit unsigned integer, with a sign and a scaling factor, which is the
+/// power of 10 dividing the integer.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DECIMAL {
+	wReserved: u16,
+	pub scale: u8,
+	pub sign: u8,
+	pub Hi32: u32,
+	pub Lo64: u64,
+}
+
+impl DECIMAL {
+	/// The `sign` value of negative numbers.
+	pub const SIGN: u8 = 0x80;
+
+	/// Creates a new `DECIMAL`, where `scale` must be between 0 and 28.
+	#[must_use]
+	pub const fn new(negative: bool, scale: u8, hi32: u32, lo64: u64) -> Self {
+		Self {
+			wReserved: 0,
+			scale,
+			sign: if negative { Self::SIGN } else { 0 },
+			Hi32: hi32,
+			Lo64: lo64,
+		}
+	}
+
+	/// Returns `true` if the sign bit is set.
+	#[must_use]
+	pub const fn is_negative(&self) -> bool {
+		self.sign & Self::SIGN != 0
+	}
+}
+
+/// [`DISPPARAMS`](https://learn.microsoft.com/en-us/windows/win32/api/oaidl/ns-oaidl-dispparams)
+/// struct.
+///
+/// The arguments are stored in reverse order, with the named ones first.
+#[repr(C)]
+pub struct DISPPARAMS {
+	pub rgvarg: *mut VARIANT,
+	pub rgdispidNamedArgs: *mut i32,
+	pub cArgs: u32,
+	pub cNamedArgs: u32,
+}
+
+impl_default!(DISPPARAMS);
+
+/// [`EXCEPINFO`](https://learn.microsoft.com/en-us/windows/win32/api/oaidl/ns-oaidl-excepinfo)
+/// struct.
+///
+/// Filled by [`IDispatch::Invoke`](crate::prelude::oleaut_IDispatch::Invoke)
+/// when the invoked member raises an exception. The strings are released when
+/// the object goes out of scope.
+#[repr(C)]
+pub struct EXCEPINFO {
+	pub wCode: u16,
+	wReserved: u16,
+	pub bstrSource: BSTR,
+	pub bstrDescription: BSTR,
+	pub bstrHelpFile: BSTR,
+	pub dwHelpContext: u32,
+	pvReserved: *mut std::ffi::c_void,
+	pfnDeferredFillIn: Option<unsafe extern "system" fn(*mut EXCEPINFO) -> i32>,
+	pub scode: co::HRESULT,
+}
+
+impl_default!(EXCEPINFO);
+
+impl EXCEPINFO {
+	/// If the server deferred filling the fields, calls its fill-in function,
+	/// so the fields can be read.
+	pub(in crate::oleaut) fn deferred_fill_in(&mut self) {
+		if let Some(pfn) = self.pfnDeferredFillIn.take() {
+			unsafe { pfn(self); } // ignore errors
+		}
+	}
+}
+
 /// [`PROPERTYKEY`](https://learn.microsoft.com/en-us/windows/win32/api/wtypes/ns-wtypes-propertykey)
 /// struct.
 #[repr(C)]
@@ -19,3 +133,55 @@ impl PROPERTYKEY {
 		Self { fmtid, pid: PID_FIRST_USABLE }
 	}
 }
+
+/// [`SAFEARRAYBOUND`](https://learn.microsoft.com/en-us/windows/win32/api/oaidl/ns-oaidl-safearraybound)
+/// struct.
+#[repr(C)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SAFEARRAYBOUND {
+	pub cElements: u32,
+	pub lLbound: i32,
+}
+
+impl SAFEARRAYBOUND {
+	/// Creates a new `SAFEARRAYBOUND`, with the number of elements and the
+	/// lower bound of the dimension.
+	#[must_use]
+	pub const fn new(num_elements: u32, lower_bound: i32) -> Self {
+		Self { cElements: num_elements, lLbound: lower_bound }
+	}
+
+	/// Returns the upper bound of the dimension, which is inclusive.
+	#[must_use]
+	pub const fn upper_bound(&self) -> i32 {
+		self.lLbound + self.cElements as i32 - 1
+	}
+}
+
+/// [`VARIANT_BOOL`](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-oaut/7b39eb24-9d39-498a-bcd8-75c38e5823d0)
+/// type, a 16-bit boolean where `true` is `-1`.
+///
+/// Used as the element of a [`SafeArray`](crate::SafeArray) holding
+/// [`co::VT::BOOL`](crate::co::VT::BOOL) values.
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct VARIANT_BOOL(i16);
+
+impl From<bool> for VARIANT_BOOL {
+	fn from(val: bool) -> Self {
+		if val { Self::TRUE } else { Self::FALSE }
+	}
+}
+
+impl From<VARIANT_BOOL> for bool {
+	fn from(val: VARIANT_BOOL) -> Self {
+		val.0 != 0
+	}
+}
+
+impl VARIANT_BOOL {
+	/// `VARIANT_FALSE`, which is `0`.
+	pub const FALSE: Self = Self(0);
+	/// `VARIANT_TRUE`, which is `-1`.
+	pub const TRUE: Self = Self(-1);
+}
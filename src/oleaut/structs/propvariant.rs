@@ -1,6 +1,8 @@
 #![allow(non_snake_case)]
 
 use crate::co;
+use crate::decl::*;
+use crate::ole::privs::*;
 use crate::oleaut::ffi;
 use crate::prelude::*;
 
@@ -13,6 +15,9 @@ use crate::prelude::*;
 ///
 /// The [`Default`](std::default::Default) implementation returns a
 /// [`co::VT::EMPTY`](crate::co::VT::EMPTY) value.
+///
+/// The held value can be converted from and to a
+/// [`VariantValue`](crate::VariantValue).
 #[repr(C)]
 pub struct PROPVARIANT {
 	vt: co::VT,
@@ -59,32 +64,139 @@ impl PROPVARIANT {
 	/// Creates a new object holding an `i64` value.
 	#[must_use]
 	pub fn new_i64(val: i64) -> Self {
-		unsafe { Self::from_raw(co::VT::I8, &val.to_ne_bytes()) }
+		<Self as oleaut_Variant>::new_i64(val)
 	}
 
 	/// If the object holds an `i64` value, returns it, otherwise `None`.
 	#[must_use]
 	pub fn i64(&self) -> Option<i64> {
-		if self.vt() == co::VT::I8 {
-			Some(i64::from_ne_bytes(self.raw()[..8].try_into().unwrap()))
-		} else {
-			None
-		}
+		oleaut_Variant::i64(self)
 	}
 
 	/// Creates a new object holding an `u64` value.
 	#[must_use]
 	pub fn new_u64(val: u64) -> Self {
-		unsafe { Self::from_raw(co::VT::UI8, &val.to_ne_bytes()) }
+		<Self as oleaut_Variant>::new_u64(val)
 	}
 
 	/// If the object holds an `u64` value, returns it, otherwise `None`.
 	#[must_use]
 	pub fn u64(&self) -> Option<u64> {
-		if self.vt() == co::VT::UI8 {
-			Some(u64::from_ne_bytes(self.raw()[..8].try_into().unwrap()))
+		oleaut_Variant::u64(self)
+	}
+
+	/// Creates a new object holding a [`GUID`](crate::GUID) class ID value.
+	///
+	/// The `GUID` is copied into memory allocated with
+	/// [`CoTaskMemAlloc`](crate::CoTaskMemAlloc).
+	#[must_use]
+	pub fn new_clsid(val: &GUID) -> HrResult<Self> {
+		let mut pmem = CoTaskMemAlloc(std::mem::size_of::<GUID>())?;
+		unsafe { std::ptr::write_unaligned(pmem.as_mut_ptr() as *mut GUID, *val); }
+		let (ptr, _) = pmem.leak();
+		Ok(unsafe { Self::from_raw(co::VT::CLSID, &(ptr as usize).to_ne_bytes()) })
+	}
+
+	/// If the object holds a [`GUID`](crate::GUID) class ID value, returns it,
+	/// otherwise `None`.
+	#[must_use]
+	pub fn clsid(&self) -> Option<GUID> {
+		if self.vt() == co::VT::CLSID {
+			let ptr = usize::from_ne_bytes(self.raw()[..8].try_into().unwrap());
+			Some(unsafe { std::ptr::read_unaligned(ptr as *const GUID) })
+		} else {
+			None
+		}
+	}
+
+	/// Creates a new object holding a [`DECIMAL`](crate::DECIMAL) value.
+	#[must_use]
+	pub fn new_decimal(val: &DECIMAL) -> Self {
+		let mut obj = Self::default();
+		unsafe { std::ptr::write_unaligned(&mut obj as *mut _ as *mut DECIMAL, *val); }
+		obj.vt = co::VT::DECIMAL; // DECIMAL overlaps the whole PROPVARIANT, its reserved field is the vt
+		obj
+	}
+
+	/// If the object holds a [`DECIMAL`](crate::DECIMAL) value, returns it,
+	/// otherwise `None`.
+	#[must_use]
+	pub fn decimal(&self) -> Option<DECIMAL> {
+		if self.vt() == co::VT::DECIMAL {
+			let dec = unsafe { std::ptr::read_unaligned(self as *const _ as *const DECIMAL) };
+			Some(DECIMAL::new(dec.is_negative(), dec.scale, dec.Hi32, dec.Lo64))
 		} else {
 			None
 		}
 	}
+
+	/// Creates a new object holding a [`FILETIME`](crate::FILETIME) value.
+	#[must_use]
+	pub fn new_filetime(val: &FILETIME) -> Self {
+		unsafe { Self::from_raw(co::VT::FILETIME, &val.ticks().to_ne_bytes()) }
+	}
+
+	/// If the object holds a [`FILETIME`](crate::FILETIME) value, returns it,
+	/// otherwise `None`.
+	#[must_use]
+	pub fn filetime(&self) -> Option<FILETIME> {
+		if self.vt() == co::VT::FILETIME {
+			let ticks = u64::from_ne_bytes(self.raw()[..8].try_into().unwrap());
+			Some(FILETIME::from_ticks(ticks))
+		} else {
+			None
+		}
+	}
+
+	/// Creates a new object holding a null-terminated wide string value.
+	///
+	/// The string is copied into memory allocated with
+	/// [`CoTaskMemAlloc`](crate::CoTaskMemAlloc).
+	#[must_use]
+	pub fn new_lpwstr(val: &str) -> HrResult<Self> {
+		let wstr = WString::from_str(val);
+		let wchars = wstr.as_wchars_with_nul();
+		let mut pmem = CoTaskMemAlloc(std::mem::size_of_val(wchars))?;
+		pmem.as_mut_slice()
+			.chunks_exact_mut(2)
+			.zip(wchars)
+			.for_each(|(dest, ch)| dest.copy_from_slice(&ch.to_ne_bytes()));
+		let (ptr, _) = pmem.leak();
+		Ok(unsafe { Self::from_raw(co::VT::LPWSTR, &(ptr as usize).to_ne_bytes()) })
+	}
+
+	/// If the object holds a null-terminated wide string value, returns it,
+	/// otherwise `None`.
+	#[must_use]
+	pub fn lpwstr(&self) -> Option<String> {
+		if self.vt() == co::VT::LPWSTR {
+			let ptr = usize::from_ne_bytes(self.raw()[..8].try_into().unwrap());
+			Some(unsafe { WString::from_wchars_nullt(ptr as _) }.to_string())
+		} else {
+			None
+		}
+	}
+
+	/// [`PropVariantChangeType`](https://learn.microsoft.com/en-us/windows/win32/api/propvarutil/nf-propvarutil-propvariantchangetype)
+	/// function.
+	///
+	/// Returns a new object with the value coerced to the given type.
+	#[must_use]
+	pub fn PropVariantChangeType(&self,
+		flags: co::PVCHF,
+		vt: co::VT,
+	) -> HrResult<PROPVARIANT>
+	{
+		let mut dest = PROPVARIANT::default();
+		ok_to_hrresult(
+			unsafe {
+				ffi::PropVariantChangeType(
+					&mut dest as *mut _ as _,
+					self as *const _ as _,
+					flags.raw(),
+					vt.raw(),
+				)
+			},
+		).map(|_| dest)
+	}
 }
@@ -0,0 +1,342 @@
+#![allow(non_snake_case)]
+
+use std::marker::PhantomData;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::ole::privs::*;
+use crate::oleaut::ffi;
+
+/// This trait is enabled with the `oleaut` feature, and is implemented by the
+/// types which can be stored in a [`SafeArray`](crate::SafeArray).
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+///
+/// # Safety
+///
+/// The type must have the exact memory layout of an element of the given
+/// [`co::VT`](crate::co::VT), and must be valid when zero-initialized.
+pub unsafe trait SafeArrayElement: Sized {
+	/// The variant type of the elements.
+	const VT: co::VT;
+}
+
+macro_rules! safe_array_element {
+	( $( $ty:ty, $vt:ident; )* ) => {
+		$(
+			unsafe impl SafeArrayElement for $ty {
+				const VT: co::VT = co::VT::$vt;
+			}
+		)*
+	};
+}
+
+safe_array_element! {
+	i8, I1;
+	i16, I2;
+	i32, I4;
+	i64, I8;
+	u8, UI1;
+	u16, UI2;
+	u32, UI4;
+	u64, UI8;
+	f32, R4;
+	f64, R8;
+	CY, CY;
+	DECIMAL, DECIMAL;
+	VARIANT_BOOL, BOOL;
+	BSTR, BSTR;
+	VARIANT, VARIANT;
+	IUnknown, UNKNOWN;
+	IDispatch, DISPATCH;
+}
+
+/// An owned
+/// [`SAFEARRAY`](https://learn.microsoft.com/en-us/windows/win32/api/oaidl/ns-oaidl-safearray),
+/// whose elements are of type `T`.
+///
+/// Automatically calls
+/// [`SafeArrayDestroy`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearraydestroy)
+/// when the object goes out of scope, which also releases the elements, like
+/// [`BSTR`](crate::BSTR) strings and COM objects.
+///
+/// The elements are accessed as a slice through
+/// [`SafeArrayAccessData`](crate::SafeArray::SafeArrayAccessData). In
+/// multidimensional arrays, the elements are stored in column-major order,
+/// that is, the first dimension varies fastest; use
+/// [`index_of`](crate::SafeArray::index_of) to find the position of an
+/// element.
+///
+/// Can be stored in a [`VARIANT`](crate::VARIANT) with
+/// [`VARIANT::new_safearray`](crate::VARIANT::new_safearray).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let mut arr = w::SafeArray::<i32>::from_vec(vec![10, 20, 30])?;
+/// {
+///     let mut data = arr.SafeArrayAccessData()?;
+///     data.as_mut_slice()[1] = 25;
+/// } // SafeArrayUnaccessData() automatically called
+///
+/// let var = w::VARIANT::new_safearray(arr);
+/// # Ok::<_, winsafe::co::HRESULT>(())
+/// ```
+pub struct SafeArray<T>
+	where T: SafeArrayElement,
+{
+	psa: *mut std::ffi::c_void,
+	_elem: PhantomData<T>,
+}
+
+impl<T> Drop for SafeArray<T>
+	where T: SafeArrayElement,
+{
+	fn drop(&mut self) {
+		if !self.psa.is_null() {
+			unsafe { ffi::SafeArrayDestroy(self.psa); } // ignore errors
+		}
+	}
+}
+
+impl<T> SafeArray<T>
+	where T: SafeArrayElement,
+{
+	/// Takes ownership of a `SAFEARRAY` pointer, which will be destroyed when
+	/// the object goes out of scope.
+	///
+	/// # Safety
+	///
+	/// Be sure the pointer is a valid `SAFEARRAY` whose elements are of type
+	/// `T`, and that it's not owned by anyone else.
+	#[must_use]
+	pub const unsafe fn from_ptr(psa: *mut std::ffi::c_void) -> Self {
+		Self { psa, _elem: PhantomData }
+	}
+
+	/// Ejects the underlying `SAFEARRAY` pointer, leaving a null pointer in its
+	/// place.
+	///
+	/// Since the internal pointer will be invalidated, the destructor will not
+	/// run. It's your responsibility to run it, otherwise you'll cause a memory
+	/// leak.
+	#[must_use]
+	pub fn leak(&mut self) -> *mut std::ffi::c_void {
+		std::mem::replace(&mut self.psa, std::ptr::null_mut())
+	}
+
+	/// Returns the underlying `SAFEARRAY` pointer.
+	#[must_use]
+	pub const fn as_ptr(&self) -> *mut std::ffi::c_void {
+		self.psa
+	}
+
+	/// Creates a one-dimensional array, with lower bound zero, moving the
+	/// elements of the `Vec` into it.
+	#[must_use]
+	pub fn from_vec(mut v: Vec<T>) -> HrResult<Self> {
+		let mut arr = Self::SafeArrayCreateVector(0, v.len() as _)?;
+		{
+			let mut data = arr.SafeArrayAccessData()?;
+			unsafe {
+				std::ptr::copy_nonoverlapping(v.as_ptr(), data.as_mut_slice().as_mut_ptr(), v.len());
+				v.set_len(0); // the elements were moved into the array
+			}
+		}
+		Ok(arr)
+	}
+
+	/// Returns the bounds of each dimension, in the same order they were
+	/// passed to [`SafeArrayCreate`](crate::SafeArray::SafeArrayCreate).
+	#[must_use]
+	pub fn bounds(&self) -> HrResult<Vec<SAFEARRAYBOUND>> {
+		(1..=self.SafeArrayGetDim())
+			.map(|dim| {
+				let lower = self.SafeArrayGetLBound(dim)?;
+				let upper = self.SafeArrayGetUBound(dim)?;
+				Ok(SAFEARRAYBOUND::new((upper - lower + 1) as _, lower))
+			})
+			.collect()
+	}
+
+	/// Returns the position, within the slice returned by
+	/// [`SafeArrayAccessData`](crate::SafeArray::SafeArrayAccessData), of the
+	/// element at the given indices – one for each dimension, in the same
+	/// order of the bounds passed to
+	/// [`SafeArrayCreate`](crate::SafeArray::SafeArrayCreate).
+	///
+	/// Returns `None` if the number of indices doesn't match the number of
+	/// dimensions, or if any index is out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*};
+	///
+	/// let mut arr = w::SafeArray::<f64>::SafeArrayCreate(&[
+	///     w::SAFEARRAYBOUND::new(3, 0), // 3 rows
+	///     w::SAFEARRAYBOUND::new(2, 1), // 2 columns, starting at 1
+	/// ])?;
+	///
+	/// let pos = arr.index_of(&[2, 1]).unwrap(); // last row, first column
+	/// arr.SafeArrayAccessData()?.as_mut_slice()[pos] = 4.5;
+	/// # Ok::<_, winsafe::co::HRESULT>(())
+	/// ```
+	#[must_use]
+	pub fn index_of(&self, indices: &[i32]) -> Option<usize> {
+		let bounds = self.bounds().ok()?;
+		if indices.len() != bounds.len() {
+			return None;
+		}
+
+		let mut pos = 0;
+		let mut stride = 1;
+		for (idx, bound) in indices.iter().zip(bounds.iter()) {
+			if *idx < bound.lLbound || *idx > bound.upper_bound() {
+				return None;
+			}
+			pos += (*idx - bound.lLbound) as usize * stride; // first dimension varies fastest
+			stride *= bound.cElements as usize;
+		}
+		Some(pos)
+	}
+
+	/// Returns `true` if the array has no elements.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the total number of elements, considering all dimensions.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.bounds()
+			.map(|bounds| bounds.iter().map(|b| b.cElements as usize).product())
+			.unwrap_or_default()
+	}
+
+	/// [`SafeArrayAccessData`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearrayaccessdata)
+	/// function.
+	///
+	/// Locks the array and returns its elements as a slice.
+	///
+	/// In the original C implementation, you must call
+	/// [`SafeArrayUnaccessData`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearrayunaccessdata)
+	/// when you're done with the data.
+	///
+	/// Here, the cleanup is performed automatically, because
+	/// `SafeArrayAccessData` returns a
+	/// [`SafeArrayUnaccessDataGuard`](crate::guard::SafeArrayUnaccessDataGuard),
+	/// which automatically calls `SafeArrayUnaccessData` when the guard goes
+	/// out of scope. You must, however, keep the guard alive, otherwise the
+	/// cleanup will be performed right away.
+	#[must_use]
+	pub fn SafeArrayAccessData(&mut self,
+	) -> HrResult<SafeArrayUnaccessDataGuard<'_, T>>
+	{
+		let len = self.len();
+		let mut pdata = std::ptr::null_mut::<std::ffi::c_void>();
+		ok_to_hrresult(unsafe { ffi::SafeArrayAccessData(self.psa, &mut pdata) })
+			.map(|_| unsafe { SafeArrayUnaccessDataGuard::new(self, pdata as _, len) })
+	}
+
+	/// [`SafeArrayCopy`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearraycopy)
+	/// function.
+	///
+	/// Performs a deep copy: strings are duplicated and COM objects have their
+	/// reference count incremented.
+	#[must_use]
+	pub fn SafeArrayCopy(&self) -> HrResult<Self> {
+		let mut psa_out = std::ptr::null_mut::<std::ffi::c_void>();
+		ok_to_hrresult(unsafe { ffi::SafeArrayCopy(self.psa, &mut psa_out) })
+			.map(|_| unsafe { Self::from_ptr(psa_out) })
+	}
+
+	/// [`SafeArrayCreate`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearraycreate)
+	/// function.
+	///
+	/// Creates an array with one dimension for each bound, with all elements
+	/// zero-initialized.
+	#[must_use]
+	pub fn SafeArrayCreate(bounds: &[SAFEARRAYBOUND]) -> HrResult<Self> {
+		if bounds.is_empty() {
+			return Err(co::HRESULT::E_INVALIDARG);
+		}
+		let psa = unsafe {
+			ffi::SafeArrayCreate(T::VT.raw(), bounds.len() as _, bounds.as_ptr() as _)
+		};
+		if psa.is_null() {
+			Err(co::HRESULT::E_OUTOFMEMORY)
+		} else {
+			Ok(unsafe { Self::from_ptr(psa) })
+		}
+	}
+
+	/// [`SafeArrayCreateVector`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearraycreatevector)
+	/// function.
+	///
+	/// Creates a one-dimensional array, with all elements zero-initialized.
+	#[must_use]
+	pub fn SafeArrayCreateVector(
+		lower_bound: i32,
+		num_elements: u32,
+	) -> HrResult<Self>
+	{
+		let psa = unsafe {
+			ffi::SafeArrayCreateVector(T::VT.raw(), lower_bound, num_elements)
+		};
+		if psa.is_null() {
+			Err(co::HRESULT::E_OUTOFMEMORY)
+		} else {
+			Ok(unsafe { Self::from_ptr(psa) })
+		}
+	}
+
+	/// [`SafeArrayGetDim`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearraygetdim)
+	/// function.
+	///
+	/// Returns the number of dimensions.
+	#[must_use]
+	pub fn SafeArrayGetDim(&self) -> u32 {
+		unsafe { ffi::SafeArrayGetDim(self.psa) }
+	}
+
+	/// [`SafeArrayGetLBound`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearraygetlbound)
+	/// function.
+	///
+	/// The dimension is one-based.
+	#[must_use]
+	pub fn SafeArrayGetLBound(&self, dim: u32) -> HrResult<i32> {
+		let mut bound = i32::default();
+		ok_to_hrresult(unsafe { ffi::SafeArrayGetLBound(self.psa, dim, &mut bound) })
+			.map(|_| bound)
+	}
+
+	/// [`SafeArrayGetUBound`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-safearraygetubound)
+	/// function.
+	///
+	/// The dimension is one-based, and the returned bound is inclusive.
+	#[must_use]
+	pub fn SafeArrayGetUBound(&self, dim: u32) -> HrResult<i32> {
+		let mut bound = i32::default();
+		ok_to_hrresult(unsafe { ffi::SafeArrayGetUBound(self.psa, dim, &mut bound) })
+			.map(|_| bound)
+	}
+
+	/// Checks whether a `SAFEARRAY` pointer holds elements of type `T`, by
+	/// comparing the element size and, if available, the variant type.
+	pub(in crate::oleaut) fn check_type(psa: *mut std::ffi::c_void) -> bool {
+		let elem_sz = unsafe { ffi::SafeArrayGetElemsize(psa) };
+		let mut vt = u16::default();
+		elem_sz as usize == std::mem::size_of::<T>()
+			&& (ok_to_hrresult(unsafe { ffi::SafeArrayGetVartype(psa, &mut vt) }).is_err() // not all arrays store their type
+				|| vt == T::VT.raw())
+	}
+}
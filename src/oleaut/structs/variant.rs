@@ -3,6 +3,8 @@
 use std::mem::ManuallyDrop;
 
 use crate::co;
+use crate::decl::*;
+use crate::ole::privs::*;
 use crate::oleaut::ffi;
 use crate::prelude::*;
 
@@ -15,6 +17,21 @@ use crate::prelude::*;
 ///
 /// The [`Default`](std::default::Default) implementation returns a
 /// [`co::VT::EMPTY`](crate::co::VT::EMPTY) value.
+///
+/// The held value can be converted from and to a
+/// [`VariantValue`](crate::VariantValue):
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let var = w::VARIANT::try_from(&w::VariantValue::I32(42))?;
+///
+/// match w::VariantValue::try_from(&var)? {
+///     w::VariantValue::I32(n) => println!("{}", n),
+///     _ => {},
+/// }
+/// # Ok::<_, winsafe::co::HRESULT>(())
+/// ```
 #[repr(C)]
 pub struct VARIANT {
 	vt: co::VT,
@@ -60,6 +77,57 @@ impl oleaut_Variant for VARIANT {
 }
 
 impl VARIANT {
+	/// Creates a new object holding a reference to a value of the given
+	/// [`co::VT`](crate::co::VT), that is, the variant type will be `vt`
+	/// combined with [`co::VT::BYREF`](crate::co::VT::BYREF).
+	///
+	/// # Safety
+	///
+	/// The pointer must point to a value of the given type, which must outlive
+	/// the `VARIANT`. The value is not owned, so it won't be released.
+	#[must_use]
+	pub unsafe fn new_byref(vt: co::VT, ptr: *mut std::ffi::c_void) -> Self {
+		unsafe { Self::from_raw(vt | co::VT::BYREF, &(ptr as usize).to_ne_bytes()) }
+	}
+
+	/// If the object holds a reference, returns the
+	/// [`co::VT`](crate::co::VT) of the referenced value – without
+	/// [`co::VT::BYREF`](crate::co::VT::BYREF) – and the pointer to it,
+	/// otherwise `None`.
+	///
+	/// To read the referenced value, convert the object to a
+	/// [`VariantValue`](crate::VariantValue).
+	#[must_use]
+	pub fn byref(&self) -> Option<(co::VT, *mut std::ffi::c_void)> {
+		if self.vt().has(co::VT::BYREF) {
+			let ptr = usize::from_ne_bytes(self.raw()[..8].try_into().unwrap());
+			Some((self.vt() & !co::VT::BYREF, ptr as _))
+		} else {
+			None
+		}
+	}
+
+	/// Creates a new object holding a [`DECIMAL`](crate::DECIMAL) value.
+	#[must_use]
+	pub fn new_decimal(val: &DECIMAL) -> Self {
+		let mut obj = Self::default();
+		unsafe { std::ptr::write_unaligned(&mut obj as *mut _ as *mut DECIMAL, *val); }
+		obj.vt = co::VT::DECIMAL; // DECIMAL overlaps the whole VARIANT, its reserved field is the vt
+		obj
+	}
+
+	/// If the object holds a [`DECIMAL`](crate::DECIMAL) value, returns it,
+	/// otherwise `None`.
+	#[must_use]
+	pub fn decimal(&self) -> Option<DECIMAL> {
+		if self.vt() == co::VT::DECIMAL {
+			let dec = unsafe { std::ptr::read_unaligned(self as *const _ as *const DECIMAL) };
+			Some(DECIMAL::new(dec.is_negative(), dec.scale, dec.Hi32, dec.Lo64))
+		} else {
+			None
+		}
+	}
+
 	/// Creates a new object holding an [`IDispatch`](crate::IDispatch) COM
 	/// value.
 	///
@@ -124,4 +192,75 @@ impl VARIANT {
 			None
 		}
 	}
+
+	/// Creates a new object holding a [`SafeArray`](crate::SafeArray), whose
+	/// variant type will be the element type combined with
+	/// [`co::VT::ARRAY`](crate::co::VT::ARRAY).
+	///
+	/// The `VARIANT` takes ownership of the array, which will be destroyed
+	/// along with it.
+	#[must_use]
+	pub fn new_safearray<T>(mut val: SafeArray<T>) -> Self
+		where T: SafeArrayElement,
+	{
+		let ptr = val.leak() as usize;
+		unsafe { Self::from_raw(T::VT | co::VT::ARRAY, &ptr.to_ne_bytes()) }
+	}
+
+	/// If the object holds a [`SafeArray`](crate::SafeArray) whose elements
+	/// are of type `T`, returns a copy of it, otherwise `None`.
+	///
+	/// Note that the array is deeply copied with
+	/// [`SafeArrayCopy`](crate::SafeArray::SafeArrayCopy); `None` is also
+	/// returned if the copy fails.
+	#[must_use]
+	pub fn safearray<T>(&self) -> Option<SafeArray<T>>
+		where T: SafeArrayElement,
+	{
+		if self.vt() == T::VT | co::VT::ARRAY {
+			let ptr = usize::from_ne_bytes(self.raw()[..8].try_into().unwrap());
+			if ptr == 0 || !SafeArray::<T>::check_type(ptr as _) {
+				return None;
+			}
+			let arr = ManuallyDrop::new(unsafe { SafeArray::<T>::from_ptr(ptr as _) }); // won't destroy the stored array
+			arr.SafeArrayCopy().ok()
+		} else {
+			None
+		}
+	}
+
+	/// [`VariantChangeType`](https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-variantchangetype)
+	/// function.
+	///
+	/// Returns a new object with the value coerced to the given type.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// let var = w::VARIANT::new_bstr("3.5")?;
+	/// let num = var.VariantChangeType(co::VARIANT::default(), co::VT::R8)?;
+	///
+	/// assert_eq!(num.f64(), Some(3.5));
+	/// # Ok::<_, co::HRESULT>(())
+	/// ```
+	#[must_use]
+	pub fn VariantChangeType(&self,
+		flags: co::VARIANT,
+		vt: co::VT,
+	) -> HrResult<VARIANT>
+	{
+		let mut dest = VARIANT::default();
+		ok_to_hrresult(
+			unsafe {
+				ffi::VariantChangeType(
+					&mut dest as *mut _ as _,
+					self as *const _ as _,
+					flags.raw(),
+					vt.raw(),
+				)
+			},
+		).map(|_| dest)
+	}
 }
@@ -94,6 +94,69 @@ pub trait oleaut_Variant: Default {
 		}
 	}
 
+	/// Creates a new object holding a [`CY`](crate::CY) currency value.
+	#[must_use]
+	fn new_cy(val: CY) -> Self
+		where Self: Sized,
+	{
+		unsafe { Self::from_raw(co::VT::CY, &val.int64.to_ne_bytes()) }
+	}
+
+	/// If the object holds a [`CY`](crate::CY) currency value, returns it,
+	/// otherwise `None`.
+	#[must_use]
+	fn cy(&self) -> Option<CY> {
+		if self.vt() == co::VT::CY {
+			Some(CY { int64: i64::from_ne_bytes(self.raw()[..8].try_into().unwrap()) })
+		} else {
+			None
+		}
+	}
+
+	/// Creates a new object holding a raw
+	/// [OLE automation date](https://learn.microsoft.com/en-us/cpp/atl-mfc-shared/date-type).
+	/// To create it from a [`SYSTEMTIME`](crate::SYSTEMTIME), use
+	/// [`new_time`](crate::prelude::oleaut_Variant::new_time).
+	#[must_use]
+	fn new_date(val: f64) -> Self
+		where Self: Sized,
+	{
+		unsafe { Self::from_raw(co::VT::DATE, &val.to_ne_bytes()) }
+	}
+
+	/// If the object holds a date/time value, returns the raw
+	/// [OLE automation date](https://learn.microsoft.com/en-us/cpp/atl-mfc-shared/date-type),
+	/// otherwise `None`. To retrieve it as a [`SYSTEMTIME`](crate::SYSTEMTIME),
+	/// use [`time`](crate::prelude::oleaut_Variant::time).
+	#[must_use]
+	fn date(&self) -> Option<f64> {
+		if self.vt() == co::VT::DATE {
+			Some(f64::from_ne_bytes(self.raw()[..8].try_into().unwrap()))
+		} else {
+			None
+		}
+	}
+
+	/// Creates a new object holding an error code, the `SCODE`.
+	#[must_use]
+	fn new_error(val: co::HRESULT) -> Self
+		where Self: Sized,
+	{
+		unsafe { Self::from_raw(co::VT::ERROR, &val.raw().to_ne_bytes()) }
+	}
+
+	/// If the object holds an error code, the `SCODE`, returns it, otherwise
+	/// `None`.
+	#[must_use]
+	fn error(&self) -> Option<co::HRESULT> {
+		if self.vt() == co::VT::ERROR {
+			let val = u32::from_ne_bytes(self.raw()[..4].try_into().unwrap());
+			Some(unsafe { co::HRESULT::from_raw(val) })
+		} else {
+			None
+		}
+	}
+
 	/// Creates a new `VARIANT` holding an `f32` value.
 	#[must_use]
 	fn new_f32(val: f32) -> Self
@@ -175,32 +238,54 @@ pub trait oleaut_Variant: Default {
 	}
 
 	/// If the object holds an `i32` value, returns it, otherwise `None`.
+	///
+	/// A [`co::VT::INT`](crate::co::VT::INT) value is also returned, since it
+	/// has the same size.
 	#[must_use]
 	fn i32(&self) -> Option<i32> {
-		if self.vt() == co::VT::I4 {
+		if self.vt() == co::VT::I4 || self.vt() == co::VT::INT {
 			Some(i32::from_ne_bytes(self.raw()[..4].try_into().unwrap()))
 		} else {
 			None
 		}
 	}
 
-	/// Creates a new object holding a date/time value.
+	/// Creates a new object holding an `i64` value.
+	#[must_use]
+	fn new_i64(val: i64) -> Self
+		where Self: Sized,
+	{
+		unsafe { Self::from_raw(co::VT::I8, &val.to_ne_bytes()) }
+	}
+
+	/// If the object holds an `i64` value, returns it, otherwise `None`.
+	#[must_use]
+	fn i64(&self) -> Option<i64> {
+		if self.vt() == co::VT::I8 {
+			Some(i64::from_ne_bytes(self.raw()[..8].try_into().unwrap()))
+		} else {
+			None
+		}
+	}
+
+	/// Creates a new object holding a date/time value, converted with
+	/// [`SYSTEMTIME::to_ole_date`](crate::SYSTEMTIME::to_ole_date).
 	#[must_use]
 	fn new_time(val: &SYSTEMTIME) -> SysResult<Self>
 		where Self: Sized,
 	{
-		let double = SystemTimeToVariantTime(val)?;
+		let double = val.to_ole_date()?;
 		Ok(unsafe { Self::from_raw(co::VT::DATE, &double.to_ne_bytes()) })
 	}
 
-	/// If the object holds a date/time value, returns it, otherwise `None`.
+	/// If the object holds a date/time value, returns it converted with
+	/// [`SYSTEMTIME::from_ole_date`](crate::SYSTEMTIME::from_ole_date),
+	/// otherwise `None`.
 	#[must_use]
 	fn time(&self) -> Option<SYSTEMTIME> {
 		if self.vt() == co::VT::DATE {
 			let double = f64::from_ne_bytes(self.raw()[..8].try_into().unwrap());
-			let mut st = SYSTEMTIME::default();
-			VariantTimeToSystemTime(double, &mut st).unwrap();
-			Some(st)
+			SYSTEMTIME::from_ole_date(double).ok()
 		} else {
 			None
 		}
@@ -251,12 +336,33 @@ pub trait oleaut_Variant: Default {
 	}
 
 	/// If the object holds an `u32` value, returns it, otherwise `None`.
+	///
+	/// A [`co::VT::UINT`](crate::co::VT::UINT) value is also returned, since it
+	/// has the same size.
 	#[must_use]
 	fn u32(&self) -> Option<u32> {
-		if self.vt() == co::VT::UI4 {
+		if self.vt() == co::VT::UI4 || self.vt() == co::VT::UINT {
 			Some(u32::from_ne_bytes(self.raw()[..4].try_into().unwrap()))
 		} else {
 			None
 		}
 	}
+
+	/// Creates a new object holding an `u64` value.
+	#[must_use]
+	fn new_u64(val: u64) -> Self
+		where Self: Sized,
+	{
+		unsafe { Self::from_raw(co::VT::UI8, &val.to_ne_bytes()) }
+	}
+
+	/// If the object holds an `u64` value, returns it, otherwise `None`.
+	#[must_use]
+	fn u64(&self) -> Option<u64> {
+		if self.vt() == co::VT::UI8 {
+			Some(u64::from_ne_bytes(self.raw()[..8].try_into().unwrap()))
+		} else {
+			None
+		}
+	}
 }
@@ -0,0 +1,339 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// An error returned by [`IDispatch::Invoke`](crate::prelude::oleaut_IDispatch::Invoke)
+/// and by the [`DispatchObject`](crate::DispatchObject) methods.
+///
+/// When the invoked member raises an exception, the fields are filled with the
+/// information of the [`EXCEPINFO`](crate::EXCEPINFO) struct; otherwise, only
+/// the [`hresult`](crate::DispatchError::hresult) is set.
+///
+/// Since it can be created from a [`co::HRESULT`](crate::co::HRESULT), the
+/// `?` operator can be used to mix both error types.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DispatchError {
+	/// The error code. If the member raised an exception, this is the code
+	/// reported by the server or, if none,
+	/// [`co::HRESULT::DISP_E_EXCEPTION`](crate::co::HRESULT::DISP_E_EXCEPTION).
+	pub hresult: co::HRESULT,
+	/// The name of the exception source, usually the application name.
+	pub source: String,
+	/// The description of the exception, meant to be shown to the user.
+	pub description: String,
+	/// The path of the help file with more information about the exception.
+	pub help_file: String,
+	/// The help context ID of the topic within the help file.
+	pub help_context: u32,
+	/// The zero-based position of the argument which caused
+	/// [`co::HRESULT::DISP_E_TYPEMISMATCH`](crate::co::HRESULT::DISP_E_TYPEMISMATCH)
+	/// or
+	/// [`co::HRESULT::DISP_E_PARAMNOTFOUND`](crate::co::HRESULT::DISP_E_PARAMNOTFOUND).
+	/// Positional arguments come first, followed by the named ones.
+	pub arg_err: Option<usize>,
+}
+
+impl std::error::Error for DispatchError {}
+
+impl std::fmt::Display for DispatchError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.description.is_empty() {
+			write!(f, "{}", self.hresult)
+		} else if self.source.is_empty() {
+			write!(f, "{} ({:#010x})", self.description, self.hresult.raw())
+		} else {
+			write!(f, "{}: {} ({:#010x})",
+				self.source, self.description, self.hresult.raw())
+		}
+	}
+}
+impl std::fmt::Debug for DispatchError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		std::fmt::Display::fmt(self, f)
+	}
+}
+
+impl From<co::HRESULT> for DispatchError {
+	fn from(hr: co::HRESULT) -> Self {
+		Self {
+			hresult: hr,
+			source: String::new(),
+			description: String::new(),
+			help_file: String::new(),
+			help_context: 0,
+			arg_err: None,
+		}
+	}
+}
+
+impl From<DispatchError> for co::HRESULT {
+	/// Discards the exception information, returning only the error code.
+	fn from(err: DispatchError) -> Self {
+		err.hresult
+	}
+}
+
+impl DispatchError {
+	pub(in crate::oleaut) fn from_excep_info(ei: &EXCEPINFO) -> Self {
+		let bstr_to_string = |b: &BSTR| if b.as_ptr().is_null() {
+			String::new()
+		} else {
+			b.to_string()
+		};
+
+		Self {
+			hresult: if ei.scode == co::HRESULT::S_OK {
+				co::HRESULT::DISP_E_EXCEPTION
+			} else {
+				ei.scode
+			},
+			source: bstr_to_string(&ei.bstrSource),
+			description: bstr_to_string(&ei.bstrDescription),
+			help_file: bstr_to_string(&ei.bstrHelpFile),
+			help_context: ei.dwHelpContext,
+			arg_err: None,
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A late-bound COM automation object, which calls methods and accesses
+/// properties by name, through
+/// [`IDispatch::Invoke`](crate::prelude::oleaut_IDispatch::Invoke).
+///
+/// The DISPIDs of the names are retrieved with
+/// [`IDispatch::GetIDsOfNames`](crate::prelude::oleaut_IDispatch::GetIDsOfNames)
+/// only once, then cached. Names are case-insensitive.
+///
+/// # Examples
+///
+/// Driving Excel:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let _com_guard = w::CoInitializeEx(co::COINIT::APARTMENTTHREADED)?;
+///
+/// let excel = w::DispatchObject::from_prog_id(
+///     "Excel.Application",
+///     co::CLSCTX::LOCAL_SERVER,
+/// )?;
+/// excel.put("Visible", &w::VARIANT::new_bool(true))?;
+///
+/// let workbook = w::DispatchObject::try_from(
+///     &excel.get_object("Workbooks")?.call("Add", &[])?,
+/// )?;
+/// let cell = w::DispatchObject::try_from(
+///     &workbook.get_object("ActiveSheet")?
+///         .call("Range", &[w::VARIANT::new_bstr("A1")?])?,
+/// )?;
+/// cell.put("Value", &w::VARIANT::new_f64(3.14))?;
+///
+/// workbook.call_named(
+///     "Close",
+///     &[],
+///     &[("SaveChanges", w::VARIANT::new_bool(false))],
+/// )?;
+/// excel.call("Quit", &[])?;
+/// # Ok::<_, w::DispatchError>(())
+/// ```
+pub struct DispatchObject {
+	obj: IDispatch,
+	lcid: LCID,
+	dispids: RefCell<HashMap<String, Vec<i32>>>,
+}
+
+impl TryFrom<&VARIANT> for DispatchObject {
+	type Error = co::HRESULT;
+
+	/// Wraps the [`IDispatch`](crate::IDispatch) object held by the
+	/// `VARIANT`, failing with
+	/// [`co::HRESULT::DISP_E_TYPEMISMATCH`](crate::co::HRESULT::DISP_E_TYPEMISMATCH)
+	/// if it holds anything else.
+	fn try_from(var: &VARIANT) -> Result<Self, Self::Error> {
+		var.idispatch::<IDispatch>()
+			.map(Self::new)
+			.ok_or(co::HRESULT::DISP_E_TYPEMISMATCH)
+	}
+}
+
+impl DispatchObject {
+	/// Wraps an [`IDispatch`](crate::IDispatch) object, using
+	/// [`LCID::USER_DEFAULT`](crate::LCID::USER_DEFAULT).
+	#[must_use]
+	pub fn new(obj: IDispatch) -> Self {
+		Self::with_lcid(obj, LCID::USER_DEFAULT)
+	}
+
+	/// Wraps an [`IDispatch`](crate::IDispatch) object, using the given locale
+	/// to look up the names and to interpret the arguments.
+	#[must_use]
+	pub fn with_lcid(obj: IDispatch, lcid: LCID) -> Self {
+		Self { obj, lcid, dispids: RefCell::new(HashMap::new()) }
+	}
+
+	/// Creates the object with
+	/// [`CLSIDFromProgID`](crate::CLSIDFromProgID) and
+	/// [`CoCreateInstance`](crate::CoCreateInstance).
+	#[must_use]
+	pub fn from_prog_id(
+		prog_id: &str,
+		cls_context: co::CLSCTX,
+	) -> HrResult<Self>
+	{
+		let clsid = CLSIDFromProgID(prog_id)?;
+		let obj = CoCreateInstance::<IDispatch>(&clsid, None, cls_context)?;
+		Ok(Self::new(obj))
+	}
+
+	/// Returns the underlying [`IDispatch`](crate::IDispatch) object.
+	#[must_use]
+	pub const fn idispatch(&self) -> &IDispatch {
+		&self.obj
+	}
+
+	/// Returns the DISPID of the given member name, which is cached.
+	#[must_use]
+	pub fn dispid(&self, name: &str) -> Result<i32, DispatchError> {
+		self.dispids(name, &[]).map(|ids| ids[0])
+	}
+
+	/// Calls a method with positional arguments, returning its result.
+	///
+	/// The member is invoked as both a method and a property getter, like
+	/// Visual Basic does, so parameterized properties can also be read.
+	pub fn call(&self,
+		method: &str,
+		args: &[VARIANT],
+	) -> Result<VARIANT, DispatchError>
+	{
+		self.call_named(method, args, &[])
+	}
+
+	/// Calls a method with positional and named arguments, returning its
+	/// result. The named arguments come after the positional ones, and they
+	/// can be given in any order.
+	///
+	/// The member is invoked as both a method and a property getter, like
+	/// Visual Basic does, so parameterized properties can also be read.
+	pub fn call_named(&self,
+		method: &str,
+		args: &[VARIANT],
+		named_args: &[(&str, VARIANT)],
+	) -> Result<VARIANT, DispatchError>
+	{
+		let arg_names = named_args.iter()
+			.map(|(name, _)| *name)
+			.collect::<Vec<_>>();
+		let ids = self.dispids(method, &arg_names)?;
+
+		let named_args = named_args.iter()
+			.zip(ids[1..].iter())
+			.map(|((_, var), id)| (*id, var))
+			.collect::<Vec<_>>();
+
+		self.obj.Invoke(
+			ids[0],
+			self.lcid,
+			co::DISPATCH::METHOD | co::DISPATCH::PROPERTYGET,
+			args,
+			&named_args,
+		)
+	}
+
+	/// Retrieves the value of a property.
+	#[must_use]
+	pub fn get(&self, property: &str) -> Result<VARIANT, DispatchError> {
+		self.obj.Invoke(
+			self.dispid(property)?,
+			self.lcid,
+			co::DISPATCH::PROPERTYGET,
+			&[],
+			&[],
+		)
+	}
+
+	/// Retrieves the value of a property which holds an
+	/// [`IDispatch`](crate::IDispatch) object, which is wrapped into a new
+	/// `DispatchObject` with the same locale.
+	///
+	/// Fails with
+	/// [`co::HRESULT::DISP_E_TYPEMISMATCH`](crate::co::HRESULT::DISP_E_TYPEMISMATCH)
+	/// if the property holds anything else.
+	#[must_use]
+	pub fn get_object(&self, property: &str) -> Result<Self, DispatchError> {
+		let obj = self.get(property)?
+			.idispatch::<IDispatch>()
+			.ok_or(co::HRESULT::DISP_E_TYPEMISMATCH)?;
+		Ok(Self::with_lcid(obj, self.lcid))
+	}
+
+	/// Sets the value of a property.
+	pub fn put(&self,
+		property: &str,
+		value: &VARIANT,
+	) -> Result<(), DispatchError>
+	{
+		self.put_impl(property, value, co::DISPATCH::PROPERTYPUT)
+	}
+
+	/// Sets a property by reference assignment, which is the `Set` statement
+	/// in Visual Basic. Used with properties which hold objects.
+	pub fn put_ref(&self,
+		property: &str,
+		value: &VARIANT,
+	) -> Result<(), DispatchError>
+	{
+		self.put_impl(property, value, co::DISPATCH::PROPERTYPUTREF)
+	}
+
+	fn put_impl(&self,
+		property: &str,
+		value: &VARIANT,
+		flags: co::DISPATCH,
+	) -> Result<(), DispatchError>
+	{
+		self.obj.Invoke(
+			self.dispid(property)?,
+			self.lcid,
+			flags,
+			&[],
+			&[(co::DISPID::PROPERTYPUT.raw(), value)],
+		).map(|_| ())
+	}
+
+	/// Returns the DISPIDs of the member name followed by the names of its
+	/// arguments, looking up the cache first.
+	fn dispids(&self,
+		member: &str,
+		arg_names: &[&str],
+	) -> Result<Vec<i32>, DispatchError>
+	{
+		let names = std::iter::once(member)
+			.chain(arg_names.iter().copied())
+			.collect::<Vec<_>>();
+		let key = names.iter()
+			.map(|name| name.to_lowercase())
+			.collect::<Vec<_>>()
+			.join("\0");
+
+		if let Some(ids) = self.dispids.borrow().get(&key) {
+			return Ok(ids.clone());
+		}
+
+		let ids = self.obj.GetIDsOfNames(&names, self.lcid)
+			.map_err(|hr| {
+				let mut err = DispatchError::from(hr);
+				if hr == co::HRESULT::DISP_E_UNKNOWNNAME {
+					err.description = format!("Unknown name: {}", names.join(", "));
+				}
+				err
+			})?;
+		self.dispids.borrow_mut().insert(key, ids.clone());
+		Ok(ids)
+	}
+}
@@ -0,0 +1,3 @@
+mod dispatch_object;
+
+pub use dispatch_object::{DispatchError, DispatchObject};
@@ -146,6 +146,37 @@ const_ordinary! { FDAP: u32;
 	TOP 1
 }
 
+const_ordinary! { FDE_OVERWRITE_RESPONSE: u32;
+	/// [`FDE_OVERWRITE_RESPONSE`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/ne-shobjidl_core-fde_overwrite_response)
+	/// enumeration (`u32`).
+	=>
+	=>
+	/// The application has not handled the event. The dialog displays a UI
+	/// asking the user whether to overwrite the file.
+	DEFAULT 0
+	/// The application has determined that the file should be overwritten.
+	ACCEPT 1
+	/// The application has determined that the file should not be
+	/// overwritten.
+	REFUSE 2
+}
+
+const_ordinary! { FDE_SHAREVIOLATION_RESPONSE: u32;
+	/// [`FDE_SHAREVIOLATION_RESPONSE`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/ne-shobjidl_core-fde_shareviolation_response)
+	/// enumeration (`u32`).
+	=>
+	=>
+	/// The application has not handled the event. The dialog displays a UI
+	/// that indicates that the file is in use.
+	DEFAULT 0
+	/// The application has determined that the file should be returned from
+	/// the dialog.
+	ACCEPT 1
+	/// The application has determined that the file should not be returned
+	/// from the dialog.
+	REFUSE 2
+}
+
 const_bitflag! { GPS: u32;
 	/// [`GETPROPERTYSTOREFLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/propsys/ne-propsys-getpropertystoreflags)
 	/// enumeration (`u32`).
@@ -513,6 +544,41 @@ const_ordinary! { SIID: u32;
 	MAX_ICONS 181
 }
 
+const_bitflag! { SLDF: u32;
+	/// [`SHELL_LINK_DATA_FLAGS`](https://learn.microsoft.com/en-us/windows/win32/api/shlobj_core/ne-shlobj_core-shell_link_data_flags)
+	/// enumeration (`u32`).
+	=>
+	=>
+	DEFAULT 0x0000_0000
+	HAS_ID_LIST 0x0000_0001
+	HAS_LINK_INFO 0x0000_0002
+	HAS_NAME 0x0000_0004
+	HAS_RELPATH 0x0000_0008
+	HAS_WORKINGDIR 0x0000_0010
+	HAS_ARGS 0x0000_0020
+	HAS_ICONLOCATION 0x0000_0040
+	UNICODE 0x0000_0080
+	FORCE_NO_LINKINFO 0x0000_0100
+	HAS_EXP_SZ 0x0000_0200
+	RUN_IN_SEPARATE 0x0000_0400
+	HAS_DARWINID 0x0000_1000
+	RUNAS_USER 0x0000_2000
+	HAS_EXP_ICON_SZ 0x0000_4000
+	NO_PIDL_ALIAS 0x0000_8000
+	FORCE_UNCNAME 0x0001_0000
+	RUN_WITH_SHIMLAYER 0x0002_0000
+	FORCE_NO_LINKTRACK 0x0004_0000
+	ENABLE_TARGET_METADATA 0x0008_0000
+	DISABLE_LINK_PATH_TRACKING 0x0010_0000
+	DISABLE_KNOWNFOLDER_RELATIVE_TRACKING 0x0020_0000
+	NO_KF_ALIAS 0x0040_0000
+	ALLOW_LINK_TO_LINK 0x0080_0000
+	UNALIAS_ON_SAVE 0x0100_0000
+	PREFER_ENVIRONMENT_PATH 0x0200_0000
+	KEEP_LOCAL_IDLIST_FOR_UNC_TARGET 0x0400_0000
+	PERSIST_VOLUME_ID_RELATIVE 0x0800_0000
+}
+
 const_bitflag! { SLGP: u32;
 	/// [`IShellLink::GetPath`](crate::prelude::shell_IShellLink::GetPath)
 	/// `flags` (`u32`).
@@ -1,6 +1,9 @@
 #![allow(non_camel_case_types, non_snake_case)]
 
+use crate::co;
+use crate::decl::*;
 use crate::kernel::ffi_types::*;
+use crate::ole::com_impl::*;
 use crate::prelude::*;
 use crate::vt::*;
 
@@ -8,13 +11,13 @@ use crate::vt::*;
 #[repr(C)]
 pub struct IFileDialogEventsVT {
 	pub IUnknownVT: IUnknownVT,
-	pub OnFileOk: fn(COMPTR, COMPTR) -> HRES,
-	pub OnFolderChanging: fn(COMPTR, COMPTR, COMPTR) -> HRES,
-	pub OnFolderChange: fn(COMPTR, COMPTR) -> HRES,
-	pub OnSelectionChange: fn(COMPTR, COMPTR) -> HRES,
-	pub OnShareViolation: fn(COMPTR, COMPTR, *mut u32) -> HRES,
-	pub OnTypeChange: fn(COMPTR, COMPTR) -> HRES,
-	pub OnOverwrite: fn(COMPTR, COMPTR, *mut u32) -> HRES,
+	pub OnFileOk: extern "system" fn(COMPTR, COMPTR) -> HRES,
+	pub OnFolderChanging: extern "system" fn(COMPTR, COMPTR, COMPTR) -> HRES,
+	pub OnFolderChange: extern "system" fn(COMPTR, COMPTR) -> HRES,
+	pub OnSelectionChange: extern "system" fn(COMPTR, COMPTR) -> HRES,
+	pub OnShareViolation: extern "system" fn(COMPTR, COMPTR, COMPTR, *mut u32) -> HRES,
+	pub OnTypeChange: extern "system" fn(COMPTR, COMPTR) -> HRES,
+	pub OnOverwrite: extern "system" fn(COMPTR, COMPTR, COMPTR, *mut u32) -> HRES,
 }
 
 com_interface! { IFileDialogEvents: "973510db-7d7f-452b-8975-74a85828d354";
@@ -28,6 +31,137 @@ com_interface! { IFileDialogEvents: "973510db-7d7f-452b-8975-74a85828d354";
 
 impl shell_IFileDialogEvents for IFileDialogEvents {}
 
+impl IFileDialogEvents {
+	/// Creates a new `IFileDialogEvents` COM object, whose methods are
+	/// implemented by the given Rust object, which is dropped when the last
+	/// reference to the COM object is released.
+	///
+	/// The returned object can be passed to
+	/// [`IFileDialog::Advise`](crate::prelude::shell_IFileDialog::Advise).
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use winsafe::{self as w, prelude::*, co};
+	///
+	/// struct MyEvents;
+	///
+	/// impl w::prelude::shell_IFileDialogEventsImpl for MyEvents {
+	///     fn OnSelectionChange(&self, fd: &w::IFileDialog) -> w::HrResult<()> {
+	///         let item = fd.GetCurrentSelection()?;
+	///         println!("{}", item.GetDisplayName(co::SIGDN::FILESYSPATH)?);
+	///         Ok(())
+	///     }
+	/// }
+	///
+	/// let fd: w::IFileOpenDialog; // initialized somewhere
+	/// # let fd = unsafe { w::IFileOpenDialog::null() };
+	///
+	/// let events = w::IFileDialogEvents::new_impl(MyEvents);
+	/// let cookie = fd.Advise(&events)?;
+	/// fd.Show(&w::HWND::NULL)?;
+	/// fd.Unadvise(cookie)?;
+	/// # Ok::<_, co::HRESULT>(())
+	/// ```
+	#[must_use]
+	pub fn new_impl<T>(obj: T) -> Self
+		where T: shell_IFileDialogEventsImpl,
+	{
+		let vt = IFileDialogEventsVT {
+			IUnknownVT: ComImpl::<IFileDialogEventsVT, T>::iunknown_vt(),
+			OnFileOk: on_file_ok::<T>,
+			OnFolderChanging: on_folder_changing::<T>,
+			OnFolderChange: on_folder_change::<T>,
+			OnSelectionChange: on_selection_change::<T>,
+			OnShareViolation: on_share_violation::<T>,
+			OnTypeChange: on_type_change::<T>,
+			OnOverwrite: on_overwrite::<T>,
+		};
+		let ptr = ComImpl::new_ptr(vt, &[IUnknown::IID, IFileDialogEvents::IID], obj);
+		unsafe { Self::from_ptr(ptr) }
+	}
+}
+
+extern "system" fn on_file_ok<T>(p: COMPTR, fd: COMPTR) -> HRES
+	where T: shell_IFileDialogEventsImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IFileDialogEventsVT, T>::obj(p) };
+		match obj.OnFileOk(&borrowed(fd))? {
+			true => Ok(()),
+			false => Err(co::HRESULT::S_FALSE), // keep the dialog open
+		}
+	})
+}
+
+extern "system" fn on_folder_changing<T>(p: COMPTR, fd: COMPTR, folder: COMPTR) -> HRES
+	where T: shell_IFileDialogEventsImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IFileDialogEventsVT, T>::obj(p) };
+		obj.OnFolderChanging(&borrowed(fd), &borrowed(folder))
+	})
+}
+
+extern "system" fn on_folder_change<T>(p: COMPTR, fd: COMPTR) -> HRES
+	where T: shell_IFileDialogEventsImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IFileDialogEventsVT, T>::obj(p) };
+		obj.OnFolderChange(&borrowed(fd))
+	})
+}
+
+extern "system" fn on_selection_change<T>(p: COMPTR, fd: COMPTR) -> HRES
+	where T: shell_IFileDialogEventsImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IFileDialogEventsVT, T>::obj(p) };
+		obj.OnSelectionChange(&borrowed(fd))
+	})
+}
+
+extern "system" fn on_share_violation<T>(
+	p: COMPTR,
+	fd: COMPTR,
+	item: COMPTR,
+	response: *mut u32,
+) -> HRES
+	where T: shell_IFileDialogEventsImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IFileDialogEventsVT, T>::obj(p) };
+		let res = obj.OnShareViolation(&borrowed(fd), &borrowed(item))?;
+		unsafe { *response = res.raw(); }
+		Ok(())
+	})
+}
+
+extern "system" fn on_type_change<T>(p: COMPTR, fd: COMPTR) -> HRES
+	where T: shell_IFileDialogEventsImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IFileDialogEventsVT, T>::obj(p) };
+		obj.OnTypeChange(&borrowed(fd))
+	})
+}
+
+extern "system" fn on_overwrite<T>(
+	p: COMPTR,
+	fd: COMPTR,
+	item: COMPTR,
+	response: *mut u32,
+) -> HRES
+	where T: shell_IFileDialogEventsImpl,
+{
+	catch_hres(|| {
+		let obj = unsafe { ComImpl::<IFileDialogEventsVT, T>::obj(p) };
+		let res = obj.OnOverwrite(&borrowed(fd), &borrowed(item))?;
+		unsafe { *response = res.raw(); }
+		Ok(())
+	})
+}
+
 /// This trait is enabled with the `shell` feature, and provides methods for
 /// [`IFileDialogEvents`](crate::IFileDialogEvents).
 ///
@@ -39,3 +173,84 @@ impl shell_IFileDialogEvents for IFileDialogEvents {}
 pub trait shell_IFileDialogEvents: ole_IUnknown {
 
 }
+
+/// This trait is enabled with the `shell` feature, and is implemented by Rust
+/// objects which back an [`IFileDialogEvents`](crate::IFileDialogEvents) COM
+/// object, created with
+/// [`IFileDialogEvents::new_impl`](crate::IFileDialogEvents::new_impl).
+///
+/// All methods have default implementations, which let the dialog proceed
+/// with its default behavior.
+///
+/// Prefer importing this trait through the prelude:
+///
+/// ```no_run
+/// use winsafe::prelude::*;
+/// ```
+pub trait shell_IFileDialogEventsImpl: 'static {
+	/// [`IFileDialogEvents::OnFileOk`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-ifiledialogevents-onfileok)
+	/// method.
+	///
+	/// Returns `true` to accept the current result, or `false` to keep the
+	/// dialog open.
+	fn OnFileOk(&self, fd: &IFileDialog) -> HrResult<bool> {
+		let _ = fd;
+		Ok(true)
+	}
+
+	/// [`IFileDialogEvents::OnFolderChange`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-ifiledialogevents-onfolderchange)
+	/// method.
+	fn OnFolderChange(&self, fd: &IFileDialog) -> HrResult<()> {
+		let _ = fd;
+		Ok(())
+	}
+
+	/// [`IFileDialogEvents::OnFolderChanging`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-ifiledialogevents-onfolderchanging)
+	/// method.
+	///
+	/// Returning an error prevents the folder change.
+	fn OnFolderChanging(&self,
+		fd: &IFileDialog,
+		folder: &IShellItem,
+	) -> HrResult<()>
+	{
+		let _ = (fd, folder);
+		Ok(())
+	}
+
+	/// [`IFileDialogEvents::OnOverwrite`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-ifiledialogevents-onoverwrite)
+	/// method.
+	fn OnOverwrite(&self,
+		fd: &IFileDialog,
+		item: &IShellItem,
+	) -> HrResult<co::FDE_OVERWRITE_RESPONSE>
+	{
+		let _ = (fd, item);
+		Ok(co::FDE_OVERWRITE_RESPONSE::DEFAULT)
+	}
+
+	/// [`IFileDialogEvents::OnSelectionChange`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-ifiledialogevents-onselectionchange)
+	/// method.
+	fn OnSelectionChange(&self, fd: &IFileDialog) -> HrResult<()> {
+		let _ = fd;
+		Ok(())
+	}
+
+	/// [`IFileDialogEvents::OnShareViolation`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-ifiledialogevents-onshareviolation)
+	/// method.
+	fn OnShareViolation(&self,
+		fd: &IFileDialog,
+		item: &IShellItem,
+	) -> HrResult<co::FDE_SHAREVIOLATION_RESPONSE>
+	{
+		let _ = (fd, item);
+		Ok(co::FDE_SHAREVIOLATION_RESPONSE::DEFAULT)
+	}
+
+	/// [`IFileDialogEvents::OnTypeChange`](https://learn.microsoft.com/en-us/windows/win32/api/shobjidl_core/nf-shobjidl_core-ifiledialogevents-ontypechange)
+	/// method.
+	fn OnTypeChange(&self, fd: &IFileDialog) -> HrResult<()> {
+		let _ = fd;
+		Ok(())
+	}
+}
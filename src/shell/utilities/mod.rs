@@ -0,0 +1,4 @@
+mod shell_link_file;
+
+pub use shell_link_file::{ShellLinkExtra, ShellLinkFile, ShellLinkInfo,
+	ShellLinkLocal, ShellLinkNetwork, ShellLinkProperty, ShellLinkPropertyStorage};
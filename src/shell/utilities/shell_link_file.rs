@@ -0,0 +1,1018 @@
+use crate::co;
+use crate::decl::*;
+use crate::prelude::*;
+
+/// Information to locate the target of a [`ShellLinkFile`](crate::ShellLinkFile)
+/// when its ID list can't be resolved, as the
+/// [`LinkInfo`](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-shllink/6813269d-0cc8-4be2-933f-e96e8e3412dc)
+/// structure.
+///
+/// ANSI strings are stored in the system code page, and they're decoded as
+/// [`Encoding::Win1252`](crate::Encoding::Win1252), which maps every byte to a
+/// character, so they're written back unchanged. The Unicode versions, when
+/// present, hold the exact text.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ShellLinkInfo {
+	/// The volume and the path of a target on a local drive.
+	pub local: Option<ShellLinkLocal>,
+	/// The share of a target on a network location.
+	pub network: Option<ShellLinkNetwork>,
+	/// Path appended to the local base path or the network share, in ANSI.
+	pub common_path_suffix: String,
+	/// Path appended to the local base path or the network share, in Unicode.
+	pub common_path_suffix_unicode: Option<String>,
+}
+
+impl ShellLinkInfo {
+	/// Returns the full path of the target: the local base path or the network
+	/// share, followed by the common path suffix. The Unicode versions are
+	/// preferred.
+	#[must_use]
+	pub fn target_path(&self) -> Option<String> {
+		let suffix = self.common_path_suffix_unicode.as_deref()
+			.unwrap_or(&self.common_path_suffix);
+
+		if let Some(local) = &self.local {
+			let base = local.base_path_unicode.as_deref().unwrap_or(&local.base_path);
+			Some(format!("{}{}", base, suffix))
+		} else if let Some(network) = &self.network {
+			let net_name = network.net_name_unicode.as_deref().unwrap_or(&network.net_name);
+			if suffix.is_empty() {
+				Some(net_name.to_owned())
+			} else {
+				Some(format!("{}\\{}", net_name, suffix))
+			}
+		} else {
+			None
+		}
+	}
+}
+
+/// The `VolumeID` and `LocalBasePath` fields of a
+/// [`ShellLinkInfo`](crate::ShellLinkInfo).
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShellLinkLocal {
+	/// Type of the drive which holds the target.
+	pub drive_type: co::DRIVE,
+	/// Serial number of the volume which holds the target.
+	pub drive_serial_number: u32,
+	/// Label of the volume which holds the target.
+	pub volume_label: String,
+	/// Whether the volume label is stored in Unicode, instead of ANSI.
+	pub volume_label_unicode: bool,
+	/// Path of the target, in ANSI.
+	pub base_path: String,
+	/// Path of the target, in Unicode.
+	pub base_path_unicode: Option<String>,
+}
+
+/// The `CommonNetworkRelativeLink` structure of a
+/// [`ShellLinkInfo`](crate::ShellLinkInfo).
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShellLinkNetwork {
+	/// The server share path, like `\\server\share`, in ANSI.
+	pub net_name: String,
+	/// The device name, like `Z:`, in ANSI.
+	pub device_name: Option<String>,
+	/// The network provider type, one of the `WNNC_NET_*` values.
+	pub provider_type: Option<u32>,
+	/// The server share path, in Unicode.
+	pub net_name_unicode: Option<String>,
+	/// The device name, in Unicode.
+	pub device_name_unicode: Option<String>,
+}
+
+/// A serialized property storage of a
+/// [`ShellLinkExtra::PropertyStore`](crate::ShellLinkExtra::PropertyStore)
+/// block, in the
+/// [MS-PROPSTORE](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-propstore/39ea873f-7af5-44dd-92f9-bc1f293852cc)
+/// format.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShellLinkPropertyStorage {
+	/// The format ID of the properties, which is the `fmtid` of their
+	/// [`PROPERTYKEY`](crate::PROPERTYKEY).
+	pub format_id: GUID,
+	/// The properties of the storage.
+	pub properties: Vec<ShellLinkProperty>,
+}
+
+/// A property of a [`ShellLinkPropertyStorage`](crate::ShellLinkPropertyStorage).
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShellLinkProperty {
+	/// The property ID, which is the `pid` of its
+	/// [`PROPERTYKEY`](crate::PROPERTYKEY). Zero for named properties.
+	pub id: u32,
+	/// The name of the property, if the storage holds named properties.
+	pub name: Option<String>,
+	/// The raw `TypedPropertyValue`: the [`co::VT`](crate::co::VT) type in the
+	/// first 2 bytes, 2 bytes of padding, then the value.
+	pub value: Vec<u8>,
+}
+
+impl ShellLinkProperty {
+	/// Returns the type of the value.
+	#[must_use]
+	pub fn vt(&self) -> Option<co::VT> {
+		le_u16(&self.value, 0).ok()
+			.map(|vt| unsafe { co::VT::from_raw(vt) })
+	}
+
+	/// Returns the value if it's a [`co::VT::LPWSTR`](crate::co::VT::LPWSTR)
+	/// or a [`co::VT::BSTR`](crate::co::VT::BSTR) string.
+	#[must_use]
+	pub fn value_str(&self) -> Option<String> {
+		let vt = self.vt()?;
+		if vt != co::VT::LPWSTR && vt != co::VT::BSTR {
+			return None;
+		}
+		let len = le_u32(&self.value, 4).ok()? as usize; // in chars for LPWSTR, bytes for BSTR
+		let len = if vt == co::VT::LPWSTR { len * 2 } else { len };
+		let bytes = self.value.get(8..8 + len)?;
+		let wchars = bytes.chunks_exact(2)
+			.map(|b| u16::from_le_bytes([b[0], b[1]]))
+			.take_while(|ch| *ch != 0)
+			.collect::<Vec<_>>();
+		Some(String::from_utf16_lossy(&wchars))
+	}
+}
+
+/// A block of the `ExtraData` section of a
+/// [`ShellLinkFile`](crate::ShellLinkFile).
+///
+/// Blocks which aren't listed, like the console properties, and blocks whose
+/// contents don't follow the specification are kept as
+/// [`Unknown`](crate::ShellLinkExtra::Unknown), so they're written back
+/// unchanged.
+#[derive(Clone, PartialEq, Eq)]
+pub enum ShellLinkExtra {
+	/// `EnvironmentVariableDataBlock`: the target path with environment
+	/// variables.
+	Environment {
+		/// The path in the system default code page.
+		ansi: String,
+		/// The path in Unicode.
+		unicode: String,
+	},
+	/// `TrackerDataBlock`: the data used by the Distributed Link Tracking
+	/// service to find a moved target.
+	Tracker {
+		/// NetBIOS name of the machine where the target was last seen.
+		machine_id: String,
+		/// Volume ID of the current location of the target.
+		droid_volume: GUID,
+		/// Object ID of the current location of the target.
+		droid_object: GUID,
+		/// Volume ID of the location where the target was created.
+		birth_volume: GUID,
+		/// Object ID of the location where the target was created.
+		birth_object: GUID,
+	},
+	/// `ConsoleFEDataBlock`: the code page of a console window.
+	ConsoleFe {
+		/// Code page used to display text in the console window.
+		code_page: u32,
+	},
+	/// `SpecialFolderDataBlock`: the location of a special folder in the ID
+	/// list.
+	SpecialFolder {
+		/// `CSIDL` of the special folder.
+		folder_id: u32,
+		/// Offset, in bytes, of the folder item within the ID list.
+		offset: u32,
+	},
+	/// `DarwinDataBlock`: the application identifier of a Windows Installer
+	/// advertised shortcut.
+	Darwin {
+		/// The identifier in the system default code page.
+		ansi: String,
+		/// The identifier in Unicode.
+		unicode: String,
+	},
+	/// `IconEnvironmentDataBlock`: the icon path with environment variables.
+	IconEnvironment {
+		/// The path in the system default code page.
+		ansi: String,
+		/// The path in Unicode.
+		unicode: String,
+	},
+	/// `ShimDataBlock`: the compatibility shim of the target.
+	Shim {
+		/// Name of the shim layer, like `WinXPSP3`.
+		layer_name: String,
+	},
+	/// `PropertyStoreDataBlock`: properties of the target.
+	PropertyStore(Vec<ShellLinkPropertyStorage>),
+	/// `KnownFolderDataBlock`: the location of a known folder in the ID list.
+	KnownFolder {
+		/// `KNOWNFOLDERID` of the known folder.
+		folder_id: GUID,
+		/// Offset, in bytes, of the folder item within the ID list.
+		offset: u32,
+	},
+	/// `VistaAndAboveIDListDataBlock`: an alternate ID list of the target,
+	/// with each item ID without its size prefix.
+	VistaIdList(Vec<Vec<u8>>),
+	/// Any other block, with its signature and the data after it.
+	Unknown { signature: u32, data: Vec<u8> },
+}
+
+impl ShellLinkExtra {
+	/// Returns the signature of the block.
+	#[must_use]
+	pub const fn signature(&self) -> u32 {
+		match self {
+			Self::Environment { .. } => SIG_ENVIRONMENT,
+			Self::Tracker { .. } => SIG_TRACKER,
+			Self::ConsoleFe { .. } => SIG_CONSOLE_FE,
+			Self::SpecialFolder { .. } => SIG_SPECIAL_FOLDER,
+			Self::Darwin { .. } => SIG_DARWIN,
+			Self::IconEnvironment { .. } => SIG_ICON_ENVIRONMENT,
+			Self::Shim { .. } => SIG_SHIM,
+			Self::PropertyStore(_) => SIG_PROPERTY_STORE,
+			Self::KnownFolder { .. } => SIG_KNOWN_FOLDER,
+			Self::VistaIdList(_) => SIG_VISTA_ID_LIST,
+			Self::Unknown { signature, .. } => *signature,
+		}
+	}
+
+	/// Parses the data of a block, after its signature.
+	fn parse(signature: u32, data: &[u8]) -> SysResult<Self> {
+		Ok(match signature {
+			SIG_ENVIRONMENT | SIG_DARWIN | SIG_ICON_ENVIRONMENT => {
+				if data.len() != MAX_PATH + MAX_PATH * 2 {
+					return Err(co::ERROR::INVALID_DATA);
+				}
+				let ansi = read_astr_fixed(&data[..MAX_PATH]);
+				let unicode = read_wstr_fixed(&data[MAX_PATH..]);
+				match signature {
+					SIG_ENVIRONMENT => Self::Environment { ansi, unicode },
+					SIG_DARWIN => Self::Darwin { ansi, unicode },
+					_ => Self::IconEnvironment { ansi, unicode },
+				}
+			},
+			SIG_TRACKER => {
+				if data.len() != 0x58 || le_u32(data, 0)? != 0x58 || le_u32(data, 4)? != 0 { // length, version
+					return Err(co::ERROR::INVALID_DATA);
+				}
+				Self::Tracker {
+					machine_id: read_astr_fixed(&data[8..24]),
+					droid_volume: read_guid(data, 24)?,
+					droid_object: read_guid(data, 40)?,
+					birth_volume: read_guid(data, 56)?,
+					birth_object: read_guid(data, 72)?,
+				}
+			},
+			SIG_CONSOLE_FE if data.len() == 4 => {
+				Self::ConsoleFe { code_page: le_u32(data, 0)? }
+			},
+			SIG_SPECIAL_FOLDER if data.len() == 8 => {
+				Self::SpecialFolder { folder_id: le_u32(data, 0)?, offset: le_u32(data, 4)? }
+			},
+			SIG_SHIM => Self::Shim { layer_name: read_wstr(data, 0)? },
+			SIG_PROPERTY_STORE => Self::PropertyStore(parse_property_store(data)?),
+			SIG_KNOWN_FOLDER if data.len() == 20 => {
+				Self::KnownFolder { folder_id: read_guid(data, 0)?, offset: le_u32(data, 16)? }
+			},
+			SIG_VISTA_ID_LIST => {
+				let (items, len) = parse_id_items(data)?;
+				if len != data.len() {
+					return Err(co::ERROR::INVALID_DATA);
+				}
+				Self::VistaIdList(items)
+			},
+			_ => return Err(co::ERROR::INVALID_DATA),
+		})
+	}
+
+	/// Serializes the data of a block, after its signature.
+	fn serialize_data(&self, buf: &mut Vec<u8>) {
+		match self {
+			Self::Environment { ansi, unicode }
+				| Self::Darwin { ansi, unicode }
+				| Self::IconEnvironment { ansi, unicode } =>
+			{
+				write_astr_fixed(buf, ansi, MAX_PATH);
+				write_wstr_fixed(buf, unicode, MAX_PATH);
+			},
+			Self::Tracker { machine_id, droid_volume, droid_object, birth_volume, birth_object } => {
+				buf.extend_from_slice(&0x58u32.to_le_bytes()); // length
+				buf.extend_from_slice(&0u32.to_le_bytes()); // version
+				write_astr_fixed(buf, machine_id, 16);
+				for guid in [droid_volume, droid_object, birth_volume, birth_object] {
+					write_guid(buf, guid);
+				}
+			},
+			Self::ConsoleFe { code_page } => buf.extend_from_slice(&code_page.to_le_bytes()),
+			Self::SpecialFolder { folder_id, offset } => {
+				buf.extend_from_slice(&folder_id.to_le_bytes());
+				buf.extend_from_slice(&offset.to_le_bytes());
+			},
+			Self::Shim { layer_name } => {
+				let start = buf.len();
+				write_wstr(buf, layer_name);
+				if buf.len() - start < 0x80 {
+					buf.resize(start + 0x80, 0); // minimum block size is 0x88
+				}
+			},
+			Self::PropertyStore(storages) => serialize_property_store(buf, storages),
+			Self::KnownFolder { folder_id, offset } => {
+				write_guid(buf, folder_id);
+				buf.extend_from_slice(&offset.to_le_bytes());
+			},
+			Self::VistaIdList(items) => serialize_id_items(buf, items),
+			Self::Unknown { data, .. } => buf.extend_from_slice(data),
+		}
+	}
+}
+
+//------------------------------------------------------------------------------
+
+/// A shell link (`.lnk`) file, in the
+/// [MS-SHLLINK](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-shllink/16cb4ca1-9339-4d0c-a68d-bf1d6cc0f943)
+/// binary format.
+///
+/// This is a pure Rust implementation, which doesn't need COM, and gives
+/// access to all the parts of the file, unlike
+/// [`IShellLink`](crate::IShellLink). Files saved by `IShellLink` are
+/// serialized back byte by byte.
+///
+/// When serializing, the presence flags of the optional structures –
+/// [`co::SLDF::HAS_ID_LIST`](crate::co::SLDF::HAS_ID_LIST) through
+/// [`co::SLDF::HAS_ICONLOCATION`](crate::co::SLDF::HAS_ICONLOCATION) – are
+/// set according to the fields, and
+/// [`co::SLDF::UNICODE`](crate::co::SLDF::UNICODE) determines the encoding of
+/// the strings. Other flags are written as they are.
+///
+/// # Examples
+///
+/// Reading the target of a shortcut:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let lnk = w::ShellLinkFile::parse_from_file("C:\\Temp\\foo.lnk")?;
+///
+/// if let Some(path) = lnk.link_info.as_ref().and_then(|li| li.target_path()) {
+///     println!("Target: {}", path);
+/// }
+/// println!("Arguments: {}", lnk.arguments.as_deref().unwrap_or(""));
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShellLinkFile {
+	/// Flags of the link.
+	pub link_flags: co::SLDF,
+	/// Attributes of the target.
+	pub file_attributes: co::FILE_ATTRIBUTE,
+	/// Creation time of the target.
+	pub creation_time: FILETIME,
+	/// Last access time of the target.
+	pub access_time: FILETIME,
+	/// Last write time of the target.
+	pub write_time: FILETIME,
+	/// Size of the target, in bytes; only the lower 32 bits are stored.
+	pub file_size: u32,
+	/// Index of the icon within the icon location.
+	pub icon_index: i32,
+	/// How the target window is shown.
+	pub show_command: co::SW,
+	/// Keyboard shortcut: the [`co::VK`](crate::co::VK) in the low byte, and
+	/// the `HOTKEYF` modifiers in the high byte.
+	pub hot_key: u16,
+	/// The `LinkTargetIDList`, with each item ID without its size prefix.
+	pub id_list: Option<Vec<Vec<u8>>>,
+	/// The `LinkInfo` structure.
+	pub link_info: Option<ShellLinkInfo>,
+	/// Description of the link.
+	pub name: Option<String>,
+	/// Path of the target, relative to the link file.
+	pub relative_path: Option<String>,
+	/// Working directory of the target.
+	pub working_dir: Option<String>,
+	/// Command line arguments of the target.
+	pub arguments: Option<String>,
+	/// Path of the icon.
+	pub icon_location: Option<String>,
+	/// The `ExtraData` blocks.
+	pub extra_data: Vec<ShellLinkExtra>,
+}
+
+impl Default for ShellLinkFile {
+	fn default() -> Self {
+		Self {
+			link_flags: co::SLDF::UNICODE,
+			file_attributes: co::FILE_ATTRIBUTE::default(),
+			creation_time: FILETIME::default(),
+			access_time: FILETIME::default(),
+			write_time: FILETIME::default(),
+			file_size: 0,
+			icon_index: 0,
+			show_command: co::SW::SHOWNORMAL,
+			hot_key: 0,
+			id_list: None,
+			link_info: None,
+			name: None,
+			relative_path: None,
+			working_dir: None,
+			arguments: None,
+			icon_location: None,
+			extra_data: Vec::new(),
+		}
+	}
+}
+
+impl ShellLinkFile {
+	/// Creates a new, empty link, with Unicode strings.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses the contents of a `.lnk` file.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<Self> {
+		if le_u32(data, 0)? != HEADER_SIZE || data.get(4..20) != Some(&LINK_CLSID[..]) {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let link_flags = unsafe { co::SLDF::from_raw(le_u32(data, 0x14)?) };
+		let mut lnk = Self {
+			link_flags,
+			file_attributes: unsafe { co::FILE_ATTRIBUTE::from_raw(le_u32(data, 0x18)?) },
+			creation_time: FILETIME::from_ticks(le_u64(data, 0x1c)?),
+			access_time: FILETIME::from_ticks(le_u64(data, 0x24)?),
+			write_time: FILETIME::from_ticks(le_u64(data, 0x2c)?),
+			file_size: le_u32(data, 0x34)?,
+			icon_index: le_u32(data, 0x38)? as _,
+			show_command: unsafe { co::SW::from_raw(le_u32(data, 0x3c)? as _) },
+			hot_key: le_u16(data, 0x40)?,
+			..Self::default()
+		};
+		let mut off = HEADER_SIZE as usize;
+
+		if link_flags.has(co::SLDF::HAS_ID_LIST) {
+			let sz = le_u16(data, off)? as usize;
+			let list = data.get(off + 2..off + 2 + sz)
+				.ok_or(co::ERROR::INVALID_DATA)?;
+			lnk.id_list = Some(parse_id_items(list)?.0);
+			off += 2 + sz;
+		}
+
+		if link_flags.has(co::SLDF::HAS_LINK_INFO) {
+			let sz = le_u32(data, off)? as usize;
+			let info = off.checked_add(sz)
+				.and_then(|end| data.get(off..end))
+				.ok_or(co::ERROR::INVALID_DATA)?;
+			lnk.link_info = Some(parse_link_info(info)?);
+			off += sz;
+		}
+
+		let is_unicode = link_flags.has(co::SLDF::UNICODE);
+		for (flag, field) in [
+			(co::SLDF::HAS_NAME, &mut lnk.name),
+			(co::SLDF::HAS_RELPATH, &mut lnk.relative_path),
+			(co::SLDF::HAS_WORKINGDIR, &mut lnk.working_dir),
+			(co::SLDF::HAS_ARGS, &mut lnk.arguments),
+			(co::SLDF::HAS_ICONLOCATION, &mut lnk.icon_location),
+		] {
+			if link_flags.has(flag) {
+				let num_chars = le_u16(data, off)? as usize;
+				let num_bytes = if is_unicode { num_chars * 2 } else { num_chars };
+				let raw = data.get(off + 2..off + 2 + num_bytes)
+					.ok_or(co::ERROR::INVALID_DATA)?;
+				*field = Some(if is_unicode {
+					String::from_utf16_lossy(&wchars_of(raw))
+				} else {
+					decode_ansi(raw)
+				});
+				off += 2 + num_bytes;
+			}
+		}
+
+		while let Ok(sz) = le_u32(data, off) { // tolerate a missing terminal block
+			let sz = sz as usize;
+			if sz < 4 {
+				break; // terminal block
+			}
+			let block = off.checked_add(sz)
+				.and_then(|end| data.get(off..end))
+				.filter(|block| block.len() >= 8)
+				.ok_or(co::ERROR::INVALID_DATA)?;
+			let signature = le_u32(block, 4)?;
+			lnk.extra_data.push(parse_extra(signature, &block[8..]));
+			off += sz;
+		}
+
+		Ok(lnk)
+	}
+
+	/// Parses a `.lnk` file directly from the disk. The file will be
+	/// [mapped in memory](crate::FileMapped) during reading.
+	#[must_use]
+	pub fn parse_from_file(file_path: &str) -> SysResult<Self> {
+		let fin = FileMapped::open(file_path, FileAccess::ExistingReadOnly)?;
+		Self::parse(fin.as_slice())
+	}
+
+	/// Serializes the link to the contents of a `.lnk` file.
+	///
+	/// Fails with
+	/// [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA) if a
+	/// structure is too large for the format.
+	#[must_use]
+	pub fn serialize_to_bytes(&self) -> SysResult<Vec<u8>> {
+		let mut link_flags = self.link_flags;
+		for (flag, present) in [
+			(co::SLDF::HAS_ID_LIST, self.id_list.is_some()),
+			(co::SLDF::HAS_LINK_INFO, self.link_info.is_some()),
+			(co::SLDF::HAS_NAME, self.name.is_some()),
+			(co::SLDF::HAS_RELPATH, self.relative_path.is_some()),
+			(co::SLDF::HAS_WORKINGDIR, self.working_dir.is_some()),
+			(co::SLDF::HAS_ARGS, self.arguments.is_some()),
+			(co::SLDF::HAS_ICONLOCATION, self.icon_location.is_some()),
+		] {
+			link_flags = if present { link_flags | flag } else { link_flags & !flag };
+		}
+
+		let mut buf = Vec::<u8>::with_capacity(0x400);
+		buf.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+		buf.extend_from_slice(&LINK_CLSID);
+		buf.extend_from_slice(&link_flags.raw().to_le_bytes());
+		buf.extend_from_slice(&self.file_attributes.raw().to_le_bytes());
+		buf.extend_from_slice(&self.creation_time.ticks().to_le_bytes());
+		buf.extend_from_slice(&self.access_time.ticks().to_le_bytes());
+		buf.extend_from_slice(&self.write_time.ticks().to_le_bytes());
+		buf.extend_from_slice(&self.file_size.to_le_bytes());
+		buf.extend_from_slice(&self.icon_index.to_le_bytes());
+		buf.extend_from_slice(&(self.show_command.raw() as u32).to_le_bytes());
+		buf.extend_from_slice(&self.hot_key.to_le_bytes());
+		buf.extend_from_slice(&[0; 10]); // reserved
+
+		if let Some(id_list) = &self.id_list {
+			let mut list = Vec::<u8>::new();
+			serialize_id_items(&mut list, id_list);
+			let sz = u16::try_from(list.len()).map_err(|_| co::ERROR::INVALID_DATA)?;
+			buf.extend_from_slice(&sz.to_le_bytes());
+			buf.extend_from_slice(&list);
+		}
+
+		if let Some(link_info) = &self.link_info {
+			serialize_link_info(&mut buf, link_info);
+		}
+
+		let is_unicode = link_flags.has(co::SLDF::UNICODE);
+		for text in [&self.name, &self.relative_path, &self.working_dir,
+			&self.arguments, &self.icon_location].into_iter().flatten()
+		{
+			let raw = if is_unicode {
+				text.encode_utf16().flat_map(|ch| ch.to_le_bytes()).collect::<Vec<_>>()
+			} else {
+				Encoding::Win1252.encode(text)
+			};
+			let num_chars = if is_unicode { raw.len() / 2 } else { raw.len() };
+			let num_chars = u16::try_from(num_chars).map_err(|_| co::ERROR::INVALID_DATA)?;
+			buf.extend_from_slice(&num_chars.to_le_bytes());
+			buf.extend_from_slice(&raw);
+		}
+
+		for extra in self.extra_data.iter() {
+			let start = buf.len();
+			buf.extend_from_slice(&[0; 4]); // size, filled below
+			buf.extend_from_slice(&extra.signature().to_le_bytes());
+			extra.serialize_data(&mut buf);
+			let sz = (buf.len() - start) as u32;
+			buf[start..start + 4].copy_from_slice(&sz.to_le_bytes());
+		}
+		buf.extend_from_slice(&0u32.to_le_bytes()); // terminal block
+
+		Ok(buf)
+	}
+
+	/// Serializes the link directly to a `.lnk` file.
+	pub fn serialize_to_file(&self, file_path: &str) -> SysResult<()> {
+		let fout = File::open(file_path, FileAccess::OpenOrCreateRW)?;
+		fout.erase_and_write(&self.serialize_to_bytes()?)?;
+		Ok(())
+	}
+}
+
+//------------------------------------------------------------------------------
+
+const HEADER_SIZE: u32 = 0x4c;
+const LINK_CLSID: [u8; 16] = [ // 00021401-0000-0000-c000-000000000046
+	0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00,
+	0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+const MAX_PATH: usize = 260;
+
+const SIG_ENVIRONMENT: u32 = 0xa000_0001;
+const SIG_TRACKER: u32 = 0xa000_0003;
+const SIG_CONSOLE_FE: u32 = 0xa000_0004;
+const SIG_SPECIAL_FOLDER: u32 = 0xa000_0005;
+const SIG_DARWIN: u32 = 0xa000_0006;
+const SIG_ICON_ENVIRONMENT: u32 = 0xa000_0007;
+const SIG_SHIM: u32 = 0xa000_0008;
+const SIG_PROPERTY_STORE: u32 = 0xa000_0009;
+const SIG_KNOWN_FOLDER: u32 = 0xa000_000b;
+const SIG_VISTA_ID_LIST: u32 = 0xa000_000c;
+
+const PROPSTORE_VERSION: u32 = 0x5350_5331; // "1SPS"
+const PROPSTORE_NAMED_FMTID: GUID = GUID::new("d5cdd505-2e9c-101b-9397-08002b2cf9ae");
+
+/// Parses an extra data block, keeping it raw if it can't be parsed, or if it
+/// wouldn't be serialized back to the same bytes.
+fn parse_extra(signature: u32, data: &[u8]) -> ShellLinkExtra {
+	if let Ok(extra) = ShellLinkExtra::parse(signature, data) {
+		let mut check = Vec::with_capacity(data.len());
+		extra.serialize_data(&mut check);
+		if check == data {
+			return extra;
+		}
+	}
+	ShellLinkExtra::Unknown { signature, data: data.to_vec() }
+}
+
+/// Parses the item IDs of an ID list, up to the terminating zero. Returns the
+/// items and the number of bytes read.
+fn parse_id_items(data: &[u8]) -> SysResult<(Vec<Vec<u8>>, usize)> {
+	let mut items = Vec::<Vec<u8>>::new();
+	let mut off = 0;
+	loop {
+		let sz = le_u16(data, off)? as usize;
+		if sz == 0 {
+			return Ok((items, off + 2));
+		}
+		let item = data.get(off + 2..off + sz)
+			.filter(|_| sz >= 2)
+			.ok_or(co::ERROR::INVALID_DATA)?;
+		items.push(item.to_vec());
+		off += sz;
+	}
+}
+
+fn serialize_id_items(buf: &mut Vec<u8>, items: &[Vec<u8>]) {
+	for item in items.iter() {
+		buf.extend_from_slice(&((item.len() + 2) as u16).to_le_bytes());
+		buf.extend_from_slice(item);
+	}
+	buf.extend_from_slice(&0u16.to_le_bytes());
+}
+
+fn parse_link_info(data: &[u8]) -> SysResult<ShellLinkInfo> {
+	let header_sz = le_u32(data, 4)?;
+	let flags = le_u32(data, 8)?;
+	let (base_path_off_u, suffix_off_u) = if header_sz >= 0x24 {
+		(le_u32(data, 0x1c)? as usize, le_u32(data, 0x20)? as usize)
+	} else {
+		(0, 0)
+	};
+
+	let local = if flags & 0x1 != 0 { // VolumeIDAndLocalBasePath
+		let vol_off = le_u32(data, 0xc)? as usize;
+		let vol_sz = le_u32(data, vol_off)? as usize;
+		let vol = vol_off.checked_add(vol_sz)
+			.and_then(|end| data.get(vol_off..end))
+			.ok_or(co::ERROR::INVALID_DATA)?;
+		let label_off = le_u32(vol, 0xc)? as usize;
+		let (volume_label, volume_label_unicode) = if label_off == 0x14 {
+			(read_wstr(vol, le_u32(vol, 0x10)? as _)?, true)
+		} else {
+			(read_astr(vol, label_off)?, false)
+		};
+
+		Some(ShellLinkLocal {
+			drive_type: unsafe { co::DRIVE::from_raw(le_u32(vol, 4)?) },
+			drive_serial_number: le_u32(vol, 8)?,
+			volume_label,
+			volume_label_unicode,
+			base_path: read_astr(data, le_u32(data, 0x10)? as _)?,
+			base_path_unicode: if base_path_off_u == 0 {
+				None
+			} else {
+				Some(read_wstr(data, base_path_off_u)?)
+			},
+		})
+	} else {
+		None
+	};
+
+	let network = if flags & 0x2 != 0 { // CommonNetworkRelativeLinkAndPathSuffix
+		let cnrl_off = le_u32(data, 0x14)? as usize;
+		let cnrl_sz = le_u32(data, cnrl_off)? as usize;
+		let cnrl = cnrl_off.checked_add(cnrl_sz)
+			.and_then(|end| data.get(cnrl_off..end))
+			.ok_or(co::ERROR::INVALID_DATA)?;
+		let cnrl_flags = le_u32(cnrl, 4)?;
+		let net_name_off = le_u32(cnrl, 8)? as usize;
+		let device_name_off = le_u32(cnrl, 0xc)? as usize;
+		let (net_name_off_u, device_name_off_u) = if net_name_off > 0x14 {
+			(le_u32(cnrl, 0x14)? as usize, le_u32(cnrl, 0x18)? as usize)
+		} else {
+			(0, 0)
+		};
+		let has_device = cnrl_flags & 0x1 != 0; // ValidDevice
+
+		Some(ShellLinkNetwork {
+			net_name: read_astr(cnrl, net_name_off)?,
+			device_name: if has_device { Some(read_astr(cnrl, device_name_off)?) } else { None },
+			provider_type: if cnrl_flags & 0x2 != 0 { Some(le_u32(cnrl, 0x10)?) } else { None }, // ValidNetType
+			net_name_unicode: if net_name_off_u == 0 {
+				None
+			} else {
+				Some(read_wstr(cnrl, net_name_off_u)?)
+			},
+			device_name_unicode: if has_device && device_name_off_u != 0 {
+				Some(read_wstr(cnrl, device_name_off_u)?)
+			} else {
+				None
+			},
+		})
+	} else {
+		None
+	};
+
+	Ok(ShellLinkInfo {
+		local,
+		network,
+		common_path_suffix: read_astr(data, le_u32(data, 0x18)? as _)?,
+		common_path_suffix_unicode: if suffix_off_u == 0 {
+			None
+		} else {
+			Some(read_wstr(data, suffix_off_u)?)
+		},
+	})
+}
+
+/// Writes the `LinkInfo` structure. The parts are laid out in the same order
+/// written by `IShellLink`.
+fn serialize_link_info(buf: &mut Vec<u8>, info: &ShellLinkInfo) {
+	let has_unicode = info.common_path_suffix_unicode.is_some()
+		|| info.local.as_ref().is_some_and(|local| local.base_path_unicode.is_some());
+	let header_sz = if has_unicode { 0x24 } else { 0x1c };
+
+	let mut body = Vec::<u8>::new();
+	let mut offsets = [0u32; 6]; // volume, base path, network, suffix, Unicode base path, Unicode suffix
+	let off_of = |body: &Vec<u8>| (header_sz + body.len()) as u32;
+
+	if let Some(local) = &info.local {
+		offsets[0] = off_of(&body);
+		let mut vol = Vec::<u8>::new();
+		vol.extend_from_slice(&local.drive_type.raw().to_le_bytes());
+		vol.extend_from_slice(&local.drive_serial_number.to_le_bytes());
+		if local.volume_label_unicode {
+			vol.extend_from_slice(&0x14u32.to_le_bytes());
+			vol.extend_from_slice(&0x14u32.to_le_bytes());
+			write_wstr(&mut vol, &local.volume_label);
+		} else {
+			vol.extend_from_slice(&0x10u32.to_le_bytes());
+			write_astr(&mut vol, &local.volume_label);
+		}
+		body.extend_from_slice(&((vol.len() + 4) as u32).to_le_bytes());
+		body.extend_from_slice(&vol);
+
+		offsets[1] = off_of(&body);
+		write_astr(&mut body, &local.base_path);
+	}
+
+	if let Some(network) = &info.network {
+		offsets[2] = off_of(&body);
+		body.extend_from_slice(&serialize_network(network));
+	}
+
+	offsets[3] = off_of(&body);
+	write_astr(&mut body, &info.common_path_suffix);
+
+	if has_unicode {
+		if let Some(local) = &info.local {
+			offsets[4] = off_of(&body);
+			write_wstr(&mut body, local.base_path_unicode.as_deref().unwrap_or(&local.base_path));
+		}
+		offsets[5] = off_of(&body);
+		write_wstr(&mut body, info.common_path_suffix_unicode.as_deref()
+			.unwrap_or(&info.common_path_suffix));
+	}
+
+	let flags = info.local.is_some() as u32 | (info.network.is_some() as u32) << 1;
+	buf.extend_from_slice(&((header_sz + body.len()) as u32).to_le_bytes());
+	buf.extend_from_slice(&(header_sz as u32).to_le_bytes());
+	buf.extend_from_slice(&flags.to_le_bytes());
+	for off in offsets[..if has_unicode { 6 } else { 4 }].iter() {
+		buf.extend_from_slice(&off.to_le_bytes());
+	}
+	buf.extend_from_slice(&body);
+}
+
+/// Returns the `CommonNetworkRelativeLink` structure.
+fn serialize_network(network: &ShellLinkNetwork) -> Vec<u8> {
+	let has_unicode = network.net_name_unicode.is_some() || network.device_name_unicode.is_some();
+	let header_sz = if has_unicode { 0x1c } else { 0x14 };
+
+	let mut body = Vec::<u8>::new();
+	let mut offsets = [0u32; 4]; // net name, device name, Unicode net name, Unicode device name
+	offsets[0] = (header_sz + body.len()) as _;
+	write_astr(&mut body, &network.net_name);
+	if let Some(device_name) = &network.device_name {
+		offsets[1] = (header_sz + body.len()) as _;
+		write_astr(&mut body, device_name);
+	}
+	if has_unicode {
+		offsets[2] = (header_sz + body.len()) as _;
+		write_wstr(&mut body, network.net_name_unicode.as_deref().unwrap_or(&network.net_name));
+		if let Some(device_name) = &network.device_name {
+			offsets[3] = (header_sz + body.len()) as _;
+			write_wstr(&mut body, network.device_name_unicode.as_deref().unwrap_or(device_name));
+		}
+	}
+
+	let flags = network.device_name.is_some() as u32 | (network.provider_type.is_some() as u32) << 1;
+	let mut buf = Vec::<u8>::with_capacity(header_sz + body.len());
+	buf.extend_from_slice(&((header_sz + body.len()) as u32).to_le_bytes());
+	buf.extend_from_slice(&flags.to_le_bytes());
+	buf.extend_from_slice(&offsets[0].to_le_bytes());
+	buf.extend_from_slice(&offsets[1].to_le_bytes());
+	buf.extend_from_slice(&network.provider_type.unwrap_or(0).to_le_bytes());
+	if has_unicode {
+		buf.extend_from_slice(&offsets[2].to_le_bytes());
+		buf.extend_from_slice(&offsets[3].to_le_bytes());
+	}
+	buf.extend_from_slice(&body);
+	buf
+}
+
+fn parse_property_store(data: &[u8]) -> SysResult<Vec<ShellLinkPropertyStorage>> {
+	let mut storages = Vec::<ShellLinkPropertyStorage>::new();
+	let mut off = 0;
+	loop {
+		let storage_sz = le_u32(data, off)? as usize;
+		if storage_sz == 0 {
+			break; // terminator
+		}
+		let storage = off.checked_add(storage_sz)
+			.and_then(|end| data.get(off..end))
+			.ok_or(co::ERROR::INVALID_DATA)?;
+		if le_u32(storage, 4)? != PROPSTORE_VERSION {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let format_id = read_guid(storage, 8)?;
+		let is_named = format_id == PROPSTORE_NAMED_FMTID;
+
+		let mut properties = Vec::<ShellLinkProperty>::new();
+		let mut prop_off = 24;
+		loop {
+			let value_sz = le_u32(storage, prop_off)? as usize;
+			if value_sz == 0 {
+				break; // terminator
+			}
+			let prop = prop_off.checked_add(value_sz)
+				.and_then(|end| storage.get(prop_off..end))
+				.ok_or(co::ERROR::INVALID_DATA)?;
+			properties.push(if is_named {
+				let name_sz = le_u32(prop, 4)? as usize;
+				let name_end = 9usize.checked_add(name_sz)
+					.filter(|end| *end <= prop.len())
+					.ok_or(co::ERROR::INVALID_DATA)?;
+				ShellLinkProperty {
+					id: 0,
+					name: Some(read_wstr_fixed(&prop[9..name_end])),
+					value: prop[name_end..].to_vec(),
+				}
+			} else {
+				ShellLinkProperty {
+					id: le_u32(prop, 4)?,
+					name: None,
+					value: prop.get(9..).ok_or(co::ERROR::INVALID_DATA)?.to_vec(),
+				}
+			});
+			prop_off += value_sz;
+		}
+
+		storages.push(ShellLinkPropertyStorage { format_id, properties });
+		off += storage_sz;
+	}
+	Ok(storages)
+}
+
+fn serialize_property_store(buf: &mut Vec<u8>, storages: &[ShellLinkPropertyStorage]) {
+	for storage in storages.iter() {
+		let start = buf.len();
+		buf.extend_from_slice(&[0; 4]); // size, filled below
+		buf.extend_from_slice(&PROPSTORE_VERSION.to_le_bytes());
+		write_guid(buf, &storage.format_id);
+
+		for prop in storage.properties.iter() {
+			let prop_start = buf.len();
+			buf.extend_from_slice(&[0; 4]); // size, filled below
+			if let Some(name) = &prop.name {
+				let mut name_buf = Vec::<u8>::new();
+				write_wstr(&mut name_buf, name);
+				buf.extend_from_slice(&(name_buf.len() as u32).to_le_bytes());
+				buf.push(0); // reserved
+				buf.extend_from_slice(&name_buf);
+			} else {
+				buf.extend_from_slice(&prop.id.to_le_bytes());
+				buf.push(0); // reserved
+			}
+			buf.extend_from_slice(&prop.value);
+			let prop_sz = (buf.len() - prop_start) as u32;
+			buf[prop_start..prop_start + 4].copy_from_slice(&prop_sz.to_le_bytes());
+		}
+		buf.extend_from_slice(&0u32.to_le_bytes()); // terminator
+
+		let storage_sz = (buf.len() - start) as u32;
+		buf[start..start + 4].copy_from_slice(&storage_sz.to_le_bytes());
+	}
+	buf.extend_from_slice(&0u32.to_le_bytes()); // terminator
+}
+
+fn decode_ansi(raw: &[u8]) -> String {
+	Encoding::Win1252.decode(raw).to_string()
+}
+
+fn wchars_of(raw: &[u8]) -> Vec<u16> {
+	raw.chunks_exact(2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.collect()
+}
+
+/// Reads a null-terminated ANSI string.
+fn read_astr(data: &[u8], off: usize) -> SysResult<String> {
+	let raw = data.get(off..).ok_or(co::ERROR::INVALID_DATA)?;
+	let len = raw.iter().position(|ch| *ch == 0).ok_or(co::ERROR::INVALID_DATA)?;
+	Ok(decode_ansi(&raw[..len]))
+}
+
+/// Reads a null-terminated Unicode string.
+fn read_wstr(data: &[u8], off: usize) -> SysResult<String> {
+	let wchars = wchars_of(data.get(off..).ok_or(co::ERROR::INVALID_DATA)?);
+	let len = wchars.iter().position(|ch| *ch == 0).ok_or(co::ERROR::INVALID_DATA)?;
+	Ok(String::from_utf16_lossy(&wchars[..len]))
+}
+
+/// Reads an ANSI string from a fixed-size buffer, up to the first null.
+fn read_astr_fixed(raw: &[u8]) -> String {
+	let len = raw.iter().position(|ch| *ch == 0).unwrap_or(raw.len());
+	decode_ansi(&raw[..len])
+}
+
+/// Reads a Unicode string from a fixed-size buffer, up to the first null.
+fn read_wstr_fixed(raw: &[u8]) -> String {
+	let wchars = wchars_of(raw);
+	let len = wchars.iter().position(|ch| *ch == 0).unwrap_or(wchars.len());
+	String::from_utf16_lossy(&wchars[..len])
+}
+
+fn write_astr(buf: &mut Vec<u8>, text: &str) {
+	buf.extend_from_slice(&Encoding::Win1252.encode(text));
+	buf.push(0);
+}
+
+fn write_wstr(buf: &mut Vec<u8>, text: &str) {
+	buf.extend(text.encode_utf16().flat_map(|ch| ch.to_le_bytes()));
+	buf.extend_from_slice(&[0, 0]);
+}
+
+/// Writes an ANSI string into a fixed-size, zero-padded buffer, truncating it
+/// to keep the terminating null.
+fn write_astr_fixed(buf: &mut Vec<u8>, text: &str, num_chars: usize) {
+	let mut raw = Encoding::Win1252.encode(text);
+	raw.resize(num_chars - 1, 0);
+	buf.extend_from_slice(&raw);
+	buf.push(0);
+}
+
+/// Writes a Unicode string into a fixed-size, zero-padded buffer, truncating
+/// it to keep the terminating null.
+fn write_wstr_fixed(buf: &mut Vec<u8>, text: &str, num_chars: usize) {
+	let mut wchars = text.encode_utf16().collect::<Vec<_>>();
+	wchars.resize(num_chars - 1, 0);
+	buf.extend(wchars.iter().flat_map(|ch| ch.to_le_bytes()));
+	buf.extend_from_slice(&[0, 0]);
+}
+
+fn read_guid(data: &[u8], off: usize) -> SysResult<GUID> {
+	let raw = data.get(off..off + 16).ok_or(co::ERROR::INVALID_DATA)?;
+	Ok(unsafe { std::ptr::read_unaligned(raw.as_ptr() as *const GUID) })
+}
+
+fn write_guid(buf: &mut Vec<u8>, guid: &GUID) {
+	buf.extend_from_slice(unsafe {
+		std::slice::from_raw_parts(guid as *const _ as *const u8, 16)
+	});
+}
+
+fn le_u16(data: &[u8], off: usize) -> SysResult<u16> {
+	data.get(off..off + 2)
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
+
+fn le_u32(data: &[u8], off: usize) -> SysResult<u32> {
+	data.get(off..off + 4)
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
+
+fn le_u64(data: &[u8], off: usize) -> SysResult<u64> {
+	data.get(off..off + 8)
+		.map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+		.ok_or(co::ERROR::INVALID_DATA)
+}
@@ -31,6 +31,9 @@ impl std::fmt::Display for CDERR {
 }
 impl std::fmt::Debug for CDERR {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if let Some(val_name) = crate::prelude::NativeConst::name(self) {
+			write!(f, "CDERR::{} ", val_name)?;
+		}
 		std::fmt::Display::fmt(self, f)
 	}
 }
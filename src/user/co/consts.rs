@@ -8,9 +8,13 @@ const_bitflag! { ACCELF: u8;
 	/// Originally has `F` prefix.
 	=>
 	=>
+	/// None of the actual values (zero).
+	NoValue 0
 	/// The `key` member specifies a virtual-key code. If this flag is not
 	/// specified key is assumed to specify a character code.
 	VIRTKEY 1
+	/// No top-level menu item is highlighted when the accelerator is used.
+	NOINVERT 0x02
 	/// The SHIFT key must be held down when the accelerator key is pressed.
 	SHIFT 0x04
 	/// The CTRL key must be held down when the accelerator key is pressed.
@@ -195,7 +199,7 @@ const_ordinary! { BST: u32;
 	FOCUS 0x0008
 }
 
-const_wm! { CB;
+const_wm! { CB, #[cfg(feature = "comctl")] COMCTL_NAMES;
 	/// Combo box control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-combobox-control-reference-messages)
 	/// (`u32`).
@@ -1105,6 +1109,33 @@ const_ordinary! { DLGID: u16;
 	CONTINUE 11
 }
 
+const_ws! { DS: u32;
+	/// Dialog box
+	/// [styles](https://learn.microsoft.com/en-us/windows/win32/dlgbox/dialog-box-styles)
+	/// (`u32`).
+	=>
+	=>
+	/// None of the actual values (zero).
+	NoValue 0
+	ABSALIGN 0x0001
+	SYSMODAL 0x0002
+	/// Originally `DS_3DLOOK`.
+	LOOK3D 0x0004
+	FIXEDSYS 0x0008
+	NOFAILCREATE 0x0010
+	LOCALEDIT 0x0020
+	SETFONT 0x0040
+	MODALFRAME 0x0080
+	NOIDLEMSG 0x0100
+	SETFOREGROUND 0x0200
+	CONTROL 0x0400
+	CENTER 0x0800
+	CENTERMOUSE 0x1000
+	CONTEXTHELP 0x2000
+	SHELLFONT Self::SETFONT.0 | Self::FIXEDSYS.0
+	USEPIXELS 0x8000
+}
+
 const_bitflag! { DT: u32;
 	/// [`HDC::DrawText`](crate::prelude::user_Hdc::DrawText) `format` (`u32`).
 	=>
@@ -1169,7 +1200,7 @@ const_bitflag! { EIMES: u16;
 	COMPLETECOMPSTRKILLFOCUS 0x0004
 }
 
-const_wm! { EM;
+const_wm! { EM, #[cfg(feature = "comctl")] COMCTL_NAMES;
 	/// Edit control
 	/// [messages](https://learn.microsoft.com/en-us/windows/win32/controls/bumper-edit-control-reference-messages)
 	/// (`u32`).
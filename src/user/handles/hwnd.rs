@@ -1682,14 +1682,12 @@ pub trait user_Hwnd: Handle {
 
 	/// [`SetWindowText`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowtextw)
 	/// function.
-	fn SetWindowText(&self, text: &str) -> SysResult<()> {
+	///
+	/// Accepts either a [`&str`](str), which is converted on each call, or a
+	/// [`WStr`](crate::WStr), which is passed without allocations.
+	fn SetWindowText(&self, text: impl ToWStr) -> SysResult<()> {
 		bool_to_sysresult(
-			unsafe {
-				ffi::SetWindowTextW(
-					self.ptr(),
-					WString::from_str(text).as_ptr(),
-				)
-			},
+			unsafe { ffi::SetWindowTextW(self.ptr(), text.to_wstr().as_ptr()) },
 		)
 	}
 
@@ -8,11 +8,13 @@ use crate::user::privs::*;
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct AddString {
-	pub text: WString,
+pub struct AddString<S = WString> {
+	pub text: S,
 }
 
-unsafe impl MsgSend for AddString {
+unsafe impl<S> MsgSend for AddString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -27,7 +29,7 @@ unsafe impl MsgSend for AddString {
 		WndMsg {
 			msg_id: co::CB::ADDSTRING.into(),
 			wparam: 0,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -63,12 +65,14 @@ unsafe impl MsgSend for DeleteString {
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct Dir {
+pub struct Dir<S = WString> {
 	pub attributes: co::DDL,
-	pub path: WString,
+	pub path: S,
 }
 
-unsafe impl MsgSend for Dir {
+unsafe impl<S> MsgSend for Dir<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -83,7 +87,7 @@ unsafe impl MsgSend for Dir {
 		WndMsg {
 			msg_id: co::CB::DIR.into(),
 			wparam: self.attributes.raw() as _,
-			lparam: self.path.as_ptr() as _,
+			lparam: self.path.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -92,12 +96,14 @@ unsafe impl MsgSend for Dir {
 /// message parameters.
 ///
 /// Return type: `Option<u32>`.
-pub struct FindString {
+pub struct FindString<S = WString> {
 	pub preceding_index: Option<u32>,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for FindString {
+unsafe impl<S> MsgSend for FindString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = Option<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -111,7 +117,7 @@ unsafe impl MsgSend for FindString {
 		WndMsg {
 			msg_id: co::CB::FINDSTRING.into(),
 			wparam: self.preceding_index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -120,12 +126,14 @@ unsafe impl MsgSend for FindString {
 /// message parameters.
 ///
 /// Return type: `Option<u32>`.
-pub struct FindStringExact {
+pub struct FindStringExact<S = WString> {
 	pub preceding_index: Option<u32>,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for FindStringExact {
+unsafe impl<S> MsgSend for FindStringExact<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = Option<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -139,7 +147,7 @@ unsafe impl MsgSend for FindStringExact {
 		WndMsg {
 			msg_id: co::CB::FINDSTRINGEXACT.into(),
 			wparam: self.preceding_index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -540,12 +548,14 @@ unsafe impl MsgSend for InitStorage {
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct InsertString {
+pub struct InsertString<S = WString> {
 	pub index: Option<u32>,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for InsertString {
+unsafe impl<S> MsgSend for InsertString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -560,7 +570,7 @@ unsafe impl MsgSend for InsertString {
 		WndMsg {
 			msg_id: co::CB::INSERTSTRING.into(),
 			wparam: self.index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -597,12 +607,14 @@ pub_struct_msg_empty! { ResetContent: co::CB::RESETCONTENT.into();
 /// message parameters.
 ///
 /// Return type: `Option<u32>`.
-pub struct SelectString {
+pub struct SelectString<S = WString> {
 	pub preceding_index: Option<u32>,
-	pub search_text: WString,
+	pub search_text: S,
 }
 
-unsafe impl MsgSend for SelectString {
+unsafe impl<S> MsgSend for SelectString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = Option<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -616,7 +628,7 @@ unsafe impl MsgSend for SelectString {
 		WndMsg {
 			msg_id: co::CB::SELECTSTRING.into(),
 			wparam: self.preceding_index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.search_text.as_ptr() as _,
+			lparam: self.search_text.as_ref().as_ptr() as _,
 		}
 	}
 }
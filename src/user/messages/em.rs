@@ -540,12 +540,14 @@ unsafe impl MsgSend for PosFromChar {
 /// message parameters.
 ///
 /// Return type: `()`.
-pub struct ReplaceSel {
+pub struct ReplaceSel<S = WString> {
 	pub can_be_undone: bool,
-	pub replacement_text: WString,
+	pub replacement_text: S,
 }
 
-unsafe impl MsgSend for ReplaceSel {
+unsafe impl<S> MsgSend for ReplaceSel<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = ();
 
 	fn convert_ret(&self, _: isize) -> Self::RetType {
@@ -556,7 +558,7 @@ unsafe impl MsgSend for ReplaceSel {
 		WndMsg {
 			msg_id: co::EM::REPLACESEL.into(),
 			wparam: self.can_be_undone as _,
-			lparam: self.replacement_text.as_ptr() as _,
+			lparam: self.replacement_text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -9,11 +9,13 @@ use crate::user::privs::*;
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct AddFile {
-	pub text: WString,
+pub struct AddFile<S = WString> {
+	pub text: S,
 }
 
-unsafe impl MsgSend for AddFile {
+unsafe impl<S> MsgSend for AddFile<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -28,7 +30,7 @@ unsafe impl MsgSend for AddFile {
 		WndMsg {
 			msg_id: co::LB::ADDFILE.into(),
 			wparam: 0,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -37,11 +39,13 @@ unsafe impl MsgSend for AddFile {
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct AddString {
-	pub text: WString,
+pub struct AddString<S = WString> {
+	pub text: S,
 }
 
-unsafe impl MsgSend for AddString {
+unsafe impl<S> MsgSend for AddString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -56,7 +60,7 @@ unsafe impl MsgSend for AddString {
 		WndMsg {
 			msg_id: co::LB::ADDSTRING.into(),
 			wparam: 0,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -92,12 +96,14 @@ unsafe impl MsgSend for DeleteString {
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct Dir {
+pub struct Dir<S = WString> {
 	pub attributes: co::DDL,
-	pub path: WString,
+	pub path: S,
 }
 
-unsafe impl MsgSend for Dir {
+unsafe impl<S> MsgSend for Dir<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -112,7 +118,7 @@ unsafe impl MsgSend for Dir {
 		WndMsg {
 			msg_id: co::LB::DIR.into(),
 			wparam: self.attributes.raw() as _,
-			lparam: self.path.as_ptr() as _,
+			lparam: self.path.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -121,12 +127,14 @@ unsafe impl MsgSend for Dir {
 /// message parameters.
 ///
 /// Return type: `Option<u32>`.
-pub struct FindString {
+pub struct FindString<S = WString> {
 	pub preceding_index: Option<u32>,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for FindString {
+unsafe impl<S> MsgSend for FindString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = Option<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -140,7 +148,7 @@ unsafe impl MsgSend for FindString {
 		WndMsg {
 			msg_id: co::LB::FINDSTRING.into(),
 			wparam: self.preceding_index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -149,12 +157,14 @@ unsafe impl MsgSend for FindString {
 /// message parameters.
 ///
 /// Return type: `Option<u32>`.
-pub struct FindStringExact {
+pub struct FindStringExact<S = WString> {
 	pub preceding_index: Option<u32>,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for FindStringExact {
+unsafe impl<S> MsgSend for FindStringExact<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = Option<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -168,7 +178,7 @@ unsafe impl MsgSend for FindStringExact {
 		WndMsg {
 			msg_id: co::LB::FINDSTRINGEXACT.into(),
 			wparam: self.preceding_index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -607,12 +617,14 @@ unsafe impl MsgSend for InitStorage {
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct InsertString {
+pub struct InsertString<S = WString> {
 	pub insertion_index: Option<u32>,
-	pub text: WString,
+	pub text: S,
 }
 
-unsafe impl MsgSend for InsertString {
+unsafe impl<S> MsgSend for InsertString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -627,7 +639,7 @@ unsafe impl MsgSend for InsertString {
 		WndMsg {
 			msg_id: co::LB::INSERTSTRING.into(),
 			wparam: self.insertion_index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.text.as_ptr() as _,
+			lparam: self.text.as_ref().as_ptr() as _,
 		}
 	}
 }
@@ -664,12 +676,14 @@ pub_struct_msg_empty! { ResetContent: co::LB::RESETCONTENT.into();
 /// message parameters.
 ///
 /// Return type: `SysResult<u32>`.
-pub struct SelectString {
+pub struct SelectString<S = WString> {
 	pub index: Option<u32>,
-	pub prefix: WString,
+	pub prefix: S,
 }
 
-unsafe impl MsgSend for SelectString {
+unsafe impl<S> MsgSend for SelectString<S>
+	where S: AsRef<WStr>,
+{
 	type RetType = SysResult<u32>;
 
 	fn convert_ret(&self, v: isize) -> Self::RetType {
@@ -684,7 +698,7 @@ unsafe impl MsgSend for SelectString {
 		WndMsg {
 			msg_id: co::LB::SELECTSTRING.into(),
 			wparam: self.index.map_or(-1, |idx| idx as i32) as _,
-			lparam: self.prefix.as_ptr() as _,
+			lparam: self.prefix.as_ref().as_ptr() as _,
 		}
 	}
 }
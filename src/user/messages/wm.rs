@@ -1550,11 +1550,9 @@ unsafe impl MsgSendRecv for SetIcon {
 /// let hwnd: w::HWND; // initialized somewhere
 /// # let hwnd = w::HWND::NULL;
 ///
-/// let new_text = w::WString::from_str("some text");
-///
 /// hwnd.SendMessage(
 ///     msg::wm::SetText {
-///         text: unsafe { new_text.as_ptr() },
+///         text: w::w!("some text").as_ptr(),
 ///     },
 /// );
 /// ```
@@ -0,0 +1,403 @@
+use crate::co;
+use crate::decl::*;
+
+/// The window class of a [`DialogItem`](crate::DialogItem).
+///
+/// The predefined system classes are written as ordinals, which is how the
+/// resource compiler stores them.
+#[derive(Clone, PartialEq, Eq)]
+pub enum DialogItemClass {
+	/// The `BUTTON` system class, ordinal `0x0080`.
+	Button,
+	/// The `EDIT` system class, ordinal `0x0081`.
+	Edit,
+	/// The `STATIC` system class, ordinal `0x0082`.
+	Static,
+	/// The `LISTBOX` system class, ordinal `0x0083`.
+	ListBox,
+	/// The `SCROLLBAR` system class, ordinal `0x0084`.
+	ScrollBar,
+	/// The `COMBOBOX` system class, ordinal `0x0085`.
+	ComboBox,
+	/// Any other registered class, like `SysListView32`.
+	Name(String),
+}
+
+impl DialogItemClass {
+	/// Returns the predefined system class with the given name,
+	/// case-insensitive, or a [`DialogItemClass::Name`] otherwise.
+	#[must_use]
+	pub fn from_name(name: &str) -> Self {
+		match name.to_ascii_uppercase().as_str() {
+			"BUTTON" => Self::Button,
+			"EDIT" => Self::Edit,
+			"STATIC" => Self::Static,
+			"LISTBOX" => Self::ListBox,
+			"SCROLLBAR" => Self::ScrollBar,
+			"COMBOBOX" => Self::ComboBox,
+			_ => Self::Name(name.to_owned()),
+		}
+	}
+
+	fn write(&self, buf: &mut Vec<u8>) {
+		let ord = match self {
+			Self::Button => 0x0080,
+			Self::Edit => 0x0081,
+			Self::Static => 0x0082,
+			Self::ListBox => 0x0083,
+			Self::ScrollBar => 0x0084,
+			Self::ComboBox => 0x0085,
+			Self::Name(name) => return write_str(buf, name),
+		};
+		write_u16(buf, 0xffff);
+		write_u16(buf, ord);
+	}
+}
+
+/// A single control of a [`DialogTemplate`](crate::DialogTemplate), which is
+/// serialized as a
+/// [`DLGITEMTEMPLATEEX`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/dlgitemtemplateex).
+///
+/// Position and size are given in dialog template units.
+#[derive(Clone)]
+pub struct DialogItem {
+	class: DialogItemClass,
+	text: IdStr,
+	ctrl_id: u32,
+	pos: POINT,
+	size: SIZE,
+	style: co::WS,
+	ex_style: co::WS_EX,
+	help_id: u32,
+	extra_data: Vec<u8>,
+}
+
+impl DialogItem {
+	/// Creates a new control.
+	///
+	/// The control-specific styles, like [`co::BS`](crate::co::BS) or
+	/// [`co::ES`](crate::co::ES), can be converted into
+	/// [`co::WS`](crate::co::WS) and combined with the window styles.
+	/// [`co::WS::CHILD`](crate::co::WS::CHILD) is always added.
+	///
+	/// The text can be an ordinal, which is used by static controls to display
+	/// an icon or a bitmap resource.
+	#[must_use]
+	pub fn new(
+		class: DialogItemClass,
+		text: IdStr,
+		ctrl_id: u16,
+		pos: POINT,
+		size: SIZE,
+		style: co::WS,
+	) -> Self
+	{
+		Self {
+			class,
+			text,
+			ctrl_id: ctrl_id as _,
+			pos,
+			size,
+			style,
+			ex_style: co::WS_EX::NoValue,
+			help_id: 0,
+			extra_data: Vec::new(),
+		}
+	}
+
+	/// Sets the extended window styles, which default to none.
+	#[must_use]
+	pub fn ex_style(mut self, ex_style: co::WS_EX) -> Self {
+		self.ex_style = ex_style;
+		self
+	}
+
+	/// Sets the context help identifier, which defaults to zero.
+	#[must_use]
+	pub fn help_id(mut self, help_id: u32) -> Self {
+		self.help_id = help_id;
+		self
+	}
+
+	/// Sets the creation data, which the system passes to the control in the
+	/// `lpCreateParams` member of
+	/// [`CREATESTRUCT`](crate::CREATESTRUCT). Defaults to none.
+	#[must_use]
+	pub fn extra_data(mut self, data: &[u8]) -> Self {
+		self.extra_data = data.to_vec();
+		self
+	}
+
+	fn write(&self, buf: &mut Vec<u8>) -> SysResult<()> {
+		pad4(buf);
+		write_u32(buf, self.help_id);
+		write_u32(buf, self.ex_style.raw());
+		write_u32(buf, (self.style | co::WS::CHILD).raw());
+		write_point_size(buf, self.pos, self.size)?;
+		write_u32(buf, self.ctrl_id);
+		self.class.write(buf);
+		write_id_str(buf, &self.text);
+
+		let extra_len = u16::try_from(self.extra_data.len())
+			.map_err(|_| co::ERROR::INVALID_DATA)?;
+		write_u16(buf, extra_len);
+		buf.extend_from_slice(&self.extra_data);
+		Ok(())
+	}
+}
+
+/// Builds the binary
+/// [`DLGTEMPLATEEX`](https://learn.microsoft.com/en-us/windows/win32/dlgbox/dlgtemplateex)
+/// data of a dialog box, so it can be created without a `.res` file.
+///
+/// The template can be passed to
+/// [`gui::WindowMain::new_dlg_template`](crate::gui::WindowMain::new_dlg_template)
+/// and the other `new_dlg_template` constructors of the `gui` windows, or
+/// serialized with [`build`](crate::DialogTemplate::build) and passed to
+/// [`HINSTANCE::DialogBoxIndirectParam`](crate::prelude::user_Hinstance::DialogBoxIndirectParam)
+/// and
+/// [`HINSTANCE::CreateDialogIndirectParam`](crate::prelude::user_Hinstance::CreateDialogIndirectParam).
+///
+/// Position and size are given in dialog template units.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let mut dlg = w::DialogTemplate::new(
+///     "My dialog",
+///     w::POINT::new(0, 0),
+///     w::SIZE::new(180, 60),
+///     co::WS::POPUP | co::WS::CAPTION | co::WS::SYSMENU,
+///     co::DS::MODALFRAME | co::DS::CENTER,
+/// );
+/// dlg.font("MS Shell Dlg", 8, 400, false, 1)
+///     .item(w::DialogItem::new(
+///         w::DialogItemClass::Static,
+///         w::IdStr::from_str("Name:"),
+///         1001,
+///         w::POINT::new(10, 12),
+///         w::SIZE::new(30, 8),
+///         co::WS::VISIBLE,
+///     ))
+///     .item(w::DialogItem::new(
+///         w::DialogItemClass::Edit,
+///         w::IdStr::from_str(""),
+///         1002,
+///         w::POINT::new(44, 10),
+///         w::SIZE::new(126, 12),
+///         co::WS::VISIBLE | co::WS::TABSTOP | co::WS::BORDER
+///             | co::ES::AUTOHSCROLL.into(),
+///     ))
+///     .item(w::DialogItem::new(
+///         w::DialogItemClass::Button,
+///         w::IdStr::from_str("OK"),
+///         co::DLGID::OK.raw(),
+///         w::POINT::new(120, 36),
+///         w::SIZE::new(50, 14),
+///         co::WS::VISIBLE | co::WS::TABSTOP | co::BS::DEFPUSHBUTTON.into(),
+///     ));
+///
+/// let template_bytes = dlg.build()?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+#[derive(Clone)]
+pub struct DialogTemplate {
+	title: String,
+	pos: POINT,
+	size: SIZE,
+	style: co::WS,
+	dlg_style: co::DS,
+	ex_style: co::WS_EX,
+	help_id: u32,
+	menu: Option<IdStr>,
+	class_name: Option<IdStr>,
+	font: Option<DialogTemplateFont>,
+	items: Vec<DialogItem>,
+}
+
+#[derive(Clone)]
+struct DialogTemplateFont {
+	face_name: String,
+	point_size: u16,
+	weight: u16,
+	italic: bool,
+	char_set: u8,
+}
+
+impl DialogTemplate {
+	/// Creates a new template with no controls, no menu, the default dialog
+	/// class and the system font.
+	#[must_use]
+	pub fn new(
+		title: &str,
+		pos: POINT,
+		size: SIZE,
+		style: co::WS,
+		dlg_style: co::DS,
+	) -> Self
+	{
+		Self {
+			title: title.to_owned(),
+			pos,
+			size,
+			style,
+			dlg_style,
+			ex_style: co::WS_EX::NoValue,
+			help_id: 0,
+			menu: None,
+			class_name: None,
+			font: None,
+			items: Vec::new(),
+		}
+	}
+
+	/// Sets the extended window styles, which default to none.
+	pub fn ex_style(&mut self, ex_style: co::WS_EX) -> &mut Self {
+		self.ex_style = ex_style;
+		self
+	}
+
+	/// Sets the context help identifier, which defaults to zero.
+	pub fn help_id(&mut self, help_id: u32) -> &mut Self {
+		self.help_id = help_id;
+		self
+	}
+
+	/// Sets the menu resource to be loaded from the module which creates the
+	/// dialog.
+	pub fn menu(&mut self, menu: IdStr) -> &mut Self {
+		self.menu = Some(menu);
+		self
+	}
+
+	/// Sets a registered window class to be used instead of the default dialog
+	/// class.
+	pub fn class_name(&mut self, class_name: IdStr) -> &mut Self {
+		self.class_name = Some(class_name);
+		self
+	}
+
+	/// Sets the font used by the dialog and its controls, which also implies
+	/// [`co::DS::SETFONT`](crate::co::DS::SETFONT).
+	///
+	/// The weight and the character set are the values of
+	/// [`LOGFONT`](crate::LOGFONT), like `400` for normal weight and `1` for
+	/// `DEFAULT_CHARSET`.
+	pub fn font(&mut self,
+		face_name: &str,
+		point_size: u16,
+		weight: u16,
+		italic: bool,
+		char_set: u8,
+	) -> &mut Self
+	{
+		self.font = Some(DialogTemplateFont {
+			face_name: face_name.to_owned(),
+			point_size,
+			weight,
+			italic,
+			char_set,
+		});
+		self
+	}
+
+	/// Appends a control, which will follow the tab order of insertion.
+	pub fn item(&mut self, item: DialogItem) -> &mut Self {
+		self.items.push(item);
+		self
+	}
+
+	/// Returns the controls added so far.
+	#[must_use]
+	pub fn items(&self) -> &[DialogItem] {
+		&self.items
+	}
+
+	/// Serializes the template into the binary `DLGTEMPLATEEX` format,
+	/// followed by all the `DLGITEMTEMPLATEEX` blocks.
+	///
+	/// Fails with [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA)
+	/// if there are more than 65535 controls, or if a coordinate or the
+	/// creation data of a control exceeds the limits of the format.
+	#[must_use]
+	pub fn build(&self) -> SysResult<Vec<u8>> {
+		let num_items = u16::try_from(self.items.len())
+			.map_err(|_| co::ERROR::INVALID_DATA)?;
+
+		let mut style = self.style | self.dlg_style.into();
+		if self.font.is_some() {
+			style |= co::DS::SETFONT.into();
+		} else {
+			style &= !co::WS::from(co::DS::SETFONT); // no font data to be read
+		}
+
+		let mut buf = Vec::<u8>::default();
+		write_u16(&mut buf, 1); // dlgVer
+		write_u16(&mut buf, 0xffff); // signature
+		write_u32(&mut buf, self.help_id);
+		write_u32(&mut buf, self.ex_style.raw());
+		write_u32(&mut buf, style.raw());
+		write_u16(&mut buf, num_items);
+		write_point_size(&mut buf, self.pos, self.size)?;
+		match &self.menu {
+			Some(menu) => write_id_str(&mut buf, menu),
+			None => write_u16(&mut buf, 0x0000),
+		}
+		match &self.class_name {
+			Some(class_name) => write_id_str(&mut buf, class_name),
+			None => write_u16(&mut buf, 0x0000),
+		}
+		write_str(&mut buf, &self.title);
+
+		if let Some(font) = &self.font {
+			write_u16(&mut buf, font.point_size);
+			write_u16(&mut buf, font.weight);
+			buf.push(font.italic as _);
+			buf.push(font.char_set);
+			write_str(&mut buf, &font.face_name);
+		}
+
+		for item in self.items.iter() {
+			item.write(&mut buf)?;
+		}
+		Ok(buf)
+	}
+}
+
+fn write_point_size(buf: &mut Vec<u8>, pos: POINT, size: SIZE) -> SysResult<()> {
+	for n in [pos.x, pos.y, size.cx, size.cy] {
+		let n = i16::try_from(n).map_err(|_| co::ERROR::INVALID_DATA)?;
+		buf.extend_from_slice(&n.to_le_bytes());
+	}
+	Ok(())
+}
+
+fn write_id_str(buf: &mut Vec<u8>, id_str: &IdStr) {
+	match id_str {
+		IdStr::Id(id) => {
+			write_u16(buf, 0xffff);
+			write_u16(buf, *id);
+		},
+		IdStr::Str(s) => write_str(buf, &s.to_string()),
+	}
+}
+
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+	s.encode_utf16()
+		.chain(std::iter::once(0x0000)) // terminating null
+		.for_each(|ch| write_u16(buf, ch));
+}
+
+fn write_u16(buf: &mut Vec<u8>, n: u16) {
+	buf.extend_from_slice(&n.to_le_bytes());
+}
+
+fn write_u32(buf: &mut Vec<u8>, n: u32) {
+	buf.extend_from_slice(&n.to_le_bytes());
+}
+
+fn pad4(buf: &mut Vec<u8>) {
+	buf.resize((buf.len() + 3) & !3, 0);
+}
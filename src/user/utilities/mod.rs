@@ -0,0 +1,8 @@
+mod dialog_template;
+mod rc_lexer;
+mod rc_script;
+mod rc_symbols;
+
+pub use dialog_template::{DialogItem, DialogItemClass, DialogTemplate};
+pub use rc_script::{RcAccelTable, RcControl, RcDialog, RcFont, RcId, RcMenu,
+	RcMenuItem, RcScript, RcStringTable, RcVersionInfo, RcVersionStrings};
@@ -0,0 +1,611 @@
+use std::collections::HashMap;
+
+use crate::co;
+use crate::decl::*;
+use crate::user::utilities::rc_symbols::builtin_symbol;
+
+/// Multi-char punctuators must come first, so they're matched before their
+/// single-char prefixes.
+const PUNCTS: [&str; 28] = [
+	"&&", "||", "==", "!=", "<=", ">=", "<<", ">>",
+	",", "|", "&", "^", "+", "-", "*", "/", "%", "~", "!", "(", ")", "{", "}",
+	"<", ">", "=", ".", "\\",
+];
+
+/// Limit of nested `#define` expansions, which also catches cycles.
+const MAX_DEPTH: usize = 32;
+
+/// Limit of nested unary operators and parentheses in an expression, counted
+/// across `#define` expansions.
+const MAX_NESTING: usize = 128;
+
+/// A single token of a resource script.
+#[derive(Clone, PartialEq, Eq)]
+pub(in crate::user) enum Tok {
+	Ident(String),
+	Num(i64),
+	Str(String),
+	Punct(&'static str),
+	/// Any char which is not part of the grammar, like in unquoted file names.
+	Other(char),
+}
+
+pub(in crate::user) struct Token {
+	pub(in crate::user) tok: Tok,
+	pub(in crate::user) line: usize,
+}
+
+/// Splits the text into tokens. Comments and preprocessor directives must
+/// have been removed already.
+pub(in crate::user) fn tokenize(text: &str) -> SysResult<Vec<Token>> {
+	let chars = text.chars().collect::<Vec<_>>();
+	let mut toks = Vec::<Token>::default();
+	let mut line = 1;
+	let mut i = 0;
+
+	while i < chars.len() {
+		let ch = chars[i];
+		if ch == '\n' {
+			line += 1;
+			i += 1;
+		} else if ch.is_whitespace() {
+			i += 1;
+		} else if ch == '"' || ((ch == 'L' || ch == 'l') && chars.get(i + 1) == Some(&'"')) {
+			if ch != '"' {
+				i += 1; // skip wide string prefix
+			}
+			let (s, next) = read_str(&chars, i + 1)?;
+			line += chars[i..next].iter().filter(|ch| **ch == '\n').count();
+			toks.push(Token { tok: Tok::Str(s), line });
+			i = next;
+		} else if ch.is_ascii_digit() {
+			let start = i;
+			while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+				i += 1;
+			}
+			let num_str = chars[start..i].iter().collect::<String>();
+			toks.push(Token { tok: Tok::Num(parse_num(&num_str)?), line });
+		} else if ch.is_alphabetic() || ch == '_' {
+			let start = i;
+			while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+				i += 1;
+			}
+			toks.push(Token { tok: Tok::Ident(chars[start..i].iter().collect()), line });
+		} else {
+			let punct = PUNCTS.iter()
+				.find(|p| p.chars().enumerate().all(|(n, pch)| chars.get(i + n) == Some(&pch)));
+			match punct {
+				Some(p) => {
+					toks.push(Token { tok: Tok::Punct(p), line });
+					i += p.len();
+				},
+				None => {
+					toks.push(Token { tok: Tok::Other(ch), line });
+					i += 1;
+				},
+			}
+		}
+	}
+
+	Ok(toks)
+}
+
+/// Reads a string literal whose opening quote precedes `start`, returning it
+/// along with the index right past the closing quote. A quote can be escaped
+/// either by doubling it or with a backslash.
+fn read_str(chars: &[char], start: usize) -> SysResult<(String, usize)> {
+	let mut s = String::default();
+	let mut i = start;
+	loop {
+		match chars.get(i) {
+			None => return Err(co::ERROR::INVALID_DATA), // unterminated string
+			Some('"') => {
+				if chars.get(i + 1) == Some(&'"') {
+					s.push('"');
+					i += 2;
+				} else {
+					return Ok((s, i + 1));
+				}
+			},
+			Some('\\') => {
+				i += 1;
+				match chars.get(i) {
+					None => return Err(co::ERROR::INVALID_DATA),
+					Some('n') => s.push('\n'),
+					Some('r') => s.push('\r'),
+					Some('t') => s.push('\t'),
+					Some('a') => s.push('\x07'),
+					Some('x') | Some('X') => {
+						let digits = chars[i + 1..].iter()
+							.take(4)
+							.take_while(|ch| ch.is_ascii_hexdigit())
+							.collect::<String>();
+						let code = u32::from_str_radix(&digits, 16)
+							.map_err(|_| co::ERROR::INVALID_DATA)?;
+						s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+						i += digits.len();
+					},
+					Some(ch) if ch.is_digit(8) => {
+						let digits = chars[i..].iter()
+							.take(3)
+							.take_while(|ch| ch.is_digit(8))
+							.collect::<String>();
+						let code = u32::from_str_radix(&digits, 8).unwrap(); // already validated
+						s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+						i += digits.len() - 1;
+					},
+					Some(ch) => s.push(*ch), // \\ and \" included
+				}
+				i += 1;
+			},
+			Some(ch) => {
+				s.push(*ch);
+				i += 1;
+			},
+		}
+	}
+}
+
+/// Parses a decimal or hexadecimal number, with an optional `L` or `U`
+/// suffix.
+fn parse_num(s: &str) -> SysResult<i64> {
+	let s = s.trim_end_matches(['L', 'l', 'U', 'u']);
+	let res = if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+		i64::from_str_radix(hex, 16)
+	} else {
+		s.parse::<i64>()
+	};
+	res.map_err(|_| co::ERROR::INVALID_DATA)
+}
+
+/// Removes `//` and `/* */` comments, keeping the line breaks so line numbers
+/// are preserved.
+fn strip_comments(text: &str) -> String {
+	let chars = text.chars().collect::<Vec<_>>();
+	let mut out = String::with_capacity(text.len());
+	let mut i = 0;
+
+	while i < chars.len() {
+		let ch = chars[i];
+		if ch == '"' {
+			out.push(ch);
+			i += 1;
+			while i < chars.len() && chars[i] != '"' && chars[i] != '\n' {
+				if chars[i] == '\\' && i + 1 < chars.len() && chars[i + 1] != '\n' {
+					out.push(chars[i]);
+					i += 1;
+				}
+				out.push(chars[i]);
+				i += 1;
+			}
+			if i < chars.len() && chars[i] == '"' {
+				out.push('"');
+				i += 1;
+			}
+		} else if ch == '/' && chars.get(i + 1) == Some(&'/') {
+			while i < chars.len() && chars[i] != '\n' {
+				i += 1;
+			}
+		} else if ch == '/' && chars.get(i + 1) == Some(&'*') {
+			i += 2;
+			while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+				if chars[i] == '\n' {
+					out.push('\n');
+				}
+				i += 1;
+			}
+			i += 2;
+			out.push(' ');
+		} else {
+			out.push(ch);
+			i += 1;
+		}
+	}
+
+	out
+}
+
+/// A conditional block, opened by `#if`, `#ifdef` or `#ifndef`.
+struct Cond {
+	parent_active: bool,
+	active: bool,
+	taken: bool,
+}
+
+/// Strips the comments and runs the preprocessor directives, collecting the
+/// `#define` symbols. Returns the remaining text, where directive lines and
+/// the lines excluded by conditionals are blank.
+///
+/// `#include` directives are ignored, since there's no file system access.
+pub(in crate::user) fn preprocess(
+	text: &str,
+	defines: &mut HashMap<String, String>,
+) -> SysResult<String>
+{
+	let text = strip_comments(text);
+	let mut out = String::with_capacity(text.len());
+	let mut conds = Vec::<Cond>::default();
+	let mut lines = text.split('\n');
+
+	while let Some(line) = lines.next() {
+		let mut line = line.trim_end_matches('\r').to_owned();
+		let mut num_lines = 1;
+		while line.ends_with('\\') { // line continuation
+			line.pop();
+			match lines.next() {
+				Some(next) => line.push_str(next.trim_end_matches('\r')),
+				None => break,
+			}
+			num_lines += 1;
+		}
+
+		let active = conds.iter().all(|cond| cond.active);
+		let trimmed = line.trim_start();
+
+		if let Some(directive) = trimmed.strip_prefix('#') {
+			let directive = directive.trim_start();
+			let name_len = directive.find(|ch: char| !ch.is_ascii_alphabetic())
+				.unwrap_or(directive.len());
+			let (name, rest) = directive.split_at(name_len);
+			let rest = rest.trim();
+
+			match name {
+				"if" => {
+					let cond = active && eval_pp(rest, defines)? != 0;
+					conds.push(Cond { parent_active: active, active: cond, taken: cond });
+				},
+				"ifdef" | "ifndef" => {
+					let is_def = defines.contains_key(first_word(rest));
+					let cond = active && (is_def == (name == "ifdef"));
+					conds.push(Cond { parent_active: active, active: cond, taken: cond });
+				},
+				"elif" => {
+					let cond = conds.last_mut().ok_or(co::ERROR::INVALID_DATA)?;
+					if cond.parent_active && !cond.taken && eval_pp(rest, defines)? != 0 {
+						cond.active = true;
+						cond.taken = true;
+					} else {
+						cond.active = false;
+					}
+				},
+				"else" => {
+					let cond = conds.last_mut().ok_or(co::ERROR::INVALID_DATA)?;
+					cond.active = cond.parent_active && !cond.taken;
+					cond.taken = true;
+				},
+				"endif" => {
+					conds.pop().ok_or(co::ERROR::INVALID_DATA)?;
+				},
+				"define" if active => {
+					let sym = first_word(rest);
+					if sym.is_empty() {
+						return Err(co::ERROR::INVALID_DATA);
+					}
+					let value = &rest[sym.len()..];
+					if !value.starts_with('(') { // function-like macros are not supported
+						defines.insert(sym.to_owned(), value.trim().to_owned());
+					}
+				},
+				"undef" if active => {
+					defines.remove(first_word(rest));
+				},
+				_ => {}, // #include, #pragma and the like
+			}
+		} else if active {
+			out.push_str(&line);
+		}
+
+		(0..num_lines).for_each(|_| out.push('\n'));
+	}
+
+	if conds.is_empty() {
+		Ok(out)
+	} else {
+		Err(co::ERROR::INVALID_DATA) // missing #endif
+	}
+}
+
+fn first_word(s: &str) -> &str {
+	let len = s.find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+		.unwrap_or(s.len());
+	&s[..len]
+}
+
+/// Evaluates the expression of an `#if` or `#elif` directive.
+fn eval_pp(expr: &str, defines: &HashMap<String, String>) -> SysResult<i64> {
+	let toks = tokenize(expr)?;
+	let mut stream = RcTokens::new(toks, defines);
+	stream.is_pp = true;
+	let val = stream.expr()?;
+	if stream.is_eof() {
+		Ok(val)
+	} else {
+		Err(co::ERROR::INVALID_DATA)
+	}
+}
+
+/// A cursor over the tokens of a resource script, which also evaluates
+/// numeric expressions.
+pub(in crate::user) struct RcTokens<'a> {
+	toks: Vec<Token>,
+	pos: usize,
+	defines: &'a HashMap<String, String>,
+	/// Within `#if`, unknown symbols evaluate to zero and `defined` is valid.
+	is_pp: bool,
+	depth: usize,
+	nesting: usize,
+}
+
+impl<'a> RcTokens<'a> {
+	pub(in crate::user) fn new(
+		toks: Vec<Token>,
+		defines: &'a HashMap<String, String>,
+	) -> Self
+	{
+		Self { toks, pos: 0, defines, is_pp: false, depth: 0, nesting: 0 }
+	}
+
+	pub(in crate::user) fn is_eof(&self) -> bool {
+		self.pos >= self.toks.len()
+	}
+
+	pub(in crate::user) fn peek(&self) -> Option<&Tok> {
+		self.toks.get(self.pos).map(|t| &t.tok)
+	}
+
+	pub(in crate::user) fn next(&mut self) -> Option<Tok> {
+		let tok = self.toks.get(self.pos).map(|t| t.tok.clone());
+		if tok.is_some() {
+			self.pos += 1;
+		}
+		tok
+	}
+
+	/// Line of the current token, or zero at the end.
+	pub(in crate::user) fn line(&self) -> usize {
+		self.toks.get(self.pos).map_or(0, |t| t.line)
+	}
+
+	/// Line of the previous token, or zero at the beginning.
+	pub(in crate::user) fn prev_line(&self) -> usize {
+		if self.pos == 0 { 0 } else { self.toks[self.pos - 1].line }
+	}
+
+	/// Whether the current token is the given keyword, case-insensitive.
+	pub(in crate::user) fn is_ident(&self, kw: &str) -> bool {
+		matches!(self.peek(), Some(Tok::Ident(id)) if id.eq_ignore_ascii_case(kw))
+	}
+
+	pub(in crate::user) fn is_punct(&self, punct: &str) -> bool {
+		matches!(self.peek(), Some(Tok::Punct(p)) if *p == punct)
+	}
+
+	pub(in crate::user) fn eat_ident(&mut self, kw: &str) -> bool {
+		let is = self.is_ident(kw);
+		if is {
+			self.pos += 1;
+		}
+		is
+	}
+
+	pub(in crate::user) fn eat_punct(&mut self, punct: &str) -> bool {
+		let is = self.is_punct(punct);
+		if is {
+			self.pos += 1;
+		}
+		is
+	}
+
+	pub(in crate::user) fn expect_punct(&mut self, punct: &str) -> SysResult<()> {
+		if self.eat_punct(punct) { Ok(()) } else { Err(co::ERROR::INVALID_DATA) }
+	}
+
+	/// Whether the current token opens a block, either `BEGIN` or `{`.
+	pub(in crate::user) fn is_begin(&self) -> bool {
+		self.is_ident("BEGIN") || self.is_punct("{")
+	}
+
+	/// Whether the current token closes a block, either `END` or `}`.
+	pub(in crate::user) fn is_end(&self) -> bool {
+		self.is_ident("END") || self.is_punct("}")
+	}
+
+	pub(in crate::user) fn expect_begin(&mut self) -> SysResult<()> {
+		if self.is_begin() {
+			self.pos += 1;
+			Ok(())
+		} else {
+			Err(co::ERROR::INVALID_DATA)
+		}
+	}
+
+	/// Consumes the block end, if present.
+	pub(in crate::user) fn eat_end(&mut self) -> bool {
+		let is = self.is_end();
+		if is {
+			self.pos += 1;
+		}
+		is
+	}
+
+	/// Skips a whole block, including the nested ones. The current token must
+	/// be the block opening.
+	pub(in crate::user) fn skip_block(&mut self) -> SysResult<()> {
+		self.expect_begin()?;
+		let mut level = 1;
+		while level > 0 {
+			if self.is_begin() {
+				level += 1;
+			} else if self.is_end() {
+				level -= 1;
+			}
+			self.next().ok_or(co::ERROR::INVALID_DATA)?;
+		}
+		Ok(())
+	}
+
+	/// Skips all the tokens which are in the same line of the previous one,
+	/// stopping at a block opening.
+	pub(in crate::user) fn skip_line(&mut self) {
+		let line = self.prev_line();
+		while !self.is_eof() && self.line() == line && !self.is_begin() {
+			self.pos += 1;
+		}
+	}
+
+	pub(in crate::user) fn string(&mut self) -> SysResult<String> {
+		match self.next() {
+			Some(Tok::Str(s)) => Ok(s),
+			_ => Err(co::ERROR::INVALID_DATA),
+		}
+	}
+
+	/// Returns the numeric value of a symbol, either defined in the script or
+	/// built-in.
+	pub(in crate::user) fn symbol(&self, name: &str) -> SysResult<Option<i64>> {
+		if let Some(text) = self.defines.get(name) {
+			if self.depth >= MAX_DEPTH {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			let mut stream = RcTokens::new(tokenize(text)?, self.defines);
+			stream.is_pp = self.is_pp;
+			stream.depth = self.depth + 1;
+			stream.nesting = self.nesting;
+			let val = stream.expr()?;
+			if stream.is_eof() { Ok(Some(val)) } else { Err(co::ERROR::INVALID_DATA) }
+		} else {
+			Ok(builtin_symbol(name).map(|val| val as _))
+		}
+	}
+
+	/// Evaluates a numeric expression, with the usual C operators.
+	pub(in crate::user) fn expr(&mut self) -> SysResult<i64> {
+		let mut val = self.and_expr()?;
+		while self.eat_punct("||") {
+			let rhs = self.and_expr()?;
+			val = (val != 0 || rhs != 0) as _;
+		}
+		Ok(val)
+	}
+
+	/// Evaluates a style expression, where the values are combined with `|`
+	/// into the default ones, and the `NOT` keyword removes them.
+	pub(in crate::user) fn style_expr(&mut self, default: u32) -> SysResult<u32> {
+		self.or_terms(default as _).map(|val| val as _)
+	}
+
+	fn and_expr(&mut self) -> SysResult<i64> {
+		let mut val = self.or_terms(0)?;
+		while self.eat_punct("&&") {
+			let rhs = self.or_terms(0)?;
+			val = (val != 0 && rhs != 0) as _;
+		}
+		Ok(val)
+	}
+
+	fn or_terms(&mut self, mut acc: i64) -> SysResult<i64> {
+		loop {
+			if self.eat_ident("NOT") {
+				acc &= !self.binary(0)?;
+			} else {
+				acc |= self.binary(0)?;
+			}
+			if !self.eat_punct("|") {
+				return Ok(acc);
+			}
+		}
+	}
+
+	/// Evaluates the binary operators from the given precedence level on,
+	/// lowest first.
+	fn binary(&mut self, level: usize) -> SysResult<i64> {
+		const LEVELS: [&[&str]; 7] = [
+			&["^"], &["&"], &["==", "!="], &["<", ">", "<=", ">="],
+			&["<<", ">>"], &["+", "-"], &["*", "/", "%"],
+		];
+		if level == LEVELS.len() {
+			return self.unary();
+		}
+
+		let mut val = self.binary(level + 1)?;
+		while let Some(op) = LEVELS[level].iter().find(|op| self.is_punct(op)) {
+			self.pos += 1;
+			let rhs = self.binary(level + 1)?;
+			val = match *op {
+				"^" => val ^ rhs,
+				"&" => val & rhs,
+				"==" => (val == rhs) as _,
+				"!=" => (val != rhs) as _,
+				"<" => (val < rhs) as _,
+				">" => (val > rhs) as _,
+				"<=" => (val <= rhs) as _,
+				">=" => (val >= rhs) as _,
+				"<<" => val.wrapping_shl(rhs as _),
+				">>" => val.wrapping_shr(rhs as _),
+				"+" => val.wrapping_add(rhs),
+				"-" => val.wrapping_sub(rhs),
+				"*" => val.wrapping_mul(rhs),
+				_ => {
+					if rhs == 0 {
+						return Err(co::ERROR::INVALID_DATA); // division by zero
+					}
+					if *op == "/" { val.wrapping_div(rhs) } else { val.wrapping_rem(rhs) }
+				},
+			};
+		}
+		Ok(val)
+	}
+
+	/// Every recursion in an expression goes through here, so this is where
+	/// the nesting is limited.
+	fn unary(&mut self) -> SysResult<i64> {
+		if self.nesting >= MAX_NESTING {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		self.nesting += 1;
+		let val = self.unary_op();
+		self.nesting -= 1;
+		val
+	}
+
+	fn unary_op(&mut self) -> SysResult<i64> {
+		if self.eat_punct("-") {
+			Ok(self.unary()?.wrapping_neg())
+		} else if self.eat_punct("+") {
+			self.unary()
+		} else if self.eat_punct("~") {
+			Ok(!self.unary()?)
+		} else if self.eat_punct("!") {
+			Ok((self.unary()? == 0) as _)
+		} else if self.is_pp && self.eat_ident("defined") {
+			let has_paren = self.eat_punct("(");
+			let name = match self.next() {
+				Some(Tok::Ident(name)) => name,
+				_ => return Err(co::ERROR::INVALID_DATA),
+			};
+			if has_paren {
+				self.expect_punct(")")?;
+			}
+			Ok(self.defines.contains_key(&name) as _)
+		} else {
+			self.primary()
+		}
+	}
+
+	fn primary(&mut self) -> SysResult<i64> {
+		match self.next() {
+			Some(Tok::Num(n)) => Ok(n),
+			Some(Tok::Punct("(")) => {
+				let val = self.expr()?;
+				self.expect_punct(")")?;
+				Ok(val)
+			},
+			Some(Tok::Ident(name)) => match self.symbol(&name)? {
+				Some(val) => Ok(val),
+				None if self.is_pp => Ok(0),
+				None => Err(co::ERROR::INVALID_DATA), // undefined symbol
+			},
+			_ => Err(co::ERROR::INVALID_DATA),
+		}
+	}
+}
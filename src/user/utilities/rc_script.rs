@@ -0,0 +1,1001 @@
+use std::collections::HashMap;
+
+use crate::co;
+use crate::decl::*;
+use crate::guard::*;
+use crate::prelude::*;
+use crate::user::utilities::rc_lexer::{preprocess, RcTokens, Tok, tokenize};
+
+/// The identifier of a resource in a [`RcScript`](crate::RcScript), or the
+/// text of a control or a menu which refers to a resource.
+///
+/// Unlike [`IdStr`](crate::IdStr), the string is a Rust [`String`], so the
+/// parser doesn't depend on Windows allocations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RcId {
+	/// A numeric ID.
+	Id(u16),
+	/// A string identifier.
+	Str(String),
+}
+
+impl std::fmt::Display for RcId {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Id(id) => write!(f, "ID: {}", id),
+			Self::Str(s) => write!(f, "Str: {}", s),
+		}
+	}
+}
+
+impl RcId {
+	/// Converts into an [`IdStr`](crate::IdStr).
+	#[must_use]
+	pub fn to_id_str(&self) -> IdStr {
+		match self {
+			Self::Id(id) => IdStr::Id(*id),
+			Self::Str(s) => IdStr::from_str(s),
+		}
+	}
+}
+
+/// A `DIALOG` or `DIALOGEX` resource of a [`RcScript`](crate::RcScript).
+///
+/// Position and size are given in dialog template units.
+#[derive(Clone)]
+pub struct RcDialog {
+	/// Name or numeric ID of the resource.
+	pub id: RcId,
+	/// Language of the resource, from the `LANGUAGE` statement.
+	pub language: LANGID,
+	/// Position, in dialog units.
+	pub pos: POINT,
+	/// Size, in dialog units.
+	pub size: SIZE,
+	/// Help context ID, only present in `DIALOGEX`.
+	pub help_id: u32,
+	/// Window styles, including the dialog styles, as declared in the `STYLE`
+	/// statement.
+	pub style: co::WS,
+	/// Extended window styles, as declared in the `EXSTYLE` statement.
+	pub ex_style: co::WS_EX,
+	/// Title, as declared in the `CAPTION` statement.
+	pub caption: String,
+	/// Menu, as declared in the `MENU` statement.
+	pub menu: Option<RcId>,
+	/// Window class, as declared in the `CLASS` statement.
+	pub class_name: Option<RcId>,
+	/// Font, as declared in the `FONT` statement.
+	pub font: Option<RcFont>,
+	/// The controls, in declaration order.
+	pub controls: Vec<RcControl>,
+}
+
+impl RcDialog {
+	/// Creates a [`DialogTemplate`](crate::DialogTemplate) with the dialog and
+	/// its controls, which can be passed to the `new_dlg_template`
+	/// constructors of the `gui` windows.
+	#[must_use]
+	pub fn to_template(&self) -> DialogTemplate {
+		let mut template = DialogTemplate::new(
+			&self.caption, self.pos, self.size, self.style, co::DS::NoValue);
+		template.ex_style(self.ex_style)
+			.help_id(self.help_id);
+
+		if let Some(menu) = &self.menu {
+			template.menu(menu.to_id_str());
+		}
+		if let Some(class_name) = &self.class_name {
+			template.class_name(class_name.to_id_str());
+		}
+		if let Some(font) = &self.font {
+			template.font(&font.face_name, font.point_size, font.weight,
+				font.italic, font.char_set);
+		}
+
+		for ctrl in self.controls.iter() {
+			template.item(
+				DialogItem::new(ctrl.class.clone(), ctrl.text.to_id_str(),
+					ctrl.ctrl_id, ctrl.pos, ctrl.size, ctrl.style)
+					.ex_style(ctrl.ex_style)
+					.help_id(ctrl.help_id),
+			);
+		}
+		template
+	}
+}
+
+/// The `FONT` statement of a [`RcDialog`](crate::RcDialog).
+#[derive(Clone)]
+pub struct RcFont {
+	/// Size of the font, in points.
+	pub point_size: u16,
+	/// Name of the typeface.
+	pub face_name: String,
+	/// Weight of the font, only present in `DIALOGEX`; defaults to 400.
+	pub weight: u16,
+	/// Whether the font is italic, only present in `DIALOGEX`.
+	pub italic: bool,
+	/// Character set, only present in `DIALOGEX`; defaults to 1.
+	pub char_set: u8,
+}
+
+/// A control of a [`RcDialog`](crate::RcDialog), declared either with the
+/// generic `CONTROL` statement or with a specific one, like `PUSHBUTTON`.
+///
+/// The style already contains the defaults of the specific statement, plus
+/// [`co::WS::CHILD`](crate::co::WS::CHILD) and
+/// [`co::WS::VISIBLE`](crate::co::WS::VISIBLE), unless removed with `NOT`.
+#[derive(Clone)]
+pub struct RcControl {
+	/// Window class of the control.
+	pub class: DialogItemClass,
+	/// Text of the control, or the resource ID of its image.
+	pub text: RcId,
+	/// Control ID.
+	pub ctrl_id: u16,
+	/// Position, in dialog units.
+	pub pos: POINT,
+	/// Size, in dialog units.
+	pub size: SIZE,
+	/// Window styles, including the control-specific ones.
+	pub style: co::WS,
+	/// Extended window styles.
+	pub ex_style: co::WS_EX,
+	/// Help context ID, only present in `DIALOGEX`.
+	pub help_id: u32,
+}
+
+/// A `MENU` or `MENUEX` resource of a [`RcScript`](crate::RcScript).
+#[derive(Clone)]
+pub struct RcMenu {
+	/// Name or numeric ID of the resource.
+	pub id: RcId,
+	/// Language of the resource, from the `LANGUAGE` statement.
+	pub language: LANGID,
+	/// The top-level items.
+	pub items: Vec<RcMenuItem>,
+}
+
+impl RcMenu {
+	/// Creates a menu bar with all the items, using
+	/// [`HMENU::CreateMenu`](crate::prelude::user_Hmenu::CreateMenu) and
+	/// [`HMENU::CreatePopupMenu`](crate::prelude::user_Hmenu::CreatePopupMenu)
+	/// for the submenus.
+	///
+	/// Unless attached to a window, the menu must be freed with
+	/// [`HMENU::DestroyMenu`](crate::prelude::user_Hmenu::DestroyMenu).
+	#[must_use]
+	pub fn create_menu(&self) -> SysResult<HMENU> {
+		let mut hmenu = HMENU::CreateMenu()?;
+		if let Err(e) = Self::insert_items(&hmenu, &self.items) {
+			hmenu.DestroyMenu().ok(); // also destroys the submenus already inserted
+			return Err(e);
+		}
+		Ok(hmenu)
+	}
+
+	/// Creates a popup menu with all the items, which can be displayed with
+	/// [`HMENU::TrackPopupMenu`](crate::prelude::user_Hmenu::TrackPopupMenu).
+	///
+	/// The menu must be freed with
+	/// [`HMENU::DestroyMenu`](crate::prelude::user_Hmenu::DestroyMenu).
+	#[must_use]
+	pub fn create_popup_menu(&self) -> SysResult<HMENU> {
+		let mut hmenu = HMENU::CreatePopupMenu()?;
+		if let Err(e) = Self::insert_items(&hmenu, &self.items) {
+			hmenu.DestroyMenu().ok();
+			return Err(e);
+		}
+		Ok(hmenu)
+	}
+
+	fn insert_items(hmenu: &HMENU, items: &[RcMenuItem]) -> SysResult<()> {
+		for (idx, item) in items.iter().enumerate() {
+			let mut mii = MENUITEMINFO::default();
+			mii.fMask = co::MIIM::FTYPE | co::MIIM::STATE | co::MIIM::ID;
+			mii.fType = item.item_type;
+			mii.fState = item.state;
+			mii.wID = item.cmd_id;
+
+			let mut text = WString::from_str(&item.text);
+			if item.item_type & co::MFT::SEPARATOR != co::MFT::SEPARATOR {
+				mii.fMask |= co::MIIM::STRING;
+				mii.dwTypeData = text.as_mut_slice().as_mut_ptr();
+				mii.cch = text.str_len() as _;
+			}
+
+			let mut hsub = None;
+			if let Some(sub_items) = &item.sub_items {
+				let mut hpopup = HMENU::CreatePopupMenu()?;
+				if let Err(e) = Self::insert_items(&hpopup, sub_items) {
+					hpopup.DestroyMenu().ok();
+					return Err(e);
+				}
+				mii.fMask |= co::MIIM::SUBMENU;
+				mii.hSubMenu = unsafe { hpopup.raw_copy() };
+				hsub = Some(hpopup);
+			}
+
+			if let Err(e) = hmenu.InsertMenuItem(IdPos::Pos(idx as _), &mii) {
+				if let Some(mut hpopup) = hsub {
+					hpopup.DestroyMenu().ok(); // not owned by the parent yet
+				}
+				return Err(e);
+			}
+		}
+		Ok(())
+	}
+}
+
+/// An item of a [`RcMenu`](crate::RcMenu).
+///
+/// Items declared with the legacy `MENU` statement have their options
+/// converted into the equivalent type and state flags.
+#[derive(Clone)]
+pub struct RcMenuItem {
+	/// Text of the item, empty for separators.
+	pub text: String,
+	/// Command ID, sent in [`wm::Command`](crate::msg::wm::Command).
+	pub cmd_id: u32,
+	/// Item type, like separator or menu break.
+	pub item_type: co::MFT,
+	/// Item state, like checked or grayed.
+	pub state: co::MFS,
+	/// Only present in `POPUP` items of `MENUEX`.
+	pub help_id: u32,
+	/// The items of a `POPUP`, if this is a submenu.
+	pub sub_items: Option<Vec<RcMenuItem>>,
+}
+
+/// A `STRINGTABLE` of a [`RcScript`](crate::RcScript).
+#[derive(Clone)]
+pub struct RcStringTable {
+	/// Language of the resource, from the `LANGUAGE` statement.
+	pub language: LANGID,
+	/// The pairs of string ID and text.
+	pub strings: Vec<(u16, String)>,
+}
+
+/// An `ACCELERATORS` resource of a [`RcScript`](crate::RcScript).
+#[derive(Clone)]
+pub struct RcAccelTable {
+	/// Name or numeric ID of the resource.
+	pub id: RcId,
+	/// Language of the resource, from the `LANGUAGE` statement.
+	pub language: LANGID,
+	/// The accelerators, in declaration order.
+	pub accels: Vec<ACCEL>,
+}
+
+impl RcAccelTable {
+	/// Creates the accelerator table with
+	/// [`HACCEL::CreateAcceleratorTable`](crate::prelude::user_Haccel::CreateAcceleratorTable).
+	#[must_use]
+	pub fn create_accelerator_table(&self) -> SysResult<DestroyAcceleratorTableGuard> {
+		let mut accels = self.accels.clone();
+		HACCEL::CreateAcceleratorTable(&mut accels)
+	}
+}
+
+/// A `VERSIONINFO` resource of a [`RcScript`](crate::RcScript).
+///
+/// The fixed information has the raw values of the
+/// [`VS_FIXEDFILEINFO`](https://learn.microsoft.com/en-us/windows/win32/api/verrsrc/ns-verrsrc-vs_fixedfileinfo)
+/// fields, so they can be passed to a `VersionInfoBuilder`, of the `version`
+/// feature.
+#[derive(Clone)]
+pub struct RcVersionInfo {
+	/// Name or numeric ID of the resource.
+	pub id: RcId,
+	/// Language of the resource, from the `LANGUAGE` statement.
+	pub language: LANGID,
+	/// The `FILEVERSION` statement.
+	pub file_version: [u16; 4],
+	/// The `PRODUCTVERSION` statement.
+	pub product_version: [u16; 4],
+	/// The `FILEFLAGSMASK` statement.
+	pub file_flags_mask: u32,
+	/// The `FILEFLAGS` statement.
+	pub file_flags: u32,
+	/// The `FILEOS` statement.
+	pub file_os: u32,
+	/// The `FILETYPE` statement.
+	pub file_type: u32,
+	/// The `FILESUBTYPE` statement.
+	pub file_subtype: u32,
+	/// The blocks of `StringFileInfo`.
+	pub string_tables: Vec<RcVersionStrings>,
+	/// The pairs of the `Translation` value of the `VarFileInfo` block.
+	pub translations: Vec<(LANGID, co::CP)>,
+}
+
+/// A `StringFileInfo` block of a [`RcVersionInfo`](crate::RcVersionInfo).
+#[derive(Clone)]
+pub struct RcVersionStrings {
+	/// Language, from the block name.
+	pub lang_id: LANGID,
+	/// Code page, from the block name.
+	pub code_page: co::CP,
+	/// The pairs of name and value, like `FileDescription`.
+	pub strings: Vec<(String, String)>,
+}
+
+/// Parsed resource script – an `.rc` file – with the dialogs, menus, string
+/// tables, accelerators and version information.
+///
+/// The parser is written in pure Rust, and understands the `#define`, `#undef`,
+/// `#if`, `#ifdef`, `#ifndef`, `#elif`, `#else` and `#endif` directives.
+/// `#include` directives are ignored, so the contents of `resource.h` must be
+/// passed separately. The symbols of `windows.h` and `commctrl.h` commonly used
+/// in resource scripts, like `WS_CHILD` or `IDOK`, are built-in.
+///
+/// Other resources, like icons and bitmaps, are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, gui};
+///
+/// let rc_text = w::File::open("C:\\Temp\\app.rc", w::FileAccess::ExistingReadOnly)?
+///     .read_all()?;
+/// let h_text = w::File::open("C:\\Temp\\resource.h", w::FileAccess::ExistingReadOnly)?
+///     .read_all()?;
+///
+/// let script = w::RcScript::parse(
+///     &String::from_utf8_lossy(&rc_text),
+///     Some(&String::from_utf8_lossy(&h_text)),
+/// )?;
+///
+/// let dlg = script.dialogs.iter()
+///     .find(|dlg| dlg.id == w::RcId::Id(script.symbol("IDD_MAIN").unwrap() as _))
+///     .unwrap();
+/// let wnd = gui::WindowMain::new_dlg_template(&dlg.to_template(), None, None);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct RcScript {
+	/// `DIALOG` and `DIALOGEX` resources.
+	pub dialogs: Vec<RcDialog>,
+	/// `MENU` and `MENUEX` resources.
+	pub menus: Vec<RcMenu>,
+	/// `STRINGTABLE` resources.
+	pub string_tables: Vec<RcStringTable>,
+	/// `ACCELERATORS` resources.
+	pub accelerators: Vec<RcAccelTable>,
+	/// `VERSIONINFO` resources.
+	pub version_infos: Vec<RcVersionInfo>,
+	defines: HashMap<String, String>,
+}
+
+impl RcScript {
+	/// Parses the text of a resource script, optionally preceded by the text
+	/// of its `resource.h` header.
+	///
+	/// Fails with [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA)
+	/// if the script is malformed, or if it uses an undefined symbol.
+	#[must_use]
+	pub fn parse(rc_text: &str, header_text: Option<&str>) -> SysResult<Self> {
+		let mut defines = HashMap::<String, String>::default();
+		if let Some(header_text) = header_text {
+			preprocess(header_text, &mut defines)?;
+		}
+		let text = preprocess(rc_text, &mut defines)?;
+
+		let mut script = Self {
+			dialogs: Vec::new(),
+			menus: Vec::new(),
+			string_tables: Vec::new(),
+			accelerators: Vec::new(),
+			version_infos: Vec::new(),
+			defines: HashMap::new(),
+		};
+		let mut parser = Parser {
+			toks: RcTokens::new(tokenize(&text)?, &defines),
+			language: unsafe { LANGID::from_raw(0) }, // LANG_NEUTRAL, SUBLANG_NEUTRAL
+		};
+		parser.parse_script(&mut script)?;
+
+		script.defines = defines;
+		Ok(script)
+	}
+
+	/// Returns the numeric value of a symbol, either defined in the script, in
+	/// the header or built-in.
+	#[must_use]
+	pub fn symbol(&self, name: &str) -> Option<i32> {
+		RcTokens::new(Vec::new(), &self.defines)
+			.symbol(name)
+			.ok()
+			.flatten()
+			.map(|val| val as _)
+	}
+
+	/// Returns the string with the given ID, searching all the string tables.
+	#[must_use]
+	pub fn string(&self, id: u16) -> Option<&str> {
+		self.string_tables.iter()
+			.flat_map(|table| table.strings.iter())
+			.find(|(str_id, _)| *str_id == id)
+			.map(|(_, s)| s.as_str())
+	}
+}
+
+struct Parser<'a> {
+	toks: RcTokens<'a>,
+	language: LANGID,
+}
+
+impl<'a> Parser<'a> {
+	fn parse_script(&mut self, script: &mut RcScript) -> SysResult<()> {
+		while !self.toks.is_eof() {
+			if self.toks.eat_ident("LANGUAGE") {
+				self.language = self.language()?;
+			} else if self.toks.eat_ident("STRINGTABLE") {
+				script.string_tables.push(self.string_table()?);
+			} else {
+				let id = self.name_or_id()?;
+				let res_type = match self.toks.next() {
+					Some(Tok::Ident(res_type)) => res_type.to_ascii_uppercase(),
+					Some(_) => String::default(), // numeric or quoted types
+					None => return Err(co::ERROR::INVALID_DATA),
+				};
+				match res_type.as_str() {
+					"DIALOG" => script.dialogs.push(self.dialog(id, false)?),
+					"DIALOGEX" => script.dialogs.push(self.dialog(id, true)?),
+					"MENU" => script.menus.push(self.menu(id, false)?),
+					"MENUEX" => script.menus.push(self.menu(id, true)?),
+					"ACCELERATORS" => script.accelerators.push(self.accelerators(id)?),
+					"VERSIONINFO" => script.version_infos.push(self.version_info(id)?),
+					_ => self.skip_resource()?,
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Skips an unsupported resource, which either refers to a file in the
+	/// same line, or has its data in a block, which may start in the same line.
+	fn skip_resource(&mut self) -> SysResult<()> {
+		self.toks.skip_line();
+		if self.toks.is_begin() {
+			self.toks.skip_block()?;
+		}
+		Ok(())
+	}
+
+	/// Skips the memory flags, which are obsolete.
+	fn skip_memory_flags(&mut self) {
+		while ["PRELOAD", "LOADONCALL", "FIXED", "MOVEABLE", "DISCARDABLE",
+			"PURE", "IMPURE", "SHARED", "NONSHARED"].iter().any(|kw| self.toks.eat_ident(kw)) {}
+	}
+
+	fn language(&mut self) -> SysResult<LANGID> {
+		let lang = self.toks.expr()?;
+		self.toks.expect_punct(",")?;
+		let sub_lang = self.toks.expr()?;
+		Ok(LANGID::new(
+			unsafe { co::LANG::from_raw(lang as _) },
+			unsafe { co::SUBLANG::from_raw(sub_lang as _) },
+		))
+	}
+
+	/// Parses the optional statements common to all resources, returning
+	/// `false` if the current token is none of them.
+	fn common_statement(&mut self, language: &mut LANGID) -> SysResult<bool> {
+		if self.toks.eat_ident("LANGUAGE") {
+			*language = self.language()?;
+		} else if self.toks.eat_ident("CHARACTERISTICS") || self.toks.eat_ident("VERSION") {
+			self.toks.expr()?;
+		} else {
+			return Ok(false);
+		}
+		Ok(true)
+	}
+
+	/// Parses a resource name, which is numeric if it's a number or a defined
+	/// symbol. Undefined symbols become uppercase strings, like the resource
+	/// compiler does.
+	fn name_or_id(&mut self) -> SysResult<RcId> {
+		match self.toks.peek() {
+			Some(Tok::Str(_)) => Ok(RcId::Str(self.toks.string()?)),
+			Some(Tok::Ident(name)) => {
+				let name = name.clone();
+				match self.toks.symbol(&name)? {
+					Some(val) => {
+						self.toks.next();
+						Ok(RcId::Id(val as _))
+					},
+					None => {
+						self.toks.next();
+						Ok(RcId::Str(name.to_ascii_uppercase()))
+					},
+				}
+			},
+			_ => Ok(RcId::Id(self.toks.expr()? as _)),
+		}
+	}
+
+	/// Parses the text of a control, which is either a string or an ordinal.
+	fn text_or_ord(&mut self) -> SysResult<RcId> {
+		match self.toks.peek() {
+			Some(Tok::Str(_)) => Ok(RcId::Str(self.toks.string()?)),
+			_ => self.name_or_id(),
+		}
+	}
+
+	fn point_size(&mut self) -> SysResult<(POINT, SIZE)> {
+		let mut vals = [0i32; 4];
+		for (idx, val) in vals.iter_mut().enumerate() {
+			if idx > 0 {
+				self.toks.expect_punct(",")?;
+			}
+			*val = self.toks.expr()? as _;
+		}
+		Ok((POINT::new(vals[0], vals[1]), SIZE::new(vals[2], vals[3])))
+	}
+
+	fn dialog(&mut self, id: RcId, is_ex: bool) -> SysResult<RcDialog> {
+		self.skip_memory_flags();
+		let (pos, size) = self.point_size()?;
+		let help_id = if is_ex && self.toks.eat_punct(",") { self.toks.expr()? as _ } else { 0 };
+
+		let mut dlg = RcDialog {
+			id,
+			language: self.language,
+			pos,
+			size,
+			help_id,
+			style: co::WS::POPUP | co::WS::BORDER | co::WS::SYSMENU,
+			ex_style: co::WS_EX::NoValue,
+			caption: String::default(),
+			menu: None,
+			class_name: None,
+			font: None,
+			controls: Vec::new(),
+		};
+
+		let mut has_style = false;
+		while !self.toks.is_begin() {
+			if self.common_statement(&mut dlg.language)? {
+				// nothing else to do
+			} else if self.toks.eat_ident("STYLE") {
+				dlg.style = unsafe { co::WS::from_raw(self.toks.style_expr(0)?) };
+				has_style = true;
+			} else if self.toks.eat_ident("EXSTYLE") {
+				dlg.ex_style = unsafe { co::WS_EX::from_raw(self.toks.style_expr(0)?) };
+			} else if self.toks.eat_ident("CAPTION") {
+				dlg.caption = self.toks.string()?;
+			} else if self.toks.eat_ident("MENU") {
+				dlg.menu = Some(self.name_or_id()?);
+			} else if self.toks.eat_ident("CLASS") {
+				dlg.class_name = Some(self.name_or_id()?);
+			} else if self.toks.eat_ident("FONT") {
+				let point_size = self.toks.expr()? as _;
+				self.toks.expect_punct(",")?;
+				let face_name = self.toks.string()?;
+				let mut extra = [400, 0, 1]; // weight, italic, charset
+				for val in extra.iter_mut() {
+					if !is_ex || !self.toks.eat_punct(",") {
+						break;
+					}
+					*val = self.toks.expr()?;
+				}
+				dlg.font = Some(RcFont {
+					point_size,
+					face_name,
+					weight: extra[0] as _,
+					italic: extra[1] != 0,
+					char_set: extra[2] as _,
+				});
+			} else {
+				return Err(co::ERROR::INVALID_DATA); // unknown statement
+			}
+		}
+
+		if !dlg.caption.is_empty() && !has_style {
+			dlg.style |= co::WS::CAPTION;
+		}
+
+		self.toks.expect_begin()?;
+		while !self.toks.eat_end() {
+			if self.toks.is_eof() {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			dlg.controls.push(self.control(is_ex)?);
+		}
+		Ok(dlg)
+	}
+
+	fn control(&mut self, is_ex: bool) -> SysResult<RcControl> {
+		let stmt = match self.toks.next() {
+			Some(Tok::Ident(stmt)) => stmt.to_ascii_uppercase(),
+			_ => return Err(co::ERROR::INVALID_DATA),
+		};
+
+		// Same defaults of the resource compiler, which sometimes differ from the
+		// documentation.
+		let ws = |ws: co::WS| ws.raw();
+		let (class, default_style, has_text) = match stmt.as_str() {
+			"CONTROL" => (None, 0, true),
+			"LTEXT" => (Some(DialogItemClass::Static), co::SS::LEFT.raw() | ws(co::WS::GROUP), true),
+			"RTEXT" => (Some(DialogItemClass::Static), co::SS::RIGHT.raw() | ws(co::WS::GROUP), true),
+			"CTEXT" => (Some(DialogItemClass::Static), co::SS::CENTER.raw() | ws(co::WS::GROUP), true),
+			"ICON" => (Some(DialogItemClass::Static), co::SS::ICON.raw(), true),
+			"PUSHBUTTON" => (Some(DialogItemClass::Button), co::BS::PUSHBUTTON.raw() | ws(co::WS::TABSTOP), true),
+			"DEFPUSHBUTTON" => (Some(DialogItemClass::Button), co::BS::DEFPUSHBUTTON.raw() | ws(co::WS::TABSTOP), true),
+			"PUSHBOX" => (Some(DialogItemClass::Button), co::BS::PUSHBOX.raw() | ws(co::WS::TABSTOP), true),
+			"CHECKBOX" => (Some(DialogItemClass::Button), co::BS::CHECKBOX.raw() | ws(co::WS::TABSTOP), true),
+			"AUTOCHECKBOX" => (Some(DialogItemClass::Button), co::BS::AUTOCHECKBOX.raw() | ws(co::WS::TABSTOP), true),
+			"STATE3" => (Some(DialogItemClass::Button), co::BS::R3STATE.raw() | ws(co::WS::TABSTOP), true),
+			"AUTO3STATE" => (Some(DialogItemClass::Button), co::BS::AUTO3STATE.raw() | ws(co::WS::TABSTOP), true),
+			"RADIOBUTTON" => (Some(DialogItemClass::Button), co::BS::RADIOBUTTON.raw(), true),
+			"AUTORADIOBUTTON" => (Some(DialogItemClass::Button), co::BS::AUTORADIOBUTTON.raw(), true),
+			"GROUPBOX" => (Some(DialogItemClass::Button), co::BS::GROUPBOX.raw(), true),
+			"EDITTEXT" => (Some(DialogItemClass::Edit), co::ES::LEFT.raw() | ws(co::WS::BORDER | co::WS::TABSTOP), false),
+			"COMBOBOX" => (Some(DialogItemClass::ComboBox), 0, false),
+			"LISTBOX" => (Some(DialogItemClass::ListBox), co::LBS::NOTIFY.raw() | ws(co::WS::BORDER), false),
+			"SCROLLBAR" => (Some(DialogItemClass::ScrollBar), 0, false), // SBS_HORZ
+			_ => return Err(co::ERROR::INVALID_DATA), // unknown control statement
+		};
+		let default_style = default_style | ws(co::WS::CHILD | co::WS::VISIBLE);
+
+		let text = if has_text {
+			let text = self.text_or_ord()?;
+			self.toks.expect_punct(",")?;
+			text
+		} else {
+			RcId::Str(String::default())
+		};
+		let ctrl_id = self.toks.expr()? as u16;
+		self.toks.expect_punct(",")?;
+
+		let mut ctrl = RcControl {
+			class: DialogItemClass::Static,
+			text,
+			ctrl_id,
+			pos: POINT::default(),
+			size: SIZE::default(),
+			style: co::WS::NoValue,
+			ex_style: co::WS_EX::NoValue,
+			help_id: 0,
+		};
+
+		match class {
+			None => { // CONTROL text, id, class, style, x, y, cx, cy [, exstyle [, helpid]]
+				ctrl.class = match self.toks.next() {
+					Some(Tok::Str(name)) | Some(Tok::Ident(name)) => DialogItemClass::from_name(&name),
+					_ => return Err(co::ERROR::INVALID_DATA),
+				};
+				self.toks.expect_punct(",")?;
+				ctrl.style = unsafe { co::WS::from_raw(self.toks.style_expr(default_style)?) };
+				self.toks.expect_punct(",")?;
+				(ctrl.pos, ctrl.size) = self.point_size()?;
+			},
+			Some(class) => { // STMT [text,] id, x, y, cx, cy [, style [, exstyle [, helpid]]]
+				ctrl.class = class;
+				if stmt == "ICON" { // ICON text, id, x, y [, cx, cy [, style ...]]
+					let x = self.toks.expr()? as _;
+					self.toks.expect_punct(",")?;
+					let y = self.toks.expr()? as _;
+					ctrl.pos = POINT::new(x, y);
+					if self.toks.eat_punct(",") {
+						let cx = self.toks.expr()? as _;
+						self.toks.expect_punct(",")?;
+						let cy = self.toks.expr()? as _;
+						ctrl.size = SIZE::new(cx, cy);
+					}
+				} else {
+					(ctrl.pos, ctrl.size) = self.point_size()?;
+				}
+				ctrl.style = unsafe { co::WS::from_raw(default_style) };
+				if self.toks.eat_punct(",") {
+					ctrl.style = unsafe { co::WS::from_raw(self.toks.style_expr(default_style)?) };
+				}
+			},
+		}
+
+		if self.toks.eat_punct(",") {
+			ctrl.ex_style = unsafe { co::WS_EX::from_raw(self.toks.style_expr(0)?) };
+			if is_ex && self.toks.eat_punct(",") {
+				ctrl.help_id = self.toks.expr()? as _;
+			}
+		}
+
+		if self.toks.is_begin() {
+			self.toks.skip_block()?; // creation data is not supported
+		}
+		Ok(ctrl)
+	}
+
+	fn menu(&mut self, id: RcId, is_ex: bool) -> SysResult<RcMenu> {
+		self.skip_memory_flags();
+		let mut language = self.language;
+		while self.common_statement(&mut language)? {}
+		let items = self.menu_items(is_ex)?;
+		Ok(RcMenu { id, language, items })
+	}
+
+	fn menu_items(&mut self, is_ex: bool) -> SysResult<Vec<RcMenuItem>> {
+		self.toks.expect_begin()?;
+		let mut items = Vec::<RcMenuItem>::default();
+
+		while !self.toks.eat_end() {
+			let is_popup = if self.toks.eat_ident("POPUP") {
+				true
+			} else if self.toks.eat_ident("MENUITEM") {
+				false
+			} else {
+				return Err(co::ERROR::INVALID_DATA);
+			};
+
+			let mut item = RcMenuItem {
+				text: String::default(),
+				cmd_id: 0,
+				item_type: co::MFT::STRING,
+				state: co::MFS::ENABLED,
+				help_id: 0,
+				sub_items: None,
+			};
+
+			if !is_popup && self.toks.eat_ident("SEPARATOR") {
+				item.item_type = co::MFT::SEPARATOR;
+			} else {
+				item.text = self.toks.string()?;
+				if is_ex {
+					self.menu_ex_options(&mut item, is_popup)?;
+				} else {
+					if !is_popup {
+						self.toks.expect_punct(",")?;
+						item.cmd_id = self.toks.expr()? as _;
+					}
+					self.menu_options(&mut item);
+				}
+			}
+
+			if is_popup {
+				item.sub_items = Some(self.menu_items(is_ex)?);
+			}
+			items.push(item);
+		}
+		Ok(items)
+	}
+
+	/// Parses the options of a legacy `MENU` item, separated by commas or
+	/// spaces.
+	fn menu_options(&mut self, item: &mut RcMenuItem) {
+		loop {
+			self.toks.eat_punct(",");
+			if self.toks.eat_ident("CHECKED") {
+				item.state |= co::MFS::CHECKED;
+			} else if self.toks.eat_ident("GRAYED") {
+				item.state |= unsafe { co::MFS::from_raw(co::MF::GRAYED.raw()) };
+			} else if self.toks.eat_ident("INACTIVE") {
+				item.state |= unsafe { co::MFS::from_raw(co::MF::DISABLED.raw()) };
+			} else if self.toks.eat_ident("HELP") {
+				item.item_type |= co::MFT::RIGHTJUSTIFY;
+			} else if self.toks.eat_ident("MENUBARBREAK") {
+				item.item_type |= co::MFT::MENUBARBREAK;
+			} else if self.toks.eat_ident("MENUBREAK") {
+				item.item_type |= co::MFT::MENUBREAK;
+			} else {
+				return;
+			}
+		}
+	}
+
+	/// Parses the optional `[, id [, type [, state [, helpid]]]]` of a
+	/// `MENUEX` item, where any value can be omitted.
+	fn menu_ex_options(&mut self, item: &mut RcMenuItem, is_popup: bool) -> SysResult<()> {
+		let num_vals = if is_popup { 4 } else { 3 };
+		for idx in 0..num_vals {
+			if !self.toks.eat_punct(",") {
+				break;
+			}
+			if self.toks.is_punct(",") || self.toks.is_begin() || self.toks.is_end()
+				|| self.toks.is_ident("MENUITEM") || self.toks.is_ident("POPUP")
+			{
+				continue; // omitted value
+			}
+			let val = self.toks.style_expr(0)?;
+			match idx {
+				0 => item.cmd_id = val,
+				1 => item.item_type = unsafe { co::MFT::from_raw(val) },
+				2 => item.state = unsafe { co::MFS::from_raw(val) },
+				_ => item.help_id = val,
+			}
+		}
+		Ok(())
+	}
+
+	fn string_table(&mut self) -> SysResult<RcStringTable> {
+		self.skip_memory_flags();
+		let mut language = self.language;
+		while self.common_statement(&mut language)? {}
+
+		self.toks.expect_begin()?;
+		let mut strings = Vec::<(u16, String)>::default();
+		while !self.toks.eat_end() {
+			let id = self.toks.expr()? as u16;
+			self.toks.eat_punct(",");
+			strings.push((id, self.toks.string()?));
+		}
+		Ok(RcStringTable { language, strings })
+	}
+
+	fn accelerators(&mut self, id: RcId) -> SysResult<RcAccelTable> {
+		self.skip_memory_flags();
+		let mut language = self.language;
+		while self.common_statement(&mut language)? {}
+
+		self.toks.expect_begin()?;
+		let mut accels = Vec::<ACCEL>::default();
+		while !self.toks.eat_end() {
+			let event = match self.toks.peek() {
+				Some(Tok::Str(_)) => Some(self.toks.string()?),
+				_ => None,
+			};
+			let key_num = match event {
+				Some(_) => 0,
+				None => self.toks.expr()?,
+			};
+			self.toks.expect_punct(",")?;
+			let cmd = self.toks.expr()? as u16;
+
+			let mut flags = co::ACCELF::NoValue;
+			while self.toks.eat_punct(",") {
+				let flag = if self.toks.eat_ident("VIRTKEY") {
+					co::ACCELF::VIRTKEY
+				} else if self.toks.eat_ident("ASCII") {
+					co::ACCELF::NoValue
+				} else if self.toks.eat_ident("NOINVERT") {
+					co::ACCELF::NOINVERT
+				} else if self.toks.eat_ident("ALT") {
+					co::ACCELF::ALT
+				} else if self.toks.eat_ident("SHIFT") {
+					co::ACCELF::SHIFT
+				} else if self.toks.eat_ident("CONTROL") {
+					co::ACCELF::CONTROL
+				} else {
+					return Err(co::ERROR::INVALID_DATA);
+				};
+				flags |= flag;
+			}
+
+			let is_virt = flags & co::ACCELF::VIRTKEY == co::ACCELF::VIRTKEY;
+			let key = match event {
+				None => key_num as u16,
+				Some(s) => {
+					let chars = s.encode_utf16().collect::<Vec<_>>();
+					match chars.as_slice() {
+						[0x5e, ch] if !is_virt => { // "^C" is a control char
+							let ch = *ch as u8;
+							(ch.to_ascii_uppercase().wrapping_sub(b'@')) as u16
+						},
+						[ch] if is_virt => (*ch as u8).to_ascii_uppercase() as _,
+						[ch] => *ch,
+						_ => return Err(co::ERROR::INVALID_DATA),
+					}
+				},
+			};
+
+			accels.push(ACCEL {
+				fVirt: flags,
+				key: unsafe { co::VK::from_raw(key) },
+				cmd,
+			});
+		}
+		Ok(RcAccelTable { id, language, accels })
+	}
+
+	fn version_info(&mut self, id: RcId) -> SysResult<RcVersionInfo> {
+		let mut ver = RcVersionInfo {
+			id,
+			language: self.language,
+			file_version: [0; 4],
+			product_version: [0; 4],
+			file_flags_mask: 0,
+			file_flags: 0,
+			file_os: 0,
+			file_type: 0,
+			file_subtype: 0,
+			string_tables: Vec::new(),
+			translations: Vec::new(),
+		};
+
+		while !self.toks.is_begin() {
+			if self.common_statement(&mut ver.language)? {
+				// nothing else to do
+			} else if self.toks.eat_ident("FILEVERSION") {
+				ver.file_version = self.version_numbers()?;
+			} else if self.toks.eat_ident("PRODUCTVERSION") {
+				ver.product_version = self.version_numbers()?;
+			} else if self.toks.eat_ident("FILEFLAGSMASK") {
+				ver.file_flags_mask = self.toks.style_expr(0)?;
+			} else if self.toks.eat_ident("FILEFLAGS") {
+				ver.file_flags = self.toks.style_expr(0)?;
+			} else if self.toks.eat_ident("FILEOS") {
+				ver.file_os = self.toks.style_expr(0)?;
+			} else if self.toks.eat_ident("FILETYPE") {
+				ver.file_type = self.toks.style_expr(0)?;
+			} else if self.toks.eat_ident("FILESUBTYPE") {
+				ver.file_subtype = self.toks.style_expr(0)?;
+			} else {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+		}
+
+		self.toks.expect_begin()?;
+		while !self.toks.eat_end() {
+			if !self.toks.eat_ident("BLOCK") {
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			let block_name = self.toks.string()?;
+			if block_name.eq_ignore_ascii_case("StringFileInfo") {
+				self.toks.expect_begin()?;
+				while !self.toks.eat_end() {
+					if !self.toks.eat_ident("BLOCK") {
+						return Err(co::ERROR::INVALID_DATA);
+					}
+					let table_key = self.toks.string()?;
+					let lang_cp = u32::from_str_radix(&table_key, 16)
+						.map_err(|_| co::ERROR::INVALID_DATA)?;
+					let mut strings = Vec::<(String, String)>::default();
+
+					self.toks.expect_begin()?;
+					while !self.toks.eat_end() {
+						if !self.toks.eat_ident("VALUE") {
+							return Err(co::ERROR::INVALID_DATA);
+						}
+						let key = self.toks.string()?;
+						self.toks.expect_punct(",")?;
+						let mut val = String::default();
+						while let Some(Tok::Str(_)) = self.toks.peek() { // adjacent strings are concatenated
+							val.push_str(&self.toks.string()?);
+						}
+						strings.push((key, val.trim_end_matches('\0').to_owned()));
+					}
+
+					ver.string_tables.push(RcVersionStrings {
+						lang_id: unsafe { LANGID::from_raw((lang_cp >> 16) as _) },
+						code_page: unsafe { co::CP::from_raw((lang_cp & 0xffff) as _) },
+						strings,
+					});
+				}
+			} else if block_name.eq_ignore_ascii_case("VarFileInfo") {
+				self.toks.expect_begin()?;
+				while !self.toks.eat_end() {
+					if !self.toks.eat_ident("VALUE") {
+						return Err(co::ERROR::INVALID_DATA);
+					}
+					let key = self.toks.string()?;
+					while self.toks.eat_punct(",") {
+						let lang_id = self.toks.expr()?;
+						self.toks.expect_punct(",")?;
+						let code_page = self.toks.expr()?;
+						if key.eq_ignore_ascii_case("Translation") {
+							ver.translations.push((
+								unsafe { LANGID::from_raw(lang_id as _) },
+								unsafe { co::CP::from_raw(code_page as _) },
+							));
+						}
+					}
+				}
+			} else {
+				self.toks.skip_block()?;
+			}
+		}
+		Ok(ver)
+	}
+
+	/// Parses up to four comma-separated version numbers.
+	fn version_numbers(&mut self) -> SysResult<[u16; 4]> {
+		let mut nums = [0u16; 4];
+		for (idx, num) in nums.iter_mut().enumerate() {
+			if idx > 0 && !self.toks.eat_punct(",") {
+				break;
+			}
+			*num = self.toks.expr()? as _;
+		}
+		Ok(nums)
+	}
+}
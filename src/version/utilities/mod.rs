@@ -1,3 +1,6 @@
 mod resource_info;
+mod ver_block;
+mod version_info_builder;
 
 pub use resource_info::{ResourceInfo, ResourceInfoBlock};
+pub use version_info_builder::VersionInfoBuilder;
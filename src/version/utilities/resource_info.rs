@@ -1,9 +1,15 @@
 use crate::co;
 use crate::decl::*;
+use crate::version::utilities::ver_block::{StringTable, VerBlock};
 
 /// Retrieves data from an embedded resource, which can be read from an
 /// EXE or a DLL file.
 ///
+/// The
+/// [`VS_VERSIONINFO`](https://learn.microsoft.com/en-us/windows/win32/menurc/vs-versioninfo)
+/// tree is walked natively, so the data can also be parsed from raw bytes with
+/// [`ResourceInfo::parse`](crate::ResourceInfo::parse).
+///
 /// # Examples
 ///
 /// Reading version information:
@@ -42,55 +48,138 @@ use crate::decl::*;
 /// }
 /// # Ok::<_, winsafe::co::ERROR>(())
 /// ```
+///
+/// Listing all the strings of an extracted resource:
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*};
+///
+/// let raw_bytes: Vec<u8>; // initialized somewhere
+/// # let raw_bytes = Vec::<u8>::new();
+/// let res_info = w::ResourceInfo::parse(&raw_bytes)?;
+///
+/// for block in res_info.blocks() {
+///     for (key, val) in block.strings() {
+///         println!("{}: {}", key, val);
+///     }
+/// }
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
 pub struct ResourceInfo {
-	res_buf: HeapBlock,
+	fixed_info: Option<VS_FIXEDFILEINFO>,
+	translations: Vec<(LANGID, co::CP)>,
+	string_tables: Vec<StringTable>,
 }
 
 impl ResourceInfo {
 	/// Reads and stores the resource data from an executable file or a DLL.
 	#[must_use]
 	pub fn read_from(exe_file: &str) -> SysResult<ResourceInfo> {
-		Ok(Self { res_buf: GetFileVersionInfo(exe_file)? })
+		Self::parse(GetFileVersionInfo(exe_file)?.as_slice())
+	}
+
+	/// Parses the raw bytes of a
+	/// [`VS_VERSIONINFO`](https://learn.microsoft.com/en-us/windows/win32/menurc/vs-versioninfo)
+	/// resource, like the ones returned by
+	/// [`GetFileVersionInfo`](crate::GetFileVersionInfo) or extracted from an
+	/// EXE or a DLL file.
+	///
+	/// This is a pure Rust implementation, no system calls are made.
+	///
+	/// Fails with [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA)
+	/// if the data is malformed.
+	#[must_use]
+	pub fn parse(data: &[u8]) -> SysResult<ResourceInfo> {
+		let root = VerBlock::parse(data)?;
+		if root.key != "VS_VERSION_INFO" {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+
+		let fixed_info = if root.value.is_empty() {
+			None
+		} else if root.value.len() < std::mem::size_of::<VS_FIXEDFILEINFO>()
+			|| root.value[..4] != [0xbd, 0x04, 0xef, 0xfe] // dwSignature
+		{
+			return Err(co::ERROR::INVALID_DATA);
+		} else {
+			Some(unsafe {
+				std::ptr::read_unaligned(root.value.as_ptr() as *const VS_FIXEDFILEINFO)
+			})
+		};
+
+		let mut translations = Vec::<(LANGID, co::CP)>::default();
+		let mut string_tables = Vec::<StringTable>::default();
+
+		for block in root.children.iter() {
+			if block.key.eq_ignore_ascii_case("StringFileInfo") {
+				for table in block.children.iter() {
+					let (lang_id, code_page) = Self::parse_table_key(&table.key)?;
+					string_tables.push(StringTable {
+						lang_id,
+						code_page,
+						strings: table.children.iter()
+							.map(|s| (s.key.clone(), s.value_str()))
+							.collect(),
+					});
+				}
+			} else if block.key.eq_ignore_ascii_case("VarFileInfo") {
+				if let Some(var) = block.child("Translation") {
+					if var.value.len() % 4 != 0 {
+						return Err(co::ERROR::INVALID_DATA);
+					}
+					translations.extend(
+						var.value.chunks_exact(4)
+							.map(|ch4| unsafe {(
+								LANGID::from_raw(u16::from_le_bytes([ch4[0], ch4[1]])),
+								co::CP::from_raw(u16::from_le_bytes([ch4[2], ch4[3]])),
+							)}),
+					);
+				}
+			}
+		}
+
+		Ok(Self { fixed_info, translations, string_tables })
+	}
+
+	fn parse_table_key(key: &str) -> SysResult<(LANGID, co::CP)> {
+		if key.len() != 8 || !key.is_ascii() {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+		let lang_id = u16::from_str_radix(&key[..4], 16)
+			.map_err(|_| co::ERROR::INVALID_DATA)?;
+		let code_page = u16::from_str_radix(&key[4..], 16)
+			.map_err(|_| co::ERROR::INVALID_DATA)?;
+		Ok(unsafe { (LANGID::from_raw(lang_id), co::CP::from_raw(code_page)) })
 	}
 
 	/// Returns an iterator over the language blocks, if any, which are composed
 	/// of a language ID and a code page.
 	///
 	/// These blocks allow retrieval of version information strings in their
-	/// respective languages.
+	/// respective languages. The blocks listed in the `Translation` entry come
+	/// first, followed by any string tables not listed there.
 	#[must_use]
 	pub fn blocks(&self) -> impl Iterator<Item = ResourceInfoBlock> + '_ {
-		unsafe {
-			VarQueryValue::<(LANGID, co::CP)>(
-				self.res_buf.as_slice(),
-				"\\VarFileInfo\\Translation",
-			).ok()
-				.map(|(plangs, sz)|
-					std::slice::from_raw_parts(
-						plangs,
-						sz as usize / std::mem::size_of::<(LANGID, co::CP)>(),
-					)
-				)
-				.unwrap_or(&[])
-				.iter()
-				.map(|(lang_id, code_page)|
-					ResourceInfoBlock {
-						res_info: self,
-						lang_id: *lang_id,
-						code_page: *code_page,
-					}
-				)
-		}
+		self.translations.iter()
+			.map(|(lang_id, code_page)| (*lang_id, *code_page))
+			.chain(
+				self.string_tables.iter()
+					.map(|table| (table.lang_id, table.code_page))
+					.filter(|pair| !self.translations.contains(pair)),
+			)
+			.map(|(lang_id, code_page)|
+				ResourceInfoBlock {
+					res_info: self,
+					lang_id,
+					code_page,
+				}
+			)
 	}
 
 	/// Returns the version information, if any.
 	#[must_use]
-	pub fn version_info(&self) -> Option<&VS_FIXEDFILEINFO> {
-		unsafe {
-			VarQueryValue::<VS_FIXEDFILEINFO>(self.res_buf.as_slice(), "\\")
-				.ok()
-				.map(|(pvsf, _)| &*pvsf)
-		}
+	pub const fn version_info(&self) -> Option<&VS_FIXEDFILEINFO> {
+		self.fixed_info.as_ref()
 	}
 }
 
@@ -108,7 +197,7 @@ impl<'a> ResourceInfoBlock<'a> {
 
 	#[must_use] pub fn comments(&self) -> Option<String> { self.generic_string_info("Comments") }
 	#[must_use] pub fn company_name(&self) -> Option<String> { self.generic_string_info("CompanyName") }
-	#[must_use] pub fn file_description(&self) -> Option<String> { self.generic_string_info("FileDescrition") }
+	#[must_use] pub fn file_description(&self) -> Option<String> { self.generic_string_info("FileDescription") }
 	#[must_use] pub fn file_version(&self) -> Option<String> { self.generic_string_info("FileVersion") }
 	#[must_use] pub fn internal_name(&self) -> Option<String> { self.generic_string_info("InternalName") }
 	#[must_use] pub fn legal_copyright(&self) -> Option<String> { self.generic_string_info("LegalCopyright") }
@@ -119,18 +208,30 @@ impl<'a> ResourceInfoBlock<'a> {
 	#[must_use] pub fn private_build(&self) -> Option<String> { self.generic_string_info("PrivateBuild") }
 	#[must_use] pub fn special_build(&self) -> Option<String> { self.generic_string_info("SpecialBuild") }
 
+	/// Returns an iterator over all the key/value pairs of the string table of
+	/// this block, in the order they are stored.
+	///
+	/// If the block has no string table, the iterator will be empty.
+	#[must_use]
+	pub fn strings(&self) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+		let (lang_id, code_page) = (self.lang_id, self.code_page);
+		self.res_info.string_tables.iter()
+			.filter(move |table| table.lang_id == lang_id && table.code_page == code_page)
+			.flat_map(|table| table.strings.iter())
+			.map(|(key, val)| (key.as_str(), val.as_str()))
+	}
+
+	/// Returns the value of the string with the given key, if any.
+	///
+	/// The search is case-insensitive.
+	#[must_use]
+	pub fn value(&self, key: &str) -> Option<&'a str> {
+		self.strings()
+			.find(|(k, _)| k.eq_ignore_ascii_case(key))
+			.map(|(_, v)| v)
+	}
+
 	fn generic_string_info(&self, info: &str) -> Option<String> {
-		unsafe {
-			VarQueryValue::<u16>(
-				self.res_info.res_buf.as_slice(),
-				&format!("\\StringFileInfo\\{:04x}{:04x}\\{}",
-					u16::from(self.lang_id), u16::from(self.code_page), info),
-			).ok()
-				.map(|(pstr, len)| {
-					WString::from_wchars_slice(
-						std::slice::from_raw_parts(pstr, len as _),
-					).to_string()
-				})
-		}
+		self.value(info).map(|v| v.to_owned())
 	}
 }
@@ -0,0 +1,162 @@
+use crate::co;
+use crate::decl::*;
+
+/// A single node of a
+/// [`VS_VERSIONINFO`](https://learn.microsoft.com/en-us/windows/win32/menurc/vs-versioninfo)
+/// tree, which is also the layout of `StringFileInfo`, `StringTable`,
+/// `String`, `VarFileInfo` and `Var` blocks.
+pub(in crate::version) struct VerBlock<'a> {
+	pub(in crate::version) key: String,
+	pub(in crate::version) value: &'a [u8],
+	pub(in crate::version) children: Vec<VerBlock<'a>>,
+}
+
+/// The strings of a `StringTable` block, identified by a language and a code
+/// page.
+pub(in crate::version) struct StringTable {
+	pub(in crate::version) lang_id: LANGID,
+	pub(in crate::version) code_page: co::CP,
+	pub(in crate::version) strings: Vec<(String, String)>,
+}
+
+impl<'a> VerBlock<'a> {
+	/// Parses the root block of the tree. Any data past the length declared by
+	/// the root block is ignored.
+	pub(in crate::version) fn parse(data: &'a [u8]) -> SysResult<Self> {
+		Self::parse_at(data, 0, data.len(), 0)
+			.map(|(block, _)| block)
+	}
+
+	/// Parses the block which starts at `off` and must end before `limit`,
+	/// returning it along with the offset right past its end. All offsets are
+	/// relative to the beginning of the whole resource, which is how the
+	/// 32-bit alignment is computed. Fails past [`MAX_DEPTH`] nested blocks.
+	fn parse_at(
+		data: &'a [u8],
+		off: usize,
+		limit: usize,
+		depth: usize,
+	) -> SysResult<(Self, usize)>
+	{
+		if depth > MAX_DEPTH {
+			return Err(co::ERROR::INVALID_DATA);
+		} else if off + 6 > limit {
+			return Err(co::ERROR::INVALID_DATA); // not enough room for the header
+		}
+
+		let w_length = read_u16(data, off) as usize;
+		let w_value_length = read_u16(data, off + 2) as usize;
+		let w_type = read_u16(data, off + 4);
+		let end = off + w_length;
+		if w_length < 6 || end > limit || w_type > 1 {
+			return Err(co::ERROR::INVALID_DATA);
+		}
+
+		let mut pos = off + 6;
+		let mut key16 = Vec::<u16>::default();
+		loop {
+			if pos + 2 > end {
+				return Err(co::ERROR::INVALID_DATA); // key without terminating null
+			}
+			let ch = read_u16(data, pos);
+			pos += 2;
+			if ch == 0x0000 {
+				break;
+			}
+			key16.push(ch);
+		}
+		let key = String::from_utf16_lossy(&key16);
+
+		pos = align4(pos).min(end); // some compilers omit the padding at the very end
+		let value_sz = if w_type == 1 { w_value_length * 2 } else { w_value_length };
+		let value = &data[pos..(pos + value_sz).min(end)]; // text lengths are not always accurate
+		pos = align4(pos + value.len()).min(end);
+
+		let mut children = Vec::<Self>::default();
+		while pos < end {
+			if end - pos < 6 || read_u16(data, pos) == 0 { // trailing padding
+				if data[pos..end].iter().all(|b| *b == 0) {
+					break;
+				}
+				return Err(co::ERROR::INVALID_DATA);
+			}
+			let (child, child_end) = Self::parse_at(data, pos, end, depth + 1)?;
+			children.push(child);
+			pos = align4(child_end).min(end);
+		}
+
+		Ok((Self { key, value, children }, end))
+	}
+
+	/// Returns the first child with the given key, case-insensitive.
+	pub(in crate::version) fn child(&self, key: &str) -> Option<&Self> {
+		self.children.iter()
+			.find(|child| child.key.eq_ignore_ascii_case(key))
+	}
+
+	/// Returns the value as a string, stopping at the first null, if any.
+	pub(in crate::version) fn value_str(&self) -> String {
+		let chars = self.value.chunks_exact(2)
+			.map(|ch2| u16::from_le_bytes([ch2[0], ch2[1]]))
+			.take_while(|ch| *ch != 0x0000)
+			.collect::<Vec<_>>();
+		String::from_utf16_lossy(&chars)
+	}
+}
+
+/// Appends a block to the buffer, along with the padding which precedes it.
+/// The children, if any, are written by the given closure.
+///
+/// For text blocks, `value` must contain the terminating null, if any, and
+/// `wValueLength` will be written in chars; otherwise, in bytes.
+pub(in crate::version) fn write_block(
+	buf: &mut Vec<u8>,
+	key: &str,
+	is_text: bool,
+	value: &[u8],
+	write_children: impl FnOnce(&mut Vec<u8>),
+) {
+	pad4(buf);
+	let start = buf.len();
+	let value_len = if is_text { value.len() / 2 } else { value.len() };
+
+	buf.extend_from_slice(&[0, 0]); // wLength, written at the end
+	buf.extend_from_slice(&(value_len as u16).to_le_bytes());
+	buf.extend_from_slice(&(is_text as u16).to_le_bytes());
+	key.encode_utf16()
+		.chain(std::iter::once(0x0000)) // szKey terminating null
+		.for_each(|ch| buf.extend_from_slice(&ch.to_le_bytes()));
+
+	if !value.is_empty() {
+		pad4(buf);
+		buf.extend_from_slice(value);
+	}
+	write_children(buf);
+
+	let w_length = (buf.len() - start) as u16;
+	buf[start..start + 2].copy_from_slice(&w_length.to_le_bytes());
+}
+
+/// Encodes a string as UTF-16 bytes, including the terminating null.
+pub(in crate::version) fn str_to_utf16_bytes(s: &str) -> Vec<u8> {
+	s.encode_utf16()
+		.chain(std::iter::once(0x0000))
+		.flat_map(|ch| ch.to_le_bytes())
+		.collect()
+}
+
+/// Limit of nested blocks. A valid resource has only four levels, down to the
+/// `String` and `Var` blocks.
+const MAX_DEPTH: usize = 16;
+
+fn pad4(buf: &mut Vec<u8>) {
+	buf.resize(align4(buf.len()), 0);
+}
+
+fn read_u16(data: &[u8], off: usize) -> u16 {
+	u16::from_le_bytes([data[off], data[off + 1]])
+}
+
+const fn align4(off: usize) -> usize {
+	(off + 3) & !3
+}
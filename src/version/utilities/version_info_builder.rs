@@ -0,0 +1,147 @@
+use crate::co;
+use crate::decl::*;
+use crate::version::utilities::ver_block::{str_to_utf16_bytes, StringTable, write_block};
+
+/// Builds the binary
+/// [`VS_VERSIONINFO`](https://learn.microsoft.com/en-us/windows/win32/menurc/vs-versioninfo)
+/// data of a version resource, which can then be stamped into an EXE or a DLL
+/// file with
+/// [`HUPDATERSRC::UpdateResource`](crate::prelude::kernel_Hupdatersrc::UpdateResource).
+///
+/// The generated bytes can be read back with
+/// [`ResourceInfo::parse`](crate::ResourceInfo::parse).
+///
+/// # Examples
+///
+/// ```no_run
+/// use winsafe::{self as w, prelude::*, co};
+///
+/// let mut fixed_info = w::VS_FIXEDFILEINFO::default();
+/// fixed_info.set_dwFileVersion([1, 2, 3, 4]);
+/// fixed_info.set_dwProductVersion([1, 2, 3, 4]);
+/// fixed_info.dwFileOS = co::VOS::NT_WINDOWS32;
+/// fixed_info.dwFileType = co::VFT::APP;
+///
+/// let lang_id = w::LANGID::new(co::LANG::ENGLISH, co::SUBLANG::ENGLISH_US);
+///
+/// let ver_data = w::VersionInfoBuilder::new(fixed_info)
+///     .string_table(lang_id, co::CP::UTF16, &[
+///         ("CompanyName", "My Company"),
+///         ("FileVersion", "1.2.3.4"),
+///         ("ProductName", "My Product"),
+///     ])
+///     .translation(lang_id, co::CP::UTF16)
+///     .build()?;
+///
+/// let hupd = w::HUPDATERSRC::BeginUpdateResource("C:\\Temp\\foo.exe", false)?;
+/// hupd.UpdateResource(
+///     w::RtStr::Rt(co::RT::VERSION),
+///     w::IdStr::Id(1),
+///     lang_id,
+///     &ver_data,
+/// )?;
+/// # Ok::<_, winsafe::co::ERROR>(())
+/// ```
+pub struct VersionInfoBuilder {
+	fixed_info: VS_FIXEDFILEINFO,
+	string_tables: Vec<StringTable>,
+	translations: Vec<(LANGID, co::CP)>,
+}
+
+impl VersionInfoBuilder {
+	/// Creates a new builder with the given fixed version information, and no
+	/// string tables or translations.
+	#[must_use]
+	pub const fn new(fixed_info: VS_FIXEDFILEINFO) -> Self {
+		Self {
+			fixed_info,
+			string_tables: Vec::new(),
+			translations: Vec::new(),
+		}
+	}
+
+	/// Adds a string table for the given language and code page, with
+	/// arbitrary key/value pairs, which are written in the given order.
+	///
+	/// If a table with the same language and code page already exists, the
+	/// pairs are appended to it.
+	pub fn string_table(&mut self,
+		lang_id: LANGID,
+		code_page: co::CP,
+		strings: &[(impl AsRef<str>, impl AsRef<str>)],
+	) -> &mut Self
+	{
+		let strings = strings.iter()
+			.map(|(key, val)| (key.as_ref().to_owned(), val.as_ref().to_owned()));
+
+		match self.string_tables.iter_mut()
+			.find(|table| table.lang_id == lang_id && table.code_page == code_page)
+		{
+			Some(table) => table.strings.extend(strings),
+			None => self.string_tables.push(StringTable {
+				lang_id,
+				code_page,
+				strings: strings.collect(),
+			}),
+		}
+		self
+	}
+
+	/// Adds an entry to the `Translation` value of the `VarFileInfo` block.
+	pub fn translation(&mut self, lang_id: LANGID, code_page: co::CP) -> &mut Self {
+		self.translations.push((lang_id, code_page));
+		self
+	}
+
+	/// Serializes the data into the binary `VS_VERSIONINFO` format, with all
+	/// blocks aligned to 32-bit boundaries.
+	///
+	/// Fails with [`co::ERROR::INVALID_DATA`](crate::co::ERROR::INVALID_DATA)
+	/// if the data exceeds the 64 KB limit of the format.
+	#[must_use]
+	pub fn build(&self) -> SysResult<Vec<u8>> {
+		let fixed_info = unsafe {
+			std::slice::from_raw_parts(
+				&self.fixed_info as *const _ as *const u8,
+				std::mem::size_of::<VS_FIXEDFILEINFO>(),
+			)
+		};
+
+		let mut buf = Vec::<u8>::default();
+		write_block(&mut buf, "VS_VERSION_INFO", false, fixed_info, |buf| {
+			if !self.string_tables.is_empty() {
+				write_block(buf, "StringFileInfo", true, &[], |buf| {
+					for table in self.string_tables.iter() {
+						let table_key = format!("{:04x}{:04x}",
+							u16::from(table.lang_id), u16::from(table.code_page));
+						write_block(buf, &table_key, true, &[], |buf| {
+							for (key, val) in table.strings.iter() {
+								write_block(buf, key, true, &str_to_utf16_bytes(val), |_| {});
+							}
+						});
+					}
+				});
+			}
+
+			if !self.translations.is_empty() {
+				write_block(buf, "VarFileInfo", true, &[], |buf| {
+					let translations = self.translations.iter()
+						.flat_map(|(lang_id, code_page)| {
+							let mut pair = [0u8; 4];
+							pair[..2].copy_from_slice(&u16::from(*lang_id).to_le_bytes());
+							pair[2..].copy_from_slice(&u16::from(*code_page).to_le_bytes());
+							pair
+						})
+						.collect::<Vec<_>>();
+					write_block(buf, "Translation", false, &translations, |_| {});
+				});
+			}
+		});
+
+		if buf.len() > 0xffff {
+			Err(co::ERROR::INVALID_DATA) // wLength fields would overflow
+		} else {
+			Ok(buf)
+		}
+	}
+}